@@ -1,8 +1,8 @@
 extern crate rocksdb;
 
 use super::rocksdb::Error as RocksError;
-use super::rocksdb::{Options, DB};
-use super::{ClientDB, DBError, DBValue};
+use super::rocksdb::{IteratorMode, Options, DB};
+use super::{ClientDB, ColumnStats, DBError, DBValue};
 use std::fs;
 use std::path::Path;
 
@@ -28,9 +28,9 @@ impl DiskDB {
          */
         let mut options = Options::default();
         options.create_if_missing(true);
+        options.create_missing_column_families(true);
 
-        // TODO: ensure that columns are created (and remove
-        // the dead_code allow)
+        // TODO: remove the dead_code allow on `create_col` if it remains unused.
 
         /*
          * Initialise the path
@@ -132,6 +132,26 @@ impl ClientDB for DiskDB {
             }
         }
     }
+
+    /// Iterate all keys in some column, summing their sizes.
+    ///
+    /// Corresponds to the `iterator_cf()` method on the RocksDB API.
+    fn column_stats(&self, col: &str) -> Result<ColumnStats, DBError> {
+        match self.db.cf_handle(col) {
+            None => Err(DBError {
+                message: "Unknown column".to_string(),
+            }),
+            Some(handle) => {
+                let mut stats = ColumnStats::default();
+                for (key, value) in self.db.iterator_cf(handle, IteratorMode::Start)? {
+                    stats.key_count += 1;
+                    stats.key_bytes += key.len() as u64;
+                    stats.value_bytes += value.len() as u64;
+                }
+                Ok(stats)
+            }
+        }
+    }
 }
 
 #[cfg(test)]
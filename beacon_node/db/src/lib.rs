@@ -1,4 +1,3 @@
-extern crate blake2_rfc as blake2;
 extern crate bls;
 extern crate rocksdb;
 
@@ -11,4 +10,4 @@ use self::stores::COLUMNS;
 
 pub use self::disk_db::DiskDB;
 pub use self::memory_db::MemoryDB;
-pub use self::traits::{ClientDB, DBError, DBValue};
+pub use self::traits::{ClientDB, ColumnStats, DBError, DBValue};
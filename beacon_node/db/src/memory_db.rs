@@ -1,19 +1,17 @@
-use super::blake2::blake2b::blake2b;
 use super::COLUMNS;
-use super::{ClientDB, DBError, DBValue};
-use std::collections::{HashMap, HashSet};
+use super::{ClientDB, ColumnStats, DBError, DBValue};
+use std::collections::HashMap;
 use std::sync::RwLock;
 
 type DBHashMap = HashMap<Vec<u8>, Vec<u8>>;
-type ColumnHashSet = HashSet<String>;
+type ColumnHashMap = HashMap<String, DBHashMap>;
 
 /// An in-memory database implementing the ClientDB trait.
 ///
 /// It is not particularily optimized, it exists for ease and speed of testing. It's not expected
 /// this DB would be used outside of tests.
 pub struct MemoryDB {
-    db: RwLock<DBHashMap>,
-    known_columns: RwLock<ColumnHashSet>,
+    db: RwLock<ColumnHashMap>,
 }
 
 impl MemoryDB {
@@ -22,88 +20,75 @@ impl MemoryDB {
     /// All columns must be supplied initially, you will get an error if you try to access a column
     /// that was not declared here. This condition is enforced artificially to simulate RocksDB.
     pub fn open() -> Self {
-        let db: DBHashMap = HashMap::new();
-        let mut known_columns: ColumnHashSet = HashSet::new();
+        let mut db: ColumnHashMap = HashMap::new();
         for col in &COLUMNS {
-            known_columns.insert(col.to_string());
+            db.insert(col.to_string(), HashMap::new());
         }
         Self {
             db: RwLock::new(db),
-            known_columns: RwLock::new(known_columns),
         }
     }
+}
 
-    /// Hashes a key and a column name in order to get a unique key for the supplied column.
-    fn get_key_for_col(col: &str, key: &[u8]) -> Vec<u8> {
-        blake2b(32, col.as_bytes(), key).as_bytes().to_vec()
+fn unknown_column() -> DBError {
+    DBError {
+        message: "Unknown column".to_string(),
     }
 }
 
 impl ClientDB for MemoryDB {
     /// Get the value of some key from the database. Returns `None` if the key does not exist.
     fn get(&self, col: &str, key: &[u8]) -> Result<Option<DBValue>, DBError> {
-        // Panic if the DB locks are poisoned.
+        // Panic if the DB lock is poisoned.
         let db = self.db.read().unwrap();
-        let known_columns = self.known_columns.read().unwrap();
-
-        if known_columns.contains(&col.to_string()) {
-            let column_key = MemoryDB::get_key_for_col(col, key);
-            Ok(db.get(&column_key).and_then(|val| Some(val.clone())))
-        } else {
-            Err(DBError {
-                message: "Unknown column".to_string(),
-            })
-        }
+        let column = db.get(col).ok_or_else(unknown_column)?;
+
+        Ok(column.get(key).cloned())
     }
 
     /// Puts a key in the database.
     fn put(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), DBError> {
-        // Panic if the DB locks are poisoned.
+        // Panic if the DB lock is poisoned.
         let mut db = self.db.write().unwrap();
-        let known_columns = self.known_columns.read().unwrap();
-
-        if known_columns.contains(&col.to_string()) {
-            let column_key = MemoryDB::get_key_for_col(col, key);
-            db.insert(column_key, val.to_vec());
-            Ok(())
-        } else {
-            Err(DBError {
-                message: "Unknown column".to_string(),
-            })
-        }
+        let column = db.get_mut(col).ok_or_else(unknown_column)?;
+
+        column.insert(key.to_vec(), val.to_vec());
+        Ok(())
     }
 
     /// Return true if some key exists in some column.
     fn exists(&self, col: &str, key: &[u8]) -> Result<bool, DBError> {
-        // Panic if the DB locks are poisoned.
+        // Panic if the DB lock is poisoned.
         let db = self.db.read().unwrap();
-        let known_columns = self.known_columns.read().unwrap();
-
-        if known_columns.contains(&col.to_string()) {
-            let column_key = MemoryDB::get_key_for_col(col, key);
-            Ok(db.contains_key(&column_key))
-        } else {
-            Err(DBError {
-                message: "Unknown column".to_string(),
-            })
-        }
+        let column = db.get(col).ok_or_else(unknown_column)?;
+
+        Ok(column.contains_key(key))
     }
 
     /// Delete some key from the database.
     fn delete(&self, col: &str, key: &[u8]) -> Result<(), DBError> {
-        // Panic if the DB locks are poisoned.
+        // Panic if the DB lock is poisoned.
         let mut db = self.db.write().unwrap();
-        let known_columns = self.known_columns.read().unwrap();
-
-        if known_columns.contains(&col.to_string()) {
-            let column_key = MemoryDB::get_key_for_col(col, key);
-            db.remove(&column_key);
-            Ok(())
-        } else {
-            Err(DBError {
-                message: "Unknown column".to_string(),
-            })
-        }
+        let column = db.get_mut(col).ok_or_else(unknown_column)?;
+
+        column.remove(key);
+        Ok(())
+    }
+
+    /// Count the keys in some column and sum their sizes.
+    fn column_stats(&self, col: &str) -> Result<ColumnStats, DBError> {
+        // Panic if the DB lock is poisoned.
+        let db = self.db.read().unwrap();
+        let column = db.get(col).ok_or_else(unknown_column)?;
+
+        Ok(column
+            .iter()
+            .fold(ColumnStats::default(), |mut stats, (key, value)| {
+                stats.key_count += 1;
+                stats.key_bytes += key.len() as u64;
+                stats.value_bytes += value.len() as u64;
+                stats
+            }))
     }
 }
 
@@ -194,6 +179,29 @@ mod tests {
         assert_eq!(false, db.exists(col_b, "dogs".as_bytes()).unwrap());
     }
 
+    #[test]
+    fn test_memorydb_column_stats() {
+        let col_a: &str = BLOCKS_DB_COLUMN;
+        let col_b: &str = VALIDATOR_DB_COLUMN;
+
+        let db = MemoryDB::open();
+
+        db.put(col_a, "cats".as_bytes(), "lol".as_bytes()).unwrap();
+        db.put(col_a, "dogs".as_bytes(), "lolol".as_bytes())
+            .unwrap();
+        db.put(col_b, "cats".as_bytes(), "lol".as_bytes()).unwrap();
+
+        let stats = db.column_stats(col_a).unwrap();
+        assert_eq!(stats.key_count, 2);
+        assert_eq!(stats.key_bytes, 8);
+        assert_eq!(stats.value_bytes, 8);
+
+        db.delete(col_b, "cats".as_bytes()).unwrap();
+        assert_eq!(db.column_stats(col_b).unwrap(), ColumnStats::default());
+
+        assert!(db.column_stats("ColumnX").is_err());
+    }
+
     #[test]
     fn test_memorydb_threading() {
         let col_name: &str = BLOCKS_DB_COLUMN;
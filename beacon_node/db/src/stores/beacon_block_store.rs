@@ -1,8 +1,8 @@
 use super::BLOCKS_DB_COLUMN as DB_COLUMN;
 use super::{ClientDB, DBError};
-use ssz::Decodable;
 use std::sync::Arc;
-use types::{readers::BeaconBlockReader, BeaconBlock, Hash256};
+use types::readers::{BeaconBlockReader, SszBeaconBlockBuf};
+use types::Hash256;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum BeaconBlockAtSlotError {
@@ -28,18 +28,14 @@ impl<T: ClientDB> BeaconBlockStore<T> {
 
     /// Retuns an object implementing `BeaconBlockReader`, or `None` (if hash not known).
     ///
-    /// Note: Presently, this function fully deserializes a `BeaconBlock` and returns that. In the
-    /// future, it would be ideal to return an object capable of reading directly from serialized
-    /// SSZ bytes.
+    /// The reader reads fields directly from the stored SSZ bytes, so the block is only fully
+    /// deserialized by `into_beacon_block`.
     pub fn get_reader(&self, hash: &Hash256) -> Result<Option<impl BeaconBlockReader>, DBError> {
         match self.get(&hash)? {
             None => Ok(None),
-            Some(ssz) => {
-                let (block, _) = BeaconBlock::ssz_decode(&ssz, 0).map_err(|_| DBError {
-                    message: "Bad BeaconBlock SSZ.".to_string(),
-                })?;
-                Ok(Some(block))
-            }
+            Some(ssz) => SszBeaconBlockBuf::from_vec(ssz)
+                .map(Some)
+                .map_err(|_| bad_ssz()),
         }
     }
 
@@ -51,7 +47,9 @@ impl<T: ClientDB> BeaconBlockStore<T> {
     /// This function will read each block down the chain until it finds a block with the given
     /// slot number. If the slot is skipped, the function will return None.
     ///
-    /// If a block is found, a tuple of (block_hash, serialized_block) is returned.
+    /// If a block is found, a tuple of (block_hash, block) is returned.
+    ///
+    /// Each block is read from its SSZ bytes, so none are deserialized.
     ///
     /// Note: this function uses a loop instead of recursion as the compiler is over-strict when it
     /// comes to recursion and the `impl Trait` pattern. See:
@@ -64,21 +62,28 @@ impl<T: ClientDB> BeaconBlockStore<T> {
         let mut current_hash = *head_hash;
 
         loop {
-            if let Some(block_reader) = self.get_reader(&current_hash)? {
-                if block_reader.slot() == slot {
-                    break Ok(Some((current_hash, block_reader)));
-                } else if block_reader.slot() < slot {
-                    break Ok(None);
-                } else {
-                    current_hash = block_reader.parent_root();
-                }
+            let ssz = self
+                .get(&current_hash)?
+                .ok_or(BeaconBlockAtSlotError::UnknownBeaconBlock)?;
+            let block_reader = SszBeaconBlockBuf::from_vec(ssz).map_err(|_| bad_ssz())?;
+
+            if block_reader.slot() == slot {
+                break Ok(Some((current_hash, block_reader)));
+            } else if block_reader.slot() < slot {
+                break Ok(None);
             } else {
-                break Err(BeaconBlockAtSlotError::UnknownBeaconBlock);
+                current_hash = block_reader.parent_root();
             }
         }
     }
 }
 
+fn bad_ssz() -> DBError {
+    DBError {
+        message: "Bad BeaconBlock SSZ.".to_string(),
+    }
+}
+
 impl From<DBError> for BeaconBlockAtSlotError {
     fn from(e: DBError) -> Self {
         BeaconBlockAtSlotError::DBError(e.message)
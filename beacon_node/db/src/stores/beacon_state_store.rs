@@ -1,8 +1,8 @@
 use super::STATES_DB_COLUMN as DB_COLUMN;
 use super::{ClientDB, DBError};
-use ssz::Decodable;
 use std::sync::Arc;
-use types::{readers::BeaconStateReader, BeaconState, Hash256};
+use types::readers::{BeaconStateReader, SszBeaconStateBuf};
+use types::Hash256;
 
 pub struct BeaconStateStore<T>
 where
@@ -21,18 +21,16 @@ impl<T: ClientDB> BeaconStateStore<T> {
 
     /// Retuns an object implementing `BeaconStateReader`, or `None` (if hash not known).
     ///
-    /// Note: Presently, this function fully deserializes a `BeaconState` and returns that. In the
-    /// future, it would be ideal to return an object capable of reading directly from serialized
-    /// SSZ bytes.
+    /// The reader reads fields directly from the stored SSZ bytes, so the state is only fully
+    /// deserialized by `into_beacon_state`.
     pub fn get_reader(&self, hash: &Hash256) -> Result<Option<impl BeaconStateReader>, DBError> {
         match self.get(&hash)? {
             None => Ok(None),
-            Some(ssz) => {
-                let (state, _) = BeaconState::ssz_decode(&ssz, 0).map_err(|_| DBError {
+            Some(ssz) => SszBeaconStateBuf::from_vec(ssz)
+                .map(Some)
+                .map_err(|_| DBError {
                     message: "Bad State SSZ.".to_string(),
-                })?;
-                Ok(Some(state))
-            }
+                }),
         }
     }
 }
@@ -45,7 +43,7 @@ mod tests {
     use ssz::ssz_encode;
     use std::sync::Arc;
     use types::test_utils::{SeedableRng, TestRandom, XorShiftRng};
-    use types::Hash256;
+    use types::{BeaconState, Hash256};
 
     test_crud_for_store!(BeaconStateStore, DB_COLUMN);
 
@@ -61,6 +59,7 @@ mod tests {
         store.put(&state_root, &ssz_encode(&state)).unwrap();
 
         let reader = store.get_reader(&state_root).unwrap().unwrap();
+        assert_eq!(reader.slot(), state.slot);
         let decoded = reader.into_beacon_state().unwrap();
 
         assert_eq!(state, decoded);
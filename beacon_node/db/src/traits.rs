@@ -11,6 +11,14 @@ impl DBError {
     }
 }
 
+/// Summary statistics for the contents of a single column.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ColumnStats {
+    pub key_count: u64,
+    pub key_bytes: u64,
+    pub value_bytes: u64,
+}
+
 /// A generic database to be used by the "client' (i.e.,
 /// the lighthouse blockchain client).
 ///
@@ -25,4 +33,9 @@ pub trait ClientDB: Sync + Send {
     fn exists(&self, col: &str, key: &[u8]) -> Result<bool, DBError>;
 
     fn delete(&self, col: &str, key: &[u8]) -> Result<(), DBError>;
+
+    /// Count the keys in some column and the total size of its keys and values, in bytes.
+    ///
+    /// Note: this iterates the entire column and may be slow for large databases.
+    fn column_stats(&self, col: &str) -> Result<ColumnStats, DBError>;
 }
@@ -1,5 +1,5 @@
-use super::{BeaconChain, ClientDB, DBError, SlotClock};
-use slot_clock::TestingSlotClockError;
+use super::{BeaconChain, ClientDB, DBError, InfoError, SlotClock};
+use slot_clock::{SystemTimeSlotClockError, TestingSlotClockError};
 use ssz::{ssz_encode, Encodable};
 use types::{readers::BeaconBlockReader, Hash256};
 
@@ -8,6 +8,11 @@ pub enum Outcome {
     FutureSlot,
     Processed,
 
+    UnknownParentBlock,
+    ProposerUnknown,
+    InvalidBlockSignature,
+    InvalidRandaoReveal,
+
     NewCanonicalBlock,
     NewReorgBlock,
     NewForkBlock,
@@ -16,8 +21,10 @@ pub enum Outcome {
 #[derive(Debug, PartialEq)]
 pub enum Error {
     DBError(String),
+    InfoError(InfoError),
     NotImplemented,
     PresentSlotIsNone,
+    SlotClockError(String),
 }
 
 impl<T, U> BeaconChain<T, U>
@@ -42,8 +49,44 @@ where
             return Ok((Outcome::FutureSlot, block_root));
         }
 
-        // TODO: block processing has been removed.
-        // https://github.com/sigp/lighthouse/issues/98
+        // The block signatures are verified against the state of the parent block.
+        //
+        // TODO: the committees (and therefore the proposer) should be those of the epoch of the
+        // block, which may differ from that of the parent state.
+        let parent_state = match self.block_by_root(&block.parent_root())? {
+            Some(parent_block) => self.state_by_root(&parent_block.state_root)?,
+            None => return Ok((Outcome::UnknownParentBlock, block_root)),
+        };
+
+        let proposer = match self.block_proposer_index(&parent_state, block.slot())? {
+            Some(index) => match parent_state.validator_registry.get(index) {
+                Some(validator) => validator.pubkey.clone(),
+                None => return Ok((Outcome::ProposerUnknown, block_root)),
+            },
+            None => return Ok((Outcome::ProposerUnknown, block_root)),
+        };
+
+        let fork = &parent_state.fork_data;
+
+        let proposal_root = block.proposal_root(self.spec.beacon_chain_shard_number);
+        let proposal_domain = fork.get_domain(block.slot(), self.spec.domain_proposal);
+        if !block
+            .signature()
+            .verify_with_domain(&proposal_root, proposal_domain, &proposer)
+        {
+            return Ok((Outcome::InvalidBlockSignature, block_root));
+        }
+
+        let epoch = block.slot() / self.spec.epoch_length;
+        let randao_domain = fork.get_domain(block.slot(), self.spec.domain_randao);
+        if !block
+            .randao_reveal()
+            .verify_with_domain(&ssz_encode(&epoch), randao_domain, &proposer)
+        {
+            return Ok((Outcome::InvalidRandaoReveal, block_root));
+        }
+
+        // TODO: the remainder of block processing has been removed.
 
         // Update leaf blocks.
         self.block_store.put(&block_root, &ssz_encode(block)[..])?;
@@ -65,8 +108,20 @@ impl From<DBError> for Error {
     }
 }
 
+impl From<InfoError> for Error {
+    fn from(e: InfoError) -> Error {
+        Error::InfoError(e)
+    }
+}
+
 impl From<TestingSlotClockError> for Error {
     fn from(_: TestingSlotClockError) -> Error {
         unreachable!(); // Testing clock never throws an error.
     }
 }
+
+impl From<SystemTimeSlotClockError> for Error {
+    fn from(e: SystemTimeSlotClockError) -> Error {
+        Error::SlotClockError(format!("{:?}", e))
+    }
+}
@@ -1,5 +1,5 @@
 use super::{BeaconChain, ClientDB, DBError, SlotClock};
-use slot_clock::TestingSlotClockError;
+use slot_clock::{SystemTimeSlotClockError, TestingSlotClockError};
 use types::{
     readers::{BeaconBlockReader, BeaconStateReader},
     BeaconBlock, BeaconState, Hash256,
@@ -9,6 +9,7 @@ use types::{
 pub enum Error {
     DBError(String),
     PresentSlotIsNone,
+    SlotClockError(String),
 }
 
 impl<T, U> BeaconChain<T, U>
@@ -46,6 +47,7 @@ where
             state_root: Hash256::zero(), // Updated after the state is calculated.
             ..parent_block
         };
+        block.body.exits = self.exits_for_block();
 
         let parent_state = parent_state_reader
             .into_beacon_state()
@@ -54,7 +56,7 @@ where
             slot: present_slot,
             ..parent_state
         };
-        let state_root = state.canonical_root();
+        let state_root = state.cached_canonical_root(&mut self.state_tree_hash_cache);
 
         block.state_root = state_root;
 
@@ -73,3 +75,9 @@ impl From<TestingSlotClockError> for Error {
         unreachable!(); // Testing clock never throws an error.
     }
 }
+
+impl From<SystemTimeSlotClockError> for Error {
+    fn from(e: SystemTimeSlotClockError) -> Error {
+        Error::SlotClockError(format!("{:?}", e))
+    }
+}
@@ -0,0 +1,67 @@
+use super::{BeaconChain, ClientDB, InfoError, SlotClock};
+use types::Exit;
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    /// The exit was verified and added to the pool for inclusion in a block.
+    Accepted,
+
+    UnknownValidator,
+    AlreadyExited,
+    InvalidSignature,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    InfoError(InfoError),
+}
+
+impl<T, U> BeaconChain<T, U>
+where
+    T: ClientDB,
+    U: SlotClock,
+{
+    /// Verifies a voluntary `exit` against the head state and, if it is valid, adds it to the pool
+    /// of exits awaiting inclusion in a block.
+    ///
+    /// The signature is verified against the pubkey of the exiting validator, with the exit domain
+    /// of the fork of the head state. A later exit for the same validator replaces any earlier one.
+    pub fn process_exit(&mut self, exit: Exit) -> Result<Outcome, Error> {
+        let state = self.head_state()?;
+
+        let validator = match state.validator_registry.get(exit.validator_index as usize) {
+            Some(validator) => validator,
+            None => return Ok(Outcome::UnknownValidator),
+        };
+        if validator.exit_slot != self.spec.far_future_slot {
+            return Ok(Outcome::AlreadyExited);
+        }
+
+        let domain = state.fork_data.get_domain(exit.slot, self.spec.domain_exit);
+        if !exit
+            .signature
+            .verify_with_domain(&exit.signing_root(), domain, &validator.pubkey)
+        {
+            return Ok(Outcome::InvalidSignature);
+        }
+
+        self.exit_pool.insert(exit.validator_index, exit);
+        Ok(Outcome::Accepted)
+    }
+
+    /// Returns up to `max_exits` exits from the pool, in order of validator index, for inclusion
+    /// in a block.
+    pub fn exits_for_block(&self) -> Vec<Exit> {
+        self.exit_pool
+            .values()
+            .take(self.spec.max_exits as usize)
+            .cloned()
+            .collect()
+    }
+}
+
+impl From<InfoError> for Error {
+    fn from(e: InfoError) -> Error {
+        Error::InfoError(e)
+    }
+}
@@ -0,0 +1,309 @@
+use super::{BeaconChain, ClientDB, DBError, SlotClock};
+use db::stores::BeaconBlockAtSlotError;
+use std::collections::HashSet;
+use types::{
+    readers::{BeaconBlockReader, BeaconStateReader, SszBeaconBlock},
+    BeaconBlock, BeaconState, Hash256, ShardCommittee,
+};
+use validator_shuffling::{shard_and_committees_for_cycle, ValidatorAssignmentError};
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    DBError(String),
+    MissingBeaconBlock(Hash256),
+    MissingBeaconState(Hash256),
+    InvalidBeaconBlock(Hash256),
+    InvalidBeaconState(Hash256),
+    ValidatorAssignmentError(ValidatorAssignmentError),
+}
+
+/// A leaf of the block tree, along with the length of its chain.
+#[derive(Debug, PartialEq)]
+pub struct LeafBlock {
+    pub block_root: Hash256,
+    pub slot: u64,
+    /// The number of blocks in the chain ending at this leaf (i.e., the leaf and all of its known
+    /// ancestors).
+    pub chain_length: u64,
+    pub is_canonical: bool,
+}
+
+impl<T, U> BeaconChain<T, U>
+where
+    T: ClientDB,
+    U: SlotClock,
+{
+    /// Returns the root of the block at the head of the canonical chain, along with the block
+    /// itself.
+    pub fn head_block(&self) -> Result<(Hash256, BeaconBlock), Error> {
+        let root = self.canonical_leaf_block;
+        let block = self
+            .block_by_root(&root)?
+            .ok_or_else(|| Error::MissingBeaconBlock(root))?;
+        Ok((root, block))
+    }
+
+    /// Returns the `BeaconState` referenced by the block at the head of the canonical chain.
+    pub fn head_state(&self) -> Result<BeaconState, Error> {
+        let (_, block) = self.head_block()?;
+        self.state_by_root(&block.state_root)
+    }
+
+    /// Returns the state with the given root, returning an error if it is unknown.
+    pub fn state_by_root(&self, root: &Hash256) -> Result<BeaconState, Error> {
+        self.state_store
+            .get_reader(root)?
+            .ok_or_else(|| Error::MissingBeaconState(*root))?
+            .into_beacon_state()
+            .ok_or_else(|| Error::InvalidBeaconState(*root))
+    }
+
+    /// Returns the block with the given root, or `None` if it is unknown.
+    pub fn block_by_root(&self, root: &Hash256) -> Result<Option<BeaconBlock>, Error> {
+        match self.block_store.get_reader(root)? {
+            None => Ok(None),
+            Some(reader) => reader
+                .into_beacon_block()
+                .map(Some)
+                .ok_or_else(|| Error::InvalidBeaconBlock(*root)),
+        }
+    }
+
+    /// Returns the root and block at `slot` in the canonical chain, or `None` if the slot was
+    /// skipped or is later than the head.
+    pub fn block_at_slot(&self, slot: u64) -> Result<Option<(Hash256, BeaconBlock)>, Error> {
+        match self
+            .block_store
+            .block_at_slot(&self.canonical_leaf_block, slot)?
+        {
+            None => Ok(None),
+            Some((root, reader)) => reader
+                .into_beacon_block()
+                .map(|block| Some((root, block)))
+                .ok_or_else(|| Error::InvalidBeaconBlock(root)),
+        }
+    }
+
+    /// Returns the root of the block upon which the shuffling of `epoch` depends: the latest block
+    /// in the canonical chain prior to the start of the previous epoch, from which the shuffling
+    /// is seeded.
+    ///
+    /// Returns `None` if the head is earlier than the end of that period, as the dependent block
+    /// may yet be produced.
+    pub fn shuffling_dependent_root(&self, epoch: u64) -> Result<Option<Hash256>, Error> {
+        let boundary = epoch.saturating_sub(1) * self.spec.epoch_length;
+        let mut root = self.canonical_leaf_block;
+        let mut block = self.block_reader(&root)?;
+        if block.slot() + 1 < boundary {
+            return Ok(None);
+        }
+
+        while block.slot() >= boundary && block.slot() > self.spec.genesis_slot {
+            root = block.parent_root();
+            block = self.block_reader(&root)?;
+        }
+        Ok(Some(root))
+    }
+
+    /// Returns a reader over the stored block with the given root, returning an error if it is
+    /// unknown.
+    fn block_reader(&self, root: &Hash256) -> Result<impl BeaconBlockReader, Error> {
+        self.block_store
+            .get_reader(root)?
+            .ok_or_else(|| Error::MissingBeaconBlock(*root))
+    }
+
+    /// Returns each leaf of the block tree with the length of its chain, sorted by descending
+    /// chain length.
+    pub fn leaf_blocks_with_chain_lengths(&self) -> Result<Vec<LeafBlock>, Error> {
+        let mut leaves = Vec::with_capacity(self.leaf_blocks.len());
+
+        for root in &self.leaf_blocks {
+            let slot = self.block_reader(root)?.slot();
+
+            let mut chain_length = 0;
+            let mut current = *root;
+            while let Some(ssz) = self.block_store.get(&current)? {
+                let block = SszBeaconBlock::from_slice(&ssz)
+                    .map_err(|_| Error::InvalidBeaconBlock(current))?;
+                chain_length += 1;
+                if block.slot() <= self.spec.genesis_slot {
+                    break;
+                }
+                current = block.parent_root();
+            }
+
+            leaves.push(LeafBlock {
+                block_root: *root,
+                slot,
+                chain_length,
+                is_canonical: *root == self.canonical_leaf_block,
+            });
+        }
+
+        leaves.sort_by(|a, b| b.chain_length.cmp(&a.chain_length));
+        Ok(leaves)
+    }
+
+    /// Returns the present slot according to the slot clock, or `None` if it is unknown (e.g.,
+    /// prior to genesis or if the clock failed).
+    pub fn present_slot(&self) -> Option<u64> {
+        self.slot_clock.present_slot().ok().and_then(|slot| slot)
+    }
+
+    /// Returns `true` if the head of the canonical chain is more than an epoch behind the present
+    /// slot.
+    ///
+    /// Note: there is not yet a sync process; this simply indicates that the node is not aware of
+    /// recent blocks.
+    pub fn is_syncing(&self) -> Result<bool, Error> {
+        let (_, head) = self.head_block()?;
+        match self.present_slot() {
+            Some(present_slot) => Ok(head.slot + self.spec.epoch_length < present_slot),
+            None => Ok(false),
+        }
+    }
+
+    /// Returns the committee assignments for each slot of the epoch of the given `state`.
+    ///
+    /// The returned `Vec` is indexed by the slot offset into the epoch.
+    pub fn committees_for_state_epoch(
+        &self,
+        state: &BeaconState,
+    ) -> Result<Vec<Vec<ShardCommittee>>, Error> {
+        Ok(shard_and_committees_for_cycle(
+            &state.current_epoch_randao_mix[..],
+            &state.validator_registry,
+            state.current_epoch_start_shard as u16,
+            &self.spec,
+        )?)
+    }
+
+    /// Returns the index of the validator expected to propose a block at `slot`, according to the
+    /// committees of `state`. Returns `None` if no validator is assigned to the slot.
+    ///
+    /// The proposer is chosen from the first committee of the slot, as per
+    /// `get_beacon_proposer_index` in the spec.
+    pub fn block_proposer_index(
+        &self,
+        state: &BeaconState,
+        slot: u64,
+    ) -> Result<Option<usize>, Error> {
+        let committees = self.committees_for_state_epoch(state)?;
+        Ok(self.proposer_from_committees(&committees, slot))
+    }
+
+    /// Returns those of `indices` which were live during `epoch`: the proposer of each block of
+    /// the epoch in the canonical chain, and the participants of each attestation to a slot of
+    /// the epoch which has been included in the canonical chain.
+    ///
+    /// Returns `None` if the epoch has not yet ended or the node is syncing, as validators may yet
+    /// be seen live.
+    ///
+    /// Note: attestations are not yet processed outside of blocks, so only those included in a
+    /// block are seen. Committees are those of the state of the first block of the epoch or, if
+    /// every slot of the epoch was skipped, of the last block prior to it.
+    pub fn live_validators(&self, epoch: u64, indices: &[u64]) -> Result<Option<Vec<u64>>, Error> {
+        let start = epoch * self.spec.epoch_length;
+        let end = start + self.spec.epoch_length;
+        match self.present_slot() {
+            Some(present_slot) if present_slot >= end && !self.is_syncing()? => {}
+            _ => return Ok(None),
+        }
+
+        let mut proposal_slots = vec![];
+        let mut attestations = vec![];
+        let (_, mut block) = self.head_block()?;
+        let epoch_state_root = loop {
+            attestations.extend(
+                block
+                    .body
+                    .attestations
+                    .iter()
+                    .filter(|a| a.data.slot >= start && a.data.slot < end)
+                    .cloned(),
+            );
+
+            // Blocks prior to the epoch cannot contain its messages.
+            if block.slot < start || block.slot <= self.spec.genesis_slot {
+                break block.state_root;
+            }
+            if block.slot < end {
+                proposal_slots.push(block.slot);
+            }
+
+            let parent = self
+                .block_by_root(&block.parent_root)?
+                .ok_or_else(|| Error::MissingBeaconBlock(block.parent_root))?;
+            if block.slot < end && parent.slot < start {
+                break block.state_root;
+            }
+            block = parent;
+        };
+
+        let state = self.state_by_root(&epoch_state_root)?;
+        let committees = self.committees_for_state_epoch(&state)?;
+
+        let mut live = HashSet::new();
+        for slot in proposal_slots {
+            live.extend(self.proposer_from_committees(&committees, slot));
+        }
+        for attestation in attestations {
+            let offset = (attestation.data.slot % self.spec.epoch_length) as usize;
+            let committee = committees.get(offset).and_then(|slot_committees| {
+                slot_committees
+                    .iter()
+                    .find(|c| c.shard == attestation.data.shard)
+            });
+            if let Some(committee) = committee {
+                live.extend(
+                    committee
+                        .committee
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| attestation.aggregation_bitfield.get(*i).unwrap_or(false))
+                        .map(|(_, validator)| *validator),
+                );
+            }
+        }
+
+        Ok(Some(
+            indices
+                .iter()
+                .filter(|index| live.contains(&(**index as usize)))
+                .cloned()
+                .collect(),
+        ))
+    }
+
+    /// Returns the proposer of `slot`, given the `committees` of its epoch.
+    fn proposer_from_committees(&self, committees: &[Vec<ShardCommittee>], slot: u64) -> Option<usize> {
+        let offset = (slot % self.spec.epoch_length) as usize;
+        committees
+            .get(offset)
+            .and_then(|slot_committees| slot_committees.first())
+            .filter(|first| !first.committee.is_empty())
+            .map(|first| first.committee[slot as usize % first.committee.len()])
+    }
+}
+
+impl From<DBError> for Error {
+    fn from(e: DBError) -> Error {
+        Error::DBError(e.message)
+    }
+}
+
+impl From<BeaconBlockAtSlotError> for Error {
+    fn from(e: BeaconBlockAtSlotError) -> Error {
+        match e {
+            BeaconBlockAtSlotError::DBError(message) => Error::DBError(message),
+            other => Error::DBError(format!("{:?}", other)),
+        }
+    }
+}
+
+impl From<ValidatorAssignmentError> for Error {
+    fn from(e: ValidatorAssignmentError) -> Error {
+        Error::ValidatorAssignmentError(e)
+    }
+}
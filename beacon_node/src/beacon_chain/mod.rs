@@ -1,5 +1,8 @@
 mod block_processing;
 mod block_production;
+mod exit_processing;
+mod info;
+mod prune;
 
 use db::{
     stores::{BeaconBlockStore, BeaconStateStore},
@@ -9,11 +12,14 @@ use genesis::{genesis_beacon_block, genesis_beacon_state, GenesisError};
 use slot_clock::SlotClock;
 use spec::ChainSpec;
 use ssz::ssz_encode;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
-use types::Hash256;
+use types::{BeaconStateTreeHashCache, Exit, Hash256};
 
-pub use crate::block_processing::Outcome as BlockProcessingOutcome;
+pub use self::block_processing::Outcome as BlockProcessingOutcome;
+pub use self::exit_processing::{Error as ExitProcessingError, Outcome as ExitProcessingOutcome};
+pub use self::info::{Error as InfoError, LeafBlock};
+pub use self::prune::{Error as PruneError, Outcome as PruneOutcome};
 
 #[derive(Debug, PartialEq)]
 pub enum BeaconChainError {
@@ -28,6 +34,13 @@ pub struct BeaconChain<T: ClientDB + Sized, U: SlotClock> {
     pub slot_clock: U,
     pub leaf_blocks: HashSet<Hash256>,
     pub canonical_leaf_block: Hash256,
+    /// Intermediate tree hashes of the most recently produced state.
+    pub state_tree_hash_cache: BeaconStateTreeHashCache,
+    /// Verified voluntary exits awaiting inclusion in a block, keyed by validator index.
+    ///
+    /// Note: exits are not yet applied to the state when a block is processed, so they remain in
+    /// the pool.
+    pub exit_pool: BTreeMap<u32, Exit>,
     pub spec: ChainSpec,
 }
 
@@ -63,6 +76,8 @@ where
             slot_clock,
             leaf_blocks,
             canonical_leaf_block: block_root,
+            state_tree_hash_cache: BeaconStateTreeHashCache::default(),
+            exit_pool: BTreeMap::new(),
             spec,
         })
     }
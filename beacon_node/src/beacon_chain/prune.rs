@@ -0,0 +1,116 @@
+use super::info::Error as InfoError;
+use super::{BeaconChain, ClientDB, DBError, SlotClock};
+use std::collections::HashSet;
+use types::{
+    readers::{BeaconBlockReader, SszBeaconBlock},
+    Hash256,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    DBError(String),
+    MissingBeaconBlock(Hash256),
+    InvalidBeaconBlock(Hash256),
+    InfoError(InfoError),
+}
+
+/// Summarises the blocks and states removed by `BeaconChain::prune`.
+#[derive(Debug, PartialEq, Default)]
+pub struct Outcome {
+    pub pruned_leaves: u64,
+    pub deleted_blocks: u64,
+    pub deleted_states: u64,
+}
+
+impl<T, U> BeaconChain<T, U>
+where
+    T: ClientDB,
+    U: SlotClock,
+{
+    /// Delete each fork which branched from the canonical chain prior to the finalized slot of
+    /// the canonical head, along with the states referenced by the blocks of that fork.
+    ///
+    /// Such forks can never become canonical, so there is no reason to keep them.
+    pub fn prune(&mut self) -> Result<Outcome, Error> {
+        let finalized_slot = self.head_state()?.finalized_slot;
+        let canonical_roots = self.canonical_roots()?;
+
+        let mut outcome = Outcome::default();
+        let mut pruned_leaves = vec![];
+
+        for leaf in self.leaf_blocks.iter() {
+            if *leaf == self.canonical_leaf_block {
+                continue;
+            }
+
+            // Walk back to the point where the fork joins the canonical chain.
+            let mut fork = vec![];
+            let mut current = *leaf;
+            let fork_slot = loop {
+                let ssz = self
+                    .block_store
+                    .get(&current)?
+                    .ok_or_else(|| Error::MissingBeaconBlock(current))?;
+                let block = SszBeaconBlock::from_slice(&ssz)
+                    .map_err(|_| Error::InvalidBeaconBlock(current))?;
+                if canonical_roots.contains(&current) {
+                    break block.slot();
+                }
+                fork.push((current, block.state_root()));
+                current = block.parent_root();
+            };
+
+            if fork_slot < finalized_slot {
+                for (block_root, state_root) in fork {
+                    if self.block_store.exists(&block_root)? {
+                        self.block_store.delete(&block_root)?;
+                        outcome.deleted_blocks += 1;
+                    }
+                    if self.state_store.exists(&state_root)? {
+                        self.state_store.delete(&state_root)?;
+                        outcome.deleted_states += 1;
+                    }
+                }
+                pruned_leaves.push(*leaf);
+            }
+        }
+
+        for leaf in pruned_leaves {
+            self.leaf_blocks.remove(&leaf);
+            outcome.pruned_leaves += 1;
+        }
+
+        Ok(outcome)
+    }
+
+    /// Returns the roots of all blocks in the canonical chain, from the head back to genesis.
+    fn canonical_roots(&self) -> Result<HashSet<Hash256>, Error> {
+        let mut roots = HashSet::new();
+        let mut current = self.canonical_leaf_block;
+        loop {
+            let ssz = self
+                .block_store
+                .get(&current)?
+                .ok_or_else(|| Error::MissingBeaconBlock(current))?;
+            let block =
+                SszBeaconBlock::from_slice(&ssz).map_err(|_| Error::InvalidBeaconBlock(current))?;
+            roots.insert(current);
+            if block.slot() <= self.spec.genesis_slot {
+                break Ok(roots);
+            }
+            current = block.parent_root();
+        }
+    }
+}
+
+impl From<DBError> for Error {
+    fn from(e: DBError) -> Error {
+        Error::DBError(e.message)
+    }
+}
+
+impl From<InfoError> for Error {
+    fn from(e: InfoError) -> Error {
+        Error::InfoError(e)
+    }
+}
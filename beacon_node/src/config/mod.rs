@@ -1,5 +1,8 @@
+use crate::http::HttpConfig;
+use crate::rpc::{AdminConfig, RpcConfig};
+use serde_derive::Deserialize;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Stores the core configuration for this Lighthouse instance.
 /// This struct is general, other components may implement more
@@ -8,10 +11,23 @@ use std::path::PathBuf;
 pub struct LighthouseConfig {
     pub data_dir: PathBuf,
     pub p2p_listen_port: u16,
+    pub rpc: RpcConfig,
+    pub admin: AdminConfig,
+    pub http: HttpConfig,
 }
 
 const DEFAULT_LIGHTHOUSE_DIR: &str = ".lighthouse";
 
+/// The subset of `LighthouseConfig` which may be supplied in a TOML config file.
+///
+/// Each section is optional; omitted sections retain their existing values.
+#[derive(Deserialize)]
+struct ConfigFile {
+    rpc: Option<RpcConfig>,
+    admin: Option<AdminConfig>,
+    http: Option<HttpConfig>,
+}
+
 impl LighthouseConfig {
     /// Build a new lighthouse configuration from defaults.
     pub fn default() -> Self {
@@ -25,6 +41,29 @@ impl LighthouseConfig {
         Self {
             data_dir,
             p2p_listen_port,
+            rpc: RpcConfig::default(),
+            admin: AdminConfig::default(),
+            http: HttpConfig::default(),
         }
     }
+
+    /// Read a TOML config file and apply any sections it contains to `self`.
+    pub fn apply_file(&mut self, path: &Path) -> Result<(), String> {
+        let contents =
+            fs::read_to_string(path).map_err(|e| format!("Unable to read {:?}: {}", path, e))?;
+        let file: ConfigFile =
+            toml::from_str(&contents).map_err(|e| format!("Unable to parse {:?}: {}", path, e))?;
+
+        if let Some(rpc) = file.rpc {
+            self.rpc = rpc;
+        }
+        if let Some(admin) = file.admin {
+            self.admin = admin;
+        }
+        if let Some(http) = file.http {
+            self.http = http;
+        }
+
+        Ok(())
+    }
 }
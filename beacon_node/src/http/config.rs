@@ -0,0 +1,23 @@
+use serde_derive::Deserialize;
+
+/// Stores the configuration for the HTTP/JSON API server.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct HttpConfig {
+    /// If `false`, the HTTP server is not started.
+    pub enabled: bool,
+    /// The host (e.g., an IP address) the server should bind to.
+    pub listen_address: String,
+    /// The TCP port the server should bind to.
+    pub port: u16,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen_address: "127.0.0.1".to_string(),
+            port: 5_052,
+        }
+    }
+}
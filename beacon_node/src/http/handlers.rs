@@ -0,0 +1,301 @@
+use crate::beacon_chain::{BeaconChain, InfoError};
+use db::ClientDB;
+use hyper::header::CONTENT_TYPE;
+use hyper::{Body, Method, Request, Response, StatusCode};
+use serde_json::{json, Value};
+use slot_clock::SlotClock;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use types::{BeaconState, Hash256};
+
+/// The version string reported by `/node/version`.
+const VERSION: &str = concat!("Lighthouse/v", env!("CARGO_PKG_VERSION"));
+
+/// An error which is returned to the HTTP client as a JSON object.
+#[derive(Debug, PartialEq)]
+pub enum ApiError {
+    NotFound(String),
+    BadRequest(String),
+    ServerError(String),
+}
+
+type ApiResult = Result<Value, ApiError>;
+
+type Query = HashMap<String, String>;
+
+/// Dispatch a HTTP request to the appropriate handler, returning a JSON response.
+pub fn route<T, U>(req: &Request<Body>, beacon_chain: &RwLock<BeaconChain<T, U>>) -> Response<Body>
+where
+    T: ClientDB,
+    U: SlotClock,
+{
+    if req.method() != Method::GET {
+        return ApiError::BadRequest("Only GET requests are supported.".to_string())
+            .into_response();
+    }
+
+    let query = parse_query(req.uri().query());
+
+    let beacon_chain = match beacon_chain.read() {
+        Ok(beacon_chain) => beacon_chain,
+        Err(_) => {
+            return ApiError::ServerError("BeaconChain lock poisoned.".to_string()).into_response()
+        }
+    };
+
+    let result = match req.uri().path() {
+        "/node/version" => node_version(),
+        "/node/syncing" => node_syncing(&beacon_chain),
+        "/beacon/head" => beacon_head(&beacon_chain),
+        "/beacon/block" => beacon_block(&beacon_chain, &query),
+        "/beacon/state/finality" => state_finality(&beacon_chain),
+        "/beacon/state/fork" => state_fork(&beacon_chain),
+        "/beacon/state/validator" => state_validator(&beacon_chain, &query),
+        "/beacon/state/balances" => state_balances(&beacon_chain),
+        "/beacon/committees" => committees(&beacon_chain),
+        path => Err(ApiError::NotFound(format!("Unknown endpoint: {}", path))),
+    };
+
+    match result {
+        Ok(value) => json_response(StatusCode::OK, &value),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// `GET /node/version`
+fn node_version() -> ApiResult {
+    Ok(json!({ "version": VERSION }))
+}
+
+/// `GET /node/syncing`
+fn node_syncing<T: ClientDB, U: SlotClock>(beacon_chain: &BeaconChain<T, U>) -> ApiResult {
+    let (_, head) = beacon_chain.head_block()?;
+    Ok(json!({
+        "is_syncing": beacon_chain.is_syncing()?,
+        "head_slot": head.slot,
+        "present_slot": beacon_chain.present_slot(),
+    }))
+}
+
+/// `GET /beacon/head`
+fn beacon_head<T: ClientDB, U: SlotClock>(beacon_chain: &BeaconChain<T, U>) -> ApiResult {
+    let (block_root, block) = beacon_chain.head_block()?;
+    Ok(json!({
+        "slot": block.slot,
+        "block_root": block_root,
+        "parent_root": block.parent_root,
+        "state_root": block.state_root,
+    }))
+}
+
+/// `GET /beacon/block?root=0x..` or `GET /beacon/block?slot=N`
+fn beacon_block<T: ClientDB, U: SlotClock>(
+    beacon_chain: &BeaconChain<T, U>,
+    query: &Query,
+) -> ApiResult {
+    let (block_root, block) = if let Some(root) = query.get("root") {
+        let root = parse_hash256(root)?;
+        let block = beacon_chain
+            .block_by_root(&root)?
+            .ok_or_else(|| ApiError::NotFound("Unknown block root.".to_string()))?;
+        (root, block)
+    } else if let Some(slot) = query.get("slot") {
+        let slot = parse_u64("slot", slot)?;
+        beacon_chain
+            .block_at_slot(slot)?
+            .ok_or_else(|| ApiError::NotFound("No canonical block at slot.".to_string()))?
+    } else {
+        return Err(ApiError::BadRequest(
+            "A root or slot parameter is required.".to_string(),
+        ));
+    };
+
+    Ok(json!({
+        "root": block_root,
+        "beacon_block": to_value(&block)?,
+    }))
+}
+
+/// `GET /beacon/state/finality`
+fn state_finality<T: ClientDB, U: SlotClock>(beacon_chain: &BeaconChain<T, U>) -> ApiResult {
+    let state = beacon_chain.head_state()?;
+    Ok(json!({
+        "previous_justified_slot": state.previous_justified_slot,
+        "justified_slot": state.justified_slot,
+        "justification_bitfield": state.justification_bitfield,
+        "finalized_slot": state.finalized_slot,
+    }))
+}
+
+/// `GET /beacon/state/fork`
+fn state_fork<T: ClientDB, U: SlotClock>(beacon_chain: &BeaconChain<T, U>) -> ApiResult {
+    let state = beacon_chain.head_state()?;
+    to_value(&state.fork_data)
+}
+
+/// `GET /beacon/state/validator?index=N` or `GET /beacon/state/validator?pubkey=0x..`
+fn state_validator<T: ClientDB, U: SlotClock>(
+    beacon_chain: &BeaconChain<T, U>,
+    query: &Query,
+) -> ApiResult {
+    let state = beacon_chain.head_state()?;
+
+    let index = if let Some(index) = query.get("index") {
+        parse_u64("index", index)? as usize
+    } else if let Some(pubkey) = query.get("pubkey") {
+        validator_index_by_pubkey(&state, &parse_hex("pubkey", pubkey)?)
+            .ok_or_else(|| ApiError::NotFound("Unknown validator pubkey.".to_string()))?
+    } else {
+        return Err(ApiError::BadRequest(
+            "An index or pubkey parameter is required.".to_string(),
+        ));
+    };
+
+    let validator = state
+        .validator_registry
+        .get(index)
+        .ok_or_else(|| ApiError::NotFound("Unknown validator index.".to_string()))?;
+
+    Ok(json!({
+        "index": index,
+        "validator": to_value(validator)?,
+        "balance": state.validator_balances.get(index),
+    }))
+}
+
+/// `GET /beacon/state/balances`
+fn state_balances<T: ClientDB, U: SlotClock>(beacon_chain: &BeaconChain<T, U>) -> ApiResult {
+    let state = beacon_chain.head_state()?;
+    to_value(&state.validator_balances)
+}
+
+/// `GET /beacon/committees`
+///
+/// Returns the committee assignments for each slot in the epoch of the head state.
+fn committees<T: ClientDB, U: SlotClock>(beacon_chain: &BeaconChain<T, U>) -> ApiResult {
+    let state = beacon_chain.head_state()?;
+    let epoch_length = beacon_chain.spec.epoch_length;
+    let epoch_start_slot = state.slot - state.slot % epoch_length;
+
+    let slots: Vec<Value> = beacon_chain
+        .committees_for_state_epoch(&state)?
+        .iter()
+        .enumerate()
+        .map(|(i, committees)| {
+            json!({
+                "slot": epoch_start_slot + i as u64,
+                "committees": committees,
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "epoch": state.slot / epoch_length,
+        "slots": slots,
+    }))
+}
+
+/// Returns the index of the validator in `state` with the given public key bytes.
+fn validator_index_by_pubkey(state: &BeaconState, pubkey: &[u8]) -> Option<usize> {
+    state
+        .validator_registry
+        .iter()
+        .position(|v| v.pubkey.as_raw().as_bytes() == pubkey)
+}
+
+/// Parse a URI query string (e.g., `a=1&b=2`) into a map.
+fn parse_query(query: Option<&str>) -> Query {
+    query
+        .unwrap_or("")
+        .split('&')
+        .filter_map(|pair| {
+            let mut split = pair.splitn(2, '=');
+            match (split.next(), split.next()) {
+                (Some(key), Some(value)) if !key.is_empty() => {
+                    Some((key.to_string(), value.to_string()))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn parse_u64(name: &str, value: &str) -> Result<u64, ApiError> {
+    value
+        .parse()
+        .map_err(|_| ApiError::BadRequest(format!("Invalid {}: {}", name, value)))
+}
+
+/// Parse a hex string, with or without a `0x` prefix.
+fn parse_hex(name: &str, value: &str) -> Result<Vec<u8>, ApiError> {
+    let trimmed = if value.starts_with("0x") {
+        &value[2..]
+    } else {
+        value
+    };
+    hex::decode(trimmed).map_err(|_| ApiError::BadRequest(format!("Invalid {}: {}", name, value)))
+}
+
+fn parse_hash256(value: &str) -> Result<Hash256, ApiError> {
+    let bytes = parse_hex("root", value)?;
+    if bytes.len() == 32 {
+        Ok(Hash256::from(&bytes[..]))
+    } else {
+        Err(ApiError::BadRequest("A root must be 32 bytes.".to_string()))
+    }
+}
+
+fn to_value<T: serde::Serialize>(item: &T) -> ApiResult {
+    serde_json::to_value(item).map_err(|e| ApiError::ServerError(e.to_string()))
+}
+
+fn json_response(status: StatusCode, value: &Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(value.to_string()))
+        .expect("Response from static parts is valid.")
+}
+
+impl ApiError {
+    /// Consume the error, returning a JSON response with an appropriate status code.
+    fn into_response(self) -> Response<Body> {
+        let (status, message) = match self {
+            ApiError::NotFound(message) => (StatusCode::NOT_FOUND, message),
+            ApiError::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            ApiError::ServerError(message) => (StatusCode::INTERNAL_SERVER_ERROR, message),
+        };
+        json_response(status, &json!({ "error": message }))
+    }
+}
+
+impl From<InfoError> for ApiError {
+    fn from(e: InfoError) -> ApiError {
+        ApiError::ServerError(format!("{:?}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query() {
+        let query = parse_query(Some("slot=10&root=0xab&bad&=x"));
+
+        assert_eq!(query.len(), 2);
+        assert_eq!(query.get("slot"), Some(&"10".to_string()));
+        assert_eq!(query.get("root"), Some(&"0xab".to_string()));
+        assert!(parse_query(None).is_empty());
+    }
+
+    #[test]
+    fn test_parse_hash256() {
+        let root = Hash256::from(&[42; 32][..]);
+        let hex_root = format!("0x{}", hex::encode(&root[..]));
+
+        assert_eq!(parse_hash256(&hex_root), Ok(root));
+        assert!(parse_hash256("0x4242").is_err());
+        assert!(parse_hash256("not hex").is_err());
+    }
+}
@@ -0,0 +1,56 @@
+mod config;
+mod handlers;
+
+use crate::beacon_chain::BeaconChain;
+use db::ClientDB;
+use futures::Future;
+use hyper::service::service_fn_ok;
+use hyper::Server;
+use slog::{error, info, Logger};
+use slot_clock::SlotClock;
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+pub use self::config::HttpConfig;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The configured host and port do not form a valid socket address.
+    InvalidAddress(String),
+    /// The server could not be bound to the configured address.
+    UnableToBind(SocketAddr, String),
+}
+
+/// Start a HTTP server which serves JSON representations of the given `BeaconChain`.
+///
+/// The server runs on its own thread; the returned handle may be used to join it.
+pub fn start_server<T, U>(
+    config: &HttpConfig,
+    beacon_chain: Arc<RwLock<BeaconChain<T, U>>>,
+    log: Logger,
+) -> Result<thread::JoinHandle<()>, Error>
+where
+    T: ClientDB + 'static,
+    U: SlotClock + 'static,
+{
+    let addr: SocketAddr = format!("{}:{}", config.listen_address, config.port)
+        .parse()
+        .map_err(|e| Error::InvalidAddress(format!("{:?}", e)))?;
+
+    let builder = Server::try_bind(&addr).map_err(|e| Error::UnableToBind(addr, e.to_string()))?;
+
+    let server = {
+        let log = log.clone();
+        builder
+            .serve(move || {
+                let beacon_chain = beacon_chain.clone();
+                service_fn_ok(move |req| handlers::route(&req, &beacon_chain))
+            })
+            .map_err(move |e| error!(log, "HTTP server failed"; "error" => e.to_string()))
+    };
+
+    info!(log, "HTTP listening on {}", addr);
+
+    Ok(thread::spawn(move || hyper::rt::run(server)))
+}
@@ -0,0 +1,3 @@
+//! The beacon chain of the `beacon_node` binary, exposed as a library so that it may be used
+//! elsewhere (e.g., by fuzz targets).
+pub mod beacon_chain;
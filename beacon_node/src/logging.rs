@@ -0,0 +1,54 @@
+use slog::{Drain, Level, OwnedKVList, Record};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A handle to a log level which may be changed while the node is running.
+///
+/// Clones share the same underlying level.
+#[derive(Clone)]
+pub struct LogLevel {
+    level: Arc<AtomicUsize>,
+}
+
+impl LogLevel {
+    pub fn new(level: Level) -> Self {
+        Self {
+            level: Arc::new(AtomicUsize::new(level.as_usize())),
+        }
+    }
+
+    pub fn get(&self) -> Level {
+        Level::from_usize(self.level.load(Ordering::Relaxed)).unwrap_or(Level::Info)
+    }
+
+    pub fn set(&self, level: Level) {
+        self.level.store(level.as_usize(), Ordering::Relaxed);
+    }
+
+    /// Wrap `drain` such that it only receives records at or above the level of `self`.
+    pub fn filter<D: Drain>(&self, drain: D) -> RuntimeLevelFilter<D> {
+        RuntimeLevelFilter {
+            drain,
+            level: self.clone(),
+        }
+    }
+}
+
+/// A `Drain` which discards records below the present value of some `LogLevel`.
+pub struct RuntimeLevelFilter<D: Drain> {
+    drain: D,
+    level: LogLevel,
+}
+
+impl<D: Drain> Drain for RuntimeLevelFilter<D> {
+    type Ok = Option<D::Ok>;
+    type Err = D::Err;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        if record.level().is_at_least(self.level.get()) {
+            self.drain.log(record, values).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
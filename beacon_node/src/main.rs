@@ -1,18 +1,32 @@
 extern crate slog;
 
 mod config;
+mod http;
+mod logging;
 mod rpc;
 
 use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
 
 use crate::config::LighthouseConfig;
-use crate::rpc::start_server;
+use crate::logging::LogLevel;
+use crate::rpc::TlsConfig;
+use beacon_node::beacon_chain::{self, BeaconChain};
 use clap::{App, Arg};
-use slog::{error, info, o, Drain};
+use db::{
+    stores::{BeaconBlockStore, BeaconStateStore, COLUMNS},
+    DiskDB,
+};
+use slog::{error, info, o, Drain, Level};
+use slot_clock::SystemTimeSlotClock;
+use spec::ChainSpec;
 
 fn main() {
     let decorator = slog_term::TermDecorator::new().build();
     let drain = slog_term::CompactFormat::new(decorator).build().fuse();
+    // The log level may be changed at runtime via the admin RPC.
+    let log_level = LogLevel::new(Level::Debug);
+    let drain = log_level.filter(drain).fuse();
     let drain = slog_async::Async::new(drain).build().fuse();
     let log = slog::Logger::root(drain, o!());
 
@@ -34,10 +48,115 @@ fn main() {
                 .help("Network listen port for p2p connections.")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("config")
+                .long("config")
+                .value_name("FILE")
+                .help("TOML file containing configuration values.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("rpc-address")
+                .long("rpc-address")
+                .value_name("HOST")
+                .help("Address the gRPC server binds to.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("rpc-port")
+                .long("rpc-port")
+                .value_name("PORT")
+                .help("Port the gRPC server binds to.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("rpc-threads")
+                .long("rpc-threads")
+                .value_name("COUNT")
+                .help("Number of gRPC worker threads.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("rpc-max-message-size")
+                .long("rpc-max-message-size")
+                .value_name("BYTES")
+                .help("Maximum size of a gRPC message.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("rpc-tls-cert")
+                .long("rpc-tls-cert")
+                .value_name("FILE")
+                .help("PEM certificate for serving gRPC over TLS.")
+                .requires("rpc-tls-key")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("rpc-tls-key")
+                .long("rpc-tls-key")
+                .value_name("FILE")
+                .help("PEM private key for serving gRPC over TLS.")
+                .requires("rpc-tls-cert")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("rpc-tls-client-ca")
+                .long("rpc-tls-client-ca")
+                .value_name("FILE")
+                .help("PEM CA certificate; if supplied, gRPC clients must present a certificate signed by it.")
+                .requires("rpc-tls-cert")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("admin")
+                .long("admin")
+                .help("Enable the admin gRPC server."),
+        )
+        .arg(
+            Arg::with_name("admin-address")
+                .long("admin-address")
+                .value_name("HOST")
+                .help("Address the admin gRPC server binds to.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("admin-port")
+                .long("admin-port")
+                .value_name("PORT")
+                .help("Port the admin gRPC server binds to.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http")
+                .long("http")
+                .help("Enable the HTTP/JSON API server."),
+        )
+        .arg(
+            Arg::with_name("http-address")
+                .long("http-address")
+                .value_name("HOST")
+                .help("Address the HTTP server binds to.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-port")
+                .long("http-port")
+                .value_name("PORT")
+                .help("Port the HTTP server binds to.")
+                .takes_value(true),
+        )
         .get_matches();
 
     let mut config = LighthouseConfig::default();
 
+    // Config file, applied before any other CLI flags so that they may override it.
+    if let Some(path) = matches.value_of("config") {
+        if let Err(e) = config.apply_file(&PathBuf::from(path)) {
+            error!(log, "Invalid config file"; "error" => e);
+            return;
+        }
+    }
+
     // Custom datadir
     if let Some(dir) = matches.value_of("datadir") {
         config.data_dir = PathBuf::from(dir.to_string());
@@ -53,12 +172,158 @@ fn main() {
         }
     }
 
+    // Custom gRPC bind address
+    if let Some(address) = matches.value_of("rpc-address") {
+        config.rpc.listen_address = address.to_string();
+    }
+
+    // Custom gRPC port
+    if let Some(port_str) = matches.value_of("rpc-port") {
+        if let Ok(port) = port_str.parse::<u16>() {
+            config.rpc.port = port;
+        } else {
+            error!(log, "Invalid RPC port"; "port" => port_str);
+            return;
+        }
+    }
+
+    // Custom gRPC worker thread count
+    if let Some(threads_str) = matches.value_of("rpc-threads") {
+        if let Ok(threads) = threads_str.parse::<usize>() {
+            config.rpc.worker_threads = threads;
+        } else {
+            error!(log, "Invalid RPC thread count"; "threads" => threads_str);
+            return;
+        }
+    }
+
+    // Custom gRPC message size limit
+    if let Some(size_str) = matches.value_of("rpc-max-message-size") {
+        if let Ok(size) = size_str.parse::<usize>() {
+            config.rpc.max_message_size = size;
+        } else {
+            error!(log, "Invalid RPC max message size"; "size" => size_str);
+            return;
+        }
+    }
+
+    // gRPC TLS
+    if let (Some(cert), Some(key)) = (
+        matches.value_of("rpc-tls-cert"),
+        matches.value_of("rpc-tls-key"),
+    ) {
+        config.rpc.tls = Some(TlsConfig {
+            server_cert: PathBuf::from(cert),
+            server_key: PathBuf::from(key),
+            client_ca: matches.value_of("rpc-tls-client-ca").map(PathBuf::from),
+        });
+    }
+
+    // Admin gRPC server
+    if matches.is_present("admin") {
+        config.admin.enabled = true;
+    }
+    if let Some(address) = matches.value_of("admin-address") {
+        config.admin.listen_address = address.to_string();
+    }
+    if let Some(port_str) = matches.value_of("admin-port") {
+        if let Ok(port) = port_str.parse::<u16>() {
+            config.admin.port = port;
+        } else {
+            error!(log, "Invalid admin port"; "port" => port_str);
+            return;
+        }
+    }
+
+    // HTTP server
+    if matches.is_present("http") {
+        config.http.enabled = true;
+    }
+    if let Some(address) = matches.value_of("http-address") {
+        config.http.listen_address = address.to_string();
+    }
+    if let Some(port_str) = matches.value_of("http-port") {
+        if let Ok(port) = port_str.parse::<u16>() {
+            config.http.port = port;
+        } else {
+            error!(log, "Invalid HTTP port"; "port" => port_str);
+            return;
+        }
+    }
+
     // Log configuration
     info!(log, "";
           "data_dir" => &config.data_dir.to_str(),
-          "port" => &config.p2p_listen_port);
+          "port" => &config.p2p_listen_port,
+          "rpc_address" => &config.rpc.listen_address,
+          "rpc_port" => &config.rpc.port);
+
+    // Ethereum
+    //
+    // TODO: Permit loading a custom spec from file.
+    let spec = ChainSpec::foundation();
+
+    // Database and stores.
+    let db = Arc::new(DiskDB::open(&config.data_dir, Some(&COLUMNS[..])));
+    let block_store = Arc::new(BeaconBlockStore::new(db.clone()));
+    let state_store = Arc::new(BeaconStateStore::new(db.clone()));
 
-    let _server = start_server(log.clone());
+    // Clock for determining the present slot.
+    let slot_clock = match SystemTimeSlotClock::new(spec.genesis_time, spec.slot_duration) {
+        Ok(clock) => clock,
+        Err(e) => {
+            error!(log, "Unable to instantiate slot clock"; "error" => format!("{:?}", e));
+            return;
+        }
+    };
+
+    // The beacon chain, shared between all API servers.
+    let beacon_chain = match BeaconChain::genesis(state_store, block_store, slot_clock, spec) {
+        Ok(chain) => Arc::new(RwLock::new(chain)),
+        Err(e) => {
+            error!(log, "Unable to initialize beacon chain"; "error" => format!("{:?}", e));
+            return;
+        }
+    };
+
+    let _server = match rpc::start_server(&config.rpc, beacon_chain.clone(), log.clone()) {
+        Ok(server) => server,
+        Err(e) => {
+            error!(log, "Unable to start gRPC server"; "error" => format!("{:?}", e));
+            return;
+        }
+    };
+
+    let _admin_server = if config.admin.enabled {
+        match rpc::start_admin_server(
+            &config.admin,
+            &config.rpc,
+            beacon_chain.clone(),
+            db.clone(),
+            log_level.clone(),
+            log.clone(),
+        ) {
+            Ok(server) => Some(server),
+            Err(e) => {
+                error!(log, "Unable to start admin gRPC server"; "error" => format!("{:?}", e));
+                return;
+            }
+        }
+    } else {
+        None
+    };
+
+    let _http_server = if config.http.enabled {
+        match http::start_server(&config.http, beacon_chain.clone(), log.clone()) {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                error!(log, "Unable to start HTTP server"; "error" => format!("{:?}", e));
+                return;
+            }
+        }
+    } else {
+        None
+    };
 
     loop {
         std::thread::sleep(std::time::Duration::from_secs(1));
@@ -0,0 +1,246 @@
+use crate::beacon_chain::BeaconChain;
+use crate::logging::LogLevel;
+use db::{stores::COLUMNS, ClientDB};
+use futures::Future;
+use grpcio::{RpcContext, RpcStatus, RpcStatusCode, UnarySink};
+use protobuf::RepeatedField;
+use protos::services::{
+    ChainSpec as ChainSpecResponse, ColumnStats, DbStatsResponse, Empty, ForkChoiceLeaf,
+    ForkChoiceTreeResponse, HeadResponse, PruneResponse, SetLogLevelRequest,
+};
+use protos::services_grpc::AdminService;
+use slog::{debug, error, info, Level, Logger};
+use slot_clock::SlotClock;
+use std::fmt::Debug;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+/// Serves operator-only information about, and control of, the beacon node.
+pub struct AdminServiceInstance<T: ClientDB, U: SlotClock> {
+    pub beacon_chain: Arc<RwLock<BeaconChain<T, U>>>,
+    pub db: Arc<T>,
+    pub log_level: LogLevel,
+    pub log: Logger,
+}
+
+// Implemented manually as `#[derive(Clone)]` would require `T` and `U` to be `Clone`.
+impl<T: ClientDB, U: SlotClock> Clone for AdminServiceInstance<T, U> {
+    fn clone(&self) -> Self {
+        Self {
+            beacon_chain: self.beacon_chain.clone(),
+            db: self.db.clone(),
+            log_level: self.log_level.clone(),
+            log: self.log.clone(),
+        }
+    }
+}
+
+impl<T, U> AdminService for AdminServiceInstance<T, U>
+where
+    T: ClientDB + 'static,
+    U: SlotClock + 'static,
+{
+    fn get_head(&mut self, ctx: RpcContext, _req: Empty, sink: UnarySink<HeadResponse>) {
+        debug!(self.log, "RPC request"; "endpoint" => "GetHead");
+
+        let result = self
+            .beacon_chain
+            .read()
+            .map_err(internal)
+            .and_then(|chain| {
+                let (block_root, block) = chain.head_block().map_err(internal)?;
+                let state = chain.head_state().map_err(internal)?;
+
+                let mut resp = HeadResponse::new();
+                resp.set_slot(block.slot);
+                resp.set_block_root(block_root.to_vec());
+                resp.set_parent_root(block.parent_root.to_vec());
+                resp.set_state_root(block.state_root.to_vec());
+                resp.set_justified_slot(state.justified_slot);
+                resp.set_finalized_slot(state.finalized_slot);
+                Ok(resp)
+            });
+
+        respond(&ctx, sink, result, &self.log)
+    }
+
+    fn get_fork_choice_tree(
+        &mut self,
+        ctx: RpcContext,
+        _req: Empty,
+        sink: UnarySink<ForkChoiceTreeResponse>,
+    ) {
+        debug!(self.log, "RPC request"; "endpoint" => "GetForkChoiceTree");
+
+        let result = self
+            .beacon_chain
+            .read()
+            .map_err(internal)
+            .and_then(|chain| {
+                let leaves = chain
+                    .leaf_blocks_with_chain_lengths()
+                    .map_err(internal)?
+                    .into_iter()
+                    .map(|leaf| {
+                        let mut resp = ForkChoiceLeaf::new();
+                        resp.set_block_root(leaf.block_root.to_vec());
+                        resp.set_slot(leaf.slot);
+                        resp.set_chain_length(leaf.chain_length);
+                        resp.set_is_canonical(leaf.is_canonical);
+                        resp
+                    })
+                    .collect();
+
+                let mut resp = ForkChoiceTreeResponse::new();
+                resp.set_leaves(RepeatedField::from_vec(leaves));
+                Ok(resp)
+            });
+
+        respond(&ctx, sink, result, &self.log)
+    }
+
+    fn get_db_stats(&mut self, ctx: RpcContext, _req: Empty, sink: UnarySink<DbStatsResponse>) {
+        debug!(self.log, "RPC request"; "endpoint" => "GetDbStats");
+
+        let columns: Result<Vec<ColumnStats>, RpcStatus> = COLUMNS
+            .iter()
+            .map(|col| {
+                let stats = self.db.column_stats(col).map_err(internal)?;
+
+                let mut resp = ColumnStats::new();
+                resp.set_name(col.to_string());
+                resp.set_key_count(stats.key_count);
+                resp.set_key_bytes(stats.key_bytes);
+                resp.set_value_bytes(stats.value_bytes);
+                Ok(resp)
+            })
+            .collect();
+
+        let result = columns.map(|columns| {
+            let mut resp = DbStatsResponse::new();
+            resp.set_columns(RepeatedField::from_vec(columns));
+            resp
+        });
+
+        respond(&ctx, sink, result, &self.log)
+    }
+
+    fn trigger_prune(&mut self, ctx: RpcContext, _req: Empty, sink: UnarySink<PruneResponse>) {
+        debug!(self.log, "RPC request"; "endpoint" => "TriggerPrune");
+
+        let result = self
+            .beacon_chain
+            .write()
+            .map_err(internal)
+            .and_then(|mut chain| chain.prune().map_err(internal));
+
+        let result = result.map(|outcome| {
+            info!(self.log, "Pruned beacon chain";
+                  "leaves" => outcome.pruned_leaves,
+                  "blocks" => outcome.deleted_blocks,
+                  "states" => outcome.deleted_states);
+
+            let mut resp = PruneResponse::new();
+            resp.set_pruned_leaves(outcome.pruned_leaves);
+            resp.set_deleted_blocks(outcome.deleted_blocks);
+            resp.set_deleted_states(outcome.deleted_states);
+            resp
+        });
+
+        respond(&ctx, sink, result, &self.log)
+    }
+
+    fn set_log_level(&mut self, ctx: RpcContext, req: SetLogLevelRequest, sink: UnarySink<Empty>) {
+        debug!(self.log, "RPC request"; "endpoint" => "SetLogLevel", "level" => req.get_level());
+
+        let result = match Level::from_str(req.get_level()) {
+            Ok(level) => {
+                self.log_level.set(level);
+                info!(self.log, "Log level changed"; "level" => level.as_str());
+                Ok(Empty::new())
+            }
+            Err(_) => Err(RpcStatus::new(
+                RpcStatusCode::InvalidArgument,
+                Some(format!("Unknown log level: {}", req.get_level())),
+            )),
+        };
+
+        respond(&ctx, sink, result, &self.log)
+    }
+
+    fn get_spec(&mut self, ctx: RpcContext, _req: Empty, sink: UnarySink<ChainSpecResponse>) {
+        debug!(self.log, "RPC request"; "endpoint" => "GetSpec");
+
+        let result = self.beacon_chain.read().map_err(internal).map(|chain| {
+            let spec = &chain.spec;
+
+            let mut resp = ChainSpecResponse::new();
+            resp.set_shard_count(spec.shard_count);
+            resp.set_target_committee_size(spec.target_committee_size);
+            resp.set_ejection_balance(spec.ejection_balance);
+            resp.set_max_balance_churn_quotient(spec.max_balance_churn_quotient);
+            resp.set_beacon_chain_shard_number(spec.beacon_chain_shard_number);
+            resp.set_max_casper_votes(spec.max_casper_votes);
+            resp.set_latest_block_roots_length(spec.latest_block_roots_length);
+            resp.set_latest_randao_mixes_length(spec.latest_randao_mixes_length);
+            resp.set_latest_penalized_exit_length(spec.latest_penalized_exit_length);
+            resp.set_max_withdrawals_per_epoch(spec.max_withdrawals_per_epoch);
+            resp.set_deposit_contract_address(spec.deposit_contract_address.to_vec());
+            resp.set_deposit_contract_tree_depth(spec.deposit_contract_tree_depth);
+            resp.set_min_deposit(spec.min_deposit);
+            resp.set_max_deposit(spec.max_deposit);
+            resp.set_genesis_fork_version(spec.genesis_fork_version);
+            resp.set_genesis_slot(spec.genesis_slot);
+            resp.set_genesis_start_shard(spec.genesis_start_shard);
+            resp.set_far_future_slot(spec.far_future_slot);
+            resp.set_zero_hash(spec.zero_hash.to_vec());
+            resp.set_bls_withdrawal_prefix_byte(u32::from(spec.bls_withdrawal_prefix_byte));
+            resp.set_slot_duration(spec.slot_duration);
+            resp.set_min_attestation_inclusion_delay(spec.min_attestation_inclusion_delay);
+            resp.set_epoch_length(spec.epoch_length);
+            resp.set_seed_lookahead(spec.seed_lookahead);
+            resp.set_entry_exit_delay(spec.entry_exit_delay);
+            resp.set_eth1_data_voting_period(spec.eth1_data_voting_period);
+            resp.set_min_validator_withdrawal_time(spec.min_validator_withdrawal_time);
+            resp.set_base_reward_quotient(spec.base_reward_quotient);
+            resp.set_whistleblower_reward_quotient(spec.whistleblower_reward_quotient);
+            resp.set_includer_reward_quotient(spec.includer_reward_quotient);
+            resp.set_inactivity_penalty_quotient(spec.inactivity_penalty_quotient);
+            resp.set_max_proposer_slashings(spec.max_proposer_slashings);
+            resp.set_max_casper_slashings(spec.max_casper_slashings);
+            resp.set_max_attestations(spec.max_attestations);
+            resp.set_max_deposits(spec.max_deposits);
+            resp.set_max_exits(spec.max_exits);
+            resp.set_domain_deposit(spec.domain_deposit);
+            resp.set_domain_attestation(spec.domain_attestation);
+            resp.set_domain_proposal(spec.domain_proposal);
+            resp.set_domain_exit(spec.domain_exit);
+            resp.set_domain_randao(spec.domain_randao);
+            resp.set_genesis_time(spec.genesis_time);
+            resp.set_initial_validator_count(spec.initial_validators.len() as u64);
+            resp
+        });
+
+        respond(&ctx, sink, result, &self.log)
+    }
+}
+
+/// Send either the response or the error status to the client.
+fn respond<R: Send + 'static>(
+    ctx: &RpcContext,
+    sink: UnarySink<R>,
+    result: Result<R, RpcStatus>,
+    log: &Logger,
+) {
+    let log = log.clone();
+    let f = match result {
+        Ok(resp) => sink.success(resp),
+        Err(status) => sink.fail(status),
+    }
+    .map_err(move |e| error!(log, "Failed to reply"; "error" => format!("{:?}", e)));
+    ctx.spawn(f)
+}
+
+fn internal<E: Debug>(e: E) -> RpcStatus {
+    RpcStatus::new(RpcStatusCode::Internal, Some(format!("{:?}", e)))
+}
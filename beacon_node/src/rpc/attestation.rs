@@ -0,0 +1,57 @@
+use futures::Future;
+use grpcio::{RpcContext, RpcStatus, RpcStatusCode, UnarySink};
+use protos::services::{
+    ProduceAttestationDataRequest, ProduceAttestationDataResponse, PublishAttestationRequest,
+    PublishAttestationResponse,
+};
+use protos::services_grpc::AttestationService;
+use slog::{debug, Logger};
+
+#[derive(Clone)]
+pub struct AttestationServiceInstance {
+    pub log: Logger,
+}
+
+impl AttestationService for AttestationServiceInstance {
+    /// Produce the `AttestationData` for signing by a validator.
+    ///
+    /// Attestation production is not yet implemented, so this always fails with `Unimplemented`
+    /// rather than serving data that does not describe the chain.
+    fn produce_attestation_data(
+        &mut self,
+        ctx: RpcContext,
+        req: ProduceAttestationDataRequest,
+        sink: UnarySink<ProduceAttestationDataResponse>,
+    ) {
+        debug!(self.log, "RPC request"; "endpoint" => "ProduceAttestationData", "slot" => req.get_slot(), "shard" => req.get_shard());
+
+        let f = sink
+            .fail(RpcStatus::new(
+                RpcStatusCode::Unimplemented,
+                Some("Attestation data production is not implemented".to_string()),
+            ))
+            .map_err(move |e| println!("failed to reply {:?}: {:?}", req, e));
+        ctx.spawn(f)
+    }
+
+    /// Accept some signed `FreeAttestation`, process and publish it.
+    ///
+    /// Attestation processing is not yet implemented, so this always fails with `Unimplemented`
+    /// rather than reporting an attestation as published when it was not.
+    fn publish_attestation(
+        &mut self,
+        ctx: RpcContext,
+        req: PublishAttestationRequest,
+        sink: UnarySink<PublishAttestationResponse>,
+    ) {
+        debug!(self.log, "RPC request"; "endpoint" => "PublishAttestation", "validator_index" => req.get_free_attestation().get_validator_index());
+
+        let f = sink
+            .fail(RpcStatus::new(
+                RpcStatusCode::Unimplemented,
+                Some("Attestation publishing is not implemented".to_string()),
+            ))
+            .map_err(move |e| println!("failed to reply {:?}: {:?}", req, e));
+        ctx.spawn(f)
+    }
+}
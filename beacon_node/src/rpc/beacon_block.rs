@@ -1,18 +1,39 @@
+use crate::beacon_chain::{BeaconChain, InfoError};
+use bls::Signature;
+use db::ClientDB;
 use futures::Future;
-use grpcio::{RpcContext, UnarySink};
+use grpcio::{RpcContext, RpcStatus, RpcStatusCode, UnarySink};
 use protos::services::{
-    BeaconBlock as BeaconBlockProto, ProduceBeaconBlockRequest, ProduceBeaconBlockResponse,
-    PublishBeaconBlockRequest, PublishBeaconBlockResponse,
+    BeaconBlock as BeaconBlockProto, CanonicalBlockRootRequest, CanonicalBlockRootResponse,
+    ProduceBeaconBlockRequest, ProduceBeaconBlockResponse, PublishBeaconBlockRequest,
+    PublishBeaconBlockResponse,
 };
 use protos::services_grpc::BeaconBlockService;
-use slog::Logger;
+use slog::{debug, error, Logger};
+use slot_clock::SlotClock;
+use ssz::ssz_encode;
+use std::sync::{Arc, RwLock};
 
-#[derive(Clone)]
-pub struct BeaconBlockServiceInstance {
+pub struct BeaconBlockServiceInstance<T: ClientDB, U: SlotClock> {
+    pub beacon_chain: Arc<RwLock<BeaconChain<T, U>>>,
     pub log: Logger,
 }
 
-impl BeaconBlockService for BeaconBlockServiceInstance {
+// Implemented manually as `#[derive(Clone)]` would require `T` and `U` to be `Clone`.
+impl<T: ClientDB, U: SlotClock> Clone for BeaconBlockServiceInstance<T, U> {
+    fn clone(&self) -> Self {
+        Self {
+            beacon_chain: self.beacon_chain.clone(),
+            log: self.log.clone(),
+        }
+    }
+}
+
+impl<T, U> BeaconBlockService for BeaconBlockServiceInstance<T, U>
+where
+    T: ClientDB + 'static,
+    U: SlotClock + 'static,
+{
     /// Produce a `BeaconBlock` for signing by a validator.
     fn produce_beacon_block(
         &mut self,
@@ -26,6 +47,8 @@ impl BeaconBlockService for BeaconBlockServiceInstance {
         let mut block = BeaconBlockProto::new();
         block.set_slot(req.get_slot());
         block.set_block_root("cats".as_bytes().to_vec());
+        block.set_randao_reveal(req.get_randao_reveal().to_vec());
+        block.set_signature(ssz_encode(&Signature::empty_signature()));
 
         let mut resp = ProduceBeaconBlockResponse::new();
         resp.set_block(block);
@@ -54,4 +77,59 @@ impl BeaconBlockService for BeaconBlockServiceInstance {
             .map_err(move |e| println!("failed to reply {:?}: {:?}", req, e));
         ctx.spawn(f)
     }
+
+    /// Return the root of the block in the canonical chain at some slot.
+    ///
+    /// Fails with `Unavailable` if the slot is later than the head, as whether a block will be
+    /// included at that slot is not yet known.
+    fn canonical_block_root(
+        &mut self,
+        ctx: RpcContext,
+        req: CanonicalBlockRootRequest,
+        sink: UnarySink<CanonicalBlockRootResponse>,
+    ) {
+        let slot = req.get_slot();
+        debug!(self.log, "RPC request"; "endpoint" => "CanonicalBlockRoot", "slot" => slot);
+
+        let result = match self.beacon_chain.read() {
+            Ok(chain) => canonical_block_root(&chain, slot),
+            Err(_) => Err(RpcStatus::new(
+                RpcStatusCode::Internal,
+                Some("BeaconChain lock poisoned".to_string()),
+            )),
+        };
+
+        let log = self.log.clone();
+        let f = match result {
+            Ok(resp) => sink.success(resp),
+            Err(status) => sink.fail(status),
+        }
+        .map_err(move |e| error!(log, "Failed to reply"; "error" => format!("{:?}", e)));
+        ctx.spawn(f)
+    }
+}
+
+fn canonical_block_root<T: ClientDB, U: SlotClock>(
+    chain: &BeaconChain<T, U>,
+    slot: u64,
+) -> Result<CanonicalBlockRootResponse, RpcStatus> {
+    let internal = |e: InfoError| RpcStatus::new(RpcStatusCode::Internal, Some(format!("{:?}", e)));
+
+    let (_, head) = chain.head_block().map_err(internal)?;
+    if slot > head.slot {
+        return Err(RpcStatus::new(
+            RpcStatusCode::Unavailable,
+            Some(format!(
+                "Slot {} is later than the head ({})",
+                slot, head.slot
+            )),
+        ));
+    }
+
+    let mut resp = CanonicalBlockRootResponse::new();
+    match chain.block_at_slot(slot).map_err(internal)? {
+        Some((root, _)) => resp.set_block_root(root.to_vec()),
+        None => resp.set_none(true),
+    }
+    Ok(resp)
 }
@@ -0,0 +1,105 @@
+use serde_derive::Deserialize;
+use std::path::PathBuf;
+
+/// Default maximum gRPC message size, in bytes.
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// Stores the configuration for the gRPC server.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct RpcConfig {
+    /// The host (e.g., an IP address) the server should bind to.
+    pub listen_address: String,
+    /// The TCP port the server should bind to.
+    pub port: u16,
+    /// The number of completion-queue threads used by the gRPC environment.
+    pub worker_threads: usize,
+    /// The maximum size of a single sent or received message, in bytes.
+    pub max_message_size: usize,
+    /// If `Some`, the server will only accept TLS connections.
+    pub tls: Option<TlsConfig>,
+}
+
+/// Paths to the PEM-encoded files required to serve gRPC over TLS.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TlsConfig {
+    /// The certificate presented by the server.
+    pub server_cert: PathBuf,
+    /// The private key for `server_cert`.
+    pub server_key: PathBuf,
+    /// If `Some`, clients must present a certificate signed by this CA (i.e., mutual TLS).
+    pub client_ca: Option<PathBuf>,
+}
+
+/// Stores the configuration for the admin gRPC server.
+///
+/// The admin service exposes operator-only controls (e.g., pruning, log level) and is therefore
+/// disabled by default and served separately from the public services.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct AdminConfig {
+    /// If `false`, the admin server is not started.
+    pub enabled: bool,
+    /// The host (e.g., an IP address) the server should bind to.
+    pub listen_address: String,
+    /// The TCP port the server should bind to. Must differ from the public gRPC port.
+    pub port: u16,
+    /// If `Some`, the server will only accept TLS connections.
+    pub tls: Option<TlsConfig>,
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            listen_address: "127.0.0.1".to_string(),
+            port: 50_051,
+            worker_threads: 1,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            tls: None,
+        }
+    }
+}
+
+impl Default for AdminConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen_address: "127.0.0.1".to_string(),
+            port: 50_052,
+            tls: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_omitted_fields_use_defaults() {
+        let config: RpcConfig = toml::from_str("port = 5052").unwrap();
+
+        assert_eq!(config.port, 5052);
+        assert_eq!(config.listen_address, RpcConfig::default().listen_address);
+        assert_eq!(config.tls, None);
+    }
+
+    #[test]
+    fn test_tls_section() {
+        let config: RpcConfig = toml::from_str(
+            r#"
+            listen_address = "0.0.0.0"
+
+            [tls]
+            server_cert = "/certs/node.pem"
+            server_key = "/certs/node.key"
+            client_ca = "/certs/ca.pem"
+            "#,
+        )
+        .unwrap();
+
+        let tls = config.tls.unwrap();
+        assert_eq!(tls.server_cert, PathBuf::from("/certs/node.pem"));
+        assert_eq!(tls.client_ca, Some(PathBuf::from("/certs/ca.pem")));
+    }
+}
@@ -1,36 +1,201 @@
+mod admin;
+mod attestation;
 mod beacon_block;
+mod config;
+mod node;
 mod validator;
 
+use self::admin::AdminServiceInstance;
+use self::attestation::AttestationServiceInstance;
 use self::beacon_block::BeaconBlockServiceInstance;
+use self::node::NodeServiceInstance;
 use self::validator::ValidatorServiceInstance;
-use grpcio::{Environment, Server, ServerBuilder};
-use protos::services_grpc::{create_beacon_block_service, create_validator_service};
-use std::sync::Arc;
+use crate::beacon_chain::BeaconChain;
+use crate::logging::LogLevel;
+use db::ClientDB;
+use grpcio::{
+    CertificateRequestType, ChannelArgs, ChannelBuilder, Environment, Server, ServerBuilder,
+    ServerCredentialsBuilder,
+};
+use protos::services_grpc::{
+    create_admin_service, create_attestation_service, create_beacon_block_service,
+    create_node_service, create_validator_service,
+};
+use slot_clock::SlotClock;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+pub use self::config::{AdminConfig, RpcConfig, TlsConfig};
 
 use slog::{info, Logger};
 
-pub fn start_server(log: Logger) -> Server {
-    let log_clone = log.clone();
-    let env = Arc::new(Environment::new(1));
+#[derive(Debug)]
+pub enum Error {
+    /// The configuration is unusable (e.g., zero worker threads).
+    InvalidConfig(String),
+    /// A TLS certificate or key could not be read from disk.
+    UnableToReadFile(PathBuf, String),
+    /// The server could not be bound to the configured address.
+    UnableToBind(String, u16, String),
+}
+
+/// Start a gRPC server which serves the public services for the given `BeaconChain`.
+pub fn start_server<T, U>(
+    config: &RpcConfig,
+    beacon_chain: Arc<RwLock<BeaconChain<T, U>>>,
+    log: Logger,
+) -> Result<Server, Error>
+where
+    T: ClientDB + 'static,
+    U: SlotClock + 'static,
+{
+    let (env, channel_args) = environment(config)?;
 
     let beacon_block_service = {
-        let instance = BeaconBlockServiceInstance { log: log.clone() };
+        let instance = BeaconBlockServiceInstance {
+            beacon_chain: beacon_chain.clone(),
+            log: log.clone(),
+        };
         create_beacon_block_service(instance)
     };
     let validator_service = {
-        let instance = ValidatorServiceInstance { log: log.clone() };
+        let instance = ValidatorServiceInstance {
+            beacon_chain: beacon_chain.clone(),
+            log: log.clone(),
+        };
         create_validator_service(instance)
     };
+    let attestation_service = {
+        let instance = AttestationServiceInstance { log: log.clone() };
+        create_attestation_service(instance)
+    };
+    let node_service = {
+        let instance = NodeServiceInstance {
+            beacon_chain,
+            log: log.clone(),
+        };
+        create_node_service(instance)
+    };
 
-    let mut server = ServerBuilder::new(env)
+    let builder = ServerBuilder::new(env)
+        .channel_args(channel_args)
         .register_service(beacon_block_service)
         .register_service(validator_service)
-        .bind("127.0.0.1", 50_051)
-        .build()
-        .unwrap();
-    server.start();
+        .register_service(attestation_service)
+        .register_service(node_service);
+
+    let server = bind(builder, &config.listen_address, config.port, &config.tls)?;
+    for &(ref host, port) in server.bind_addrs() {
+        info!(log, "gRPC listening on {}:{}", host, port; "tls" => config.tls.is_some());
+    }
+    Ok(server)
+}
+
+/// Start a gRPC server which serves the `AdminService` for the given `BeaconChain`.
+///
+/// The admin server must not share an address with the public server described by `rpc_config`,
+/// whose worker threads and maximum message size it uses.
+pub fn start_admin_server<T, U>(
+    config: &AdminConfig,
+    rpc_config: &RpcConfig,
+    beacon_chain: Arc<RwLock<BeaconChain<T, U>>>,
+    db: Arc<T>,
+    log_level: LogLevel,
+    log: Logger,
+) -> Result<Server, Error>
+where
+    T: ClientDB + 'static,
+    U: SlotClock + 'static,
+{
+    if config.listen_address == rpc_config.listen_address && config.port == rpc_config.port {
+        return Err(Error::InvalidConfig(
+            "the admin server must not share an address with the public gRPC server".to_string(),
+        ));
+    }
+
+    let (env, channel_args) = environment(rpc_config)?;
+
+    let admin_service = {
+        let instance = AdminServiceInstance {
+            beacon_chain,
+            db,
+            log_level,
+            log: log.clone(),
+        };
+        create_admin_service(instance)
+    };
+
+    let builder = ServerBuilder::new(env)
+        .channel_args(channel_args)
+        .register_service(admin_service);
+
+    let server = bind(builder, &config.listen_address, config.port, &config.tls)?;
     for &(ref host, port) in server.bind_addrs() {
-        info!(log_clone, "gRPC listening on {}:{}", host, port);
+        info!(log, "Admin gRPC listening on {}:{}", host, port; "tls" => config.tls.is_some());
+    }
+    Ok(server)
+}
+
+/// Returns the gRPC environment and the channel arguments (e.g., maximum message sizes) described
+/// by `config`.
+fn environment(config: &RpcConfig) -> Result<(Arc<Environment>, ChannelArgs), Error> {
+    if config.worker_threads == 0 {
+        return Err(Error::InvalidConfig(
+            "worker_threads must be greater than zero".to_string(),
+        ));
+    }
+    if config.max_message_size > i32::max_value() as usize {
+        return Err(Error::InvalidConfig(format!(
+            "max_message_size must not exceed {} bytes",
+            i32::max_value()
+        )));
     }
-    server
+
+    let env = Arc::new(Environment::new(config.worker_threads));
+    let channel_args = ChannelBuilder::new(env.clone())
+        .max_receive_message_len(config.max_message_size as i32)
+        .max_send_message_len(config.max_message_size as i32)
+        .build_args();
+
+    Ok((env, channel_args))
+}
+
+/// Bind `builder` to the given address (using TLS, if configured), then build and start the
+/// server.
+fn bind(
+    builder: ServerBuilder,
+    listen_address: &str,
+    port: u16,
+    tls: &Option<TlsConfig>,
+) -> Result<Server, Error> {
+    let builder = match tls {
+        None => builder.bind(listen_address, port),
+        Some(tls) => {
+            let credentials = {
+                let cert = read_pem(&tls.server_cert)?;
+                let key = read_pem(&tls.server_key)?;
+                let mut credentials = ServerCredentialsBuilder::new().add_cert(cert, key);
+                if let Some(client_ca) = &tls.client_ca {
+                    credentials = credentials.root_cert(
+                        read_pem(client_ca)?,
+                        CertificateRequestType::RequestAndRequireClientCertificateAndVerify,
+                    );
+                }
+                credentials.build()
+            };
+            builder.bind_secure(listen_address, port, credentials)
+        }
+    };
+
+    let mut server = builder
+        .build()
+        .map_err(|e| Error::UnableToBind(listen_address.to_string(), port, format!("{:?}", e)))?;
+    server.start();
+    Ok(server)
+}
+
+/// Read a PEM-encoded file into memory.
+fn read_pem(path: &PathBuf) -> Result<Vec<u8>, Error> {
+    fs::read(path).map_err(|e| Error::UnableToReadFile(path.clone(), e.to_string()))
 }
@@ -0,0 +1,69 @@
+use crate::beacon_chain::{BeaconChain, InfoError};
+use db::ClientDB;
+use futures::Future;
+use grpcio::{RpcContext, RpcStatus, RpcStatusCode, UnarySink};
+use protos::services::{Empty, Fork, NodeStatus};
+use protos::services_grpc::NodeService;
+use slog::{debug, error, Logger};
+use slot_clock::SlotClock;
+use std::sync::{Arc, RwLock};
+
+/// Serves information which allows a validator client to determine if it is safe to sign
+/// messages produced by this node.
+pub struct NodeServiceInstance<T: ClientDB, U: SlotClock> {
+    pub beacon_chain: Arc<RwLock<BeaconChain<T, U>>>,
+    pub log: Logger,
+}
+
+// Implemented manually as `#[derive(Clone)]` would require `T` and `U` to be `Clone`.
+impl<T: ClientDB, U: SlotClock> Clone for NodeServiceInstance<T, U> {
+    fn clone(&self) -> Self {
+        Self {
+            beacon_chain: self.beacon_chain.clone(),
+            log: self.log.clone(),
+        }
+    }
+}
+
+impl<T, U> NodeService for NodeServiceInstance<T, U>
+where
+    T: ClientDB + 'static,
+    U: SlotClock + 'static,
+{
+    fn get_status(&mut self, ctx: RpcContext, _req: Empty, sink: UnarySink<NodeStatus>) {
+        debug!(self.log, "RPC request"; "endpoint" => "GetStatus");
+
+        let result = match self.beacon_chain.read() {
+            Ok(chain) => status(&chain).map_err(|e| format!("{:?}", e)),
+            Err(_) => Err("BeaconChain lock poisoned".to_string()),
+        };
+
+        let log = self.log.clone();
+        let f = match result {
+            Ok(resp) => sink.success(resp),
+            Err(e) => sink.fail(RpcStatus::new(RpcStatusCode::Internal, Some(e))),
+        }
+        .map_err(move |e| error!(log, "Failed to reply"; "error" => format!("{:?}", e)));
+        ctx.spawn(f)
+    }
+}
+
+fn status<T: ClientDB, U: SlotClock>(chain: &BeaconChain<T, U>) -> Result<NodeStatus, InfoError> {
+    let (_, head) = chain.head_block()?;
+    let state = chain.head_state()?;
+
+    let mut resp = NodeStatus::new();
+    resp.set_genesis_time(chain.spec.genesis_time);
+    resp.set_fork_version(state.fork_data.version_at_slot(head.slot));
+    resp.set_head_slot(head.slot);
+    resp.set_finalized_slot(state.finalized_slot);
+    resp.set_is_syncing(chain.is_syncing()?);
+
+    let mut fork = Fork::new();
+    fork.set_pre_fork_version(state.fork_data.pre_fork_version);
+    fork.set_post_fork_version(state.fork_data.post_fork_version);
+    fork.set_fork_slot(state.fork_data.fork_slot);
+    resp.set_fork(fork);
+
+    Ok(resp)
+}
@@ -1,26 +1,47 @@
-use bls::PublicKey;
+use crate::beacon_chain::{BeaconChain, ExitProcessingOutcome};
+use bls::{PublicKey, Signature};
+use db::ClientDB;
 use futures::Future;
 use grpcio::{RpcContext, RpcStatus, RpcStatusCode, UnarySink};
 use protos::services::{
-    IndexResponse, ProposeBlockSlotRequest, ProposeBlockSlotResponse, PublicKey as PublicKeyRequest,
+    AttestationDutyRequest, AttestationDutyResponse, Exit as ExitProto, IndexResponse,
+    ProposeBlockSlotRequest, ProposeBlockSlotResponse, PublicKey as PublicKeyRequest,
+    PublishExitRequest, PublishExitResponse, ValidatorLivenessRequest, ValidatorLivenessResponse,
 };
 use protos::services_grpc::ValidatorService;
 use slog::{debug, Logger};
-use ssz::Decodable;
+use slot_clock::SlotClock;
+use ssz::decode_ssz_exact;
+use std::sync::{Arc, RwLock};
+use types::Exit;
 
-#[derive(Clone)]
-pub struct ValidatorServiceInstance {
+pub struct ValidatorServiceInstance<T: ClientDB, U: SlotClock> {
+    pub beacon_chain: Arc<RwLock<BeaconChain<T, U>>>,
     pub log: Logger,
 }
 
-impl ValidatorService for ValidatorServiceInstance {
+// Implemented manually as `#[derive(Clone)]` would require `T` and `U` to be `Clone`.
+impl<T: ClientDB, U: SlotClock> Clone for ValidatorServiceInstance<T, U> {
+    fn clone(&self) -> Self {
+        Self {
+            beacon_chain: self.beacon_chain.clone(),
+            log: self.log.clone(),
+        }
+    }
+}
+
+impl<T, U> ValidatorService for ValidatorServiceInstance<T, U>
+where
+    T: ClientDB + 'static,
+    U: SlotClock + 'static,
+{
     fn validator_index(
         &mut self,
         ctx: RpcContext,
         req: PublicKeyRequest,
         sink: UnarySink<IndexResponse>,
     ) {
-        if let Ok((public_key, _)) = PublicKey::ssz_decode(req.get_public_key(), 0) {
+        if let Ok(public_key) = decode_ssz_exact::<PublicKey>(req.get_public_key()) {
             debug!(self.log, "RPC request"; "endpoint" => "ValidatorIndex", "public_key" => public_key.concatenated_hex_id());
 
             let mut resp = IndexResponse::new();
@@ -51,14 +72,166 @@ impl ValidatorService for ValidatorServiceInstance {
     ) {
         debug!(self.log, "RPC request"; "endpoint" => "ProposeBlockSlot", "epoch" => req.get_epoch(), "validator_index" => req.get_validator_index());
 
+        let dependent_root = match dependent_root(&self.beacon_chain, req.get_epoch()) {
+            Ok(root) => root,
+            Err(status) => {
+                let f = sink
+                    .fail(status)
+                    .map_err(move |e| println!("failed to reply {:?}: {:?}", req, e));
+                return ctx.spawn(f);
+            }
+        };
+
         let mut resp = ProposeBlockSlotResponse::new();
 
         // TODO: return a legit value.
         resp.set_slot(1);
+        resp.set_dependent_root(dependent_root);
 
         let f = sink
             .success(resp)
             .map_err(move |e| println!("failed to reply {:?}: {:?}", req, e));
         ctx.spawn(f)
     }
+
+    fn attestation_duty(
+        &mut self,
+        ctx: RpcContext,
+        req: AttestationDutyRequest,
+        sink: UnarySink<AttestationDutyResponse>,
+    ) {
+        debug!(self.log, "RPC request"; "endpoint" => "AttestationDuty", "epoch" => req.get_epoch(), "validator_index" => req.get_validator_index());
+
+        let dependent_root = match dependent_root(&self.beacon_chain, req.get_epoch()) {
+            Ok(root) => root,
+            Err(status) => {
+                let f = sink
+                    .fail(status)
+                    .map_err(move |e| println!("failed to reply {:?}: {:?}", req, e));
+                return ctx.spawn(f);
+            }
+        };
+
+        let mut resp = AttestationDutyResponse::new();
+
+        // TODO: return a legit value.
+        resp.set_none(true);
+        resp.set_dependent_root(dependent_root);
+
+        let f = sink
+            .success(resp)
+            .map_err(move |e| println!("failed to reply {:?}: {:?}", req, e));
+        ctx.spawn(f)
+    }
+
+    fn validator_liveness(
+        &mut self,
+        ctx: RpcContext,
+        req: ValidatorLivenessRequest,
+        sink: UnarySink<ValidatorLivenessResponse>,
+    ) {
+        debug!(self.log, "RPC request"; "endpoint" => "ValidatorLiveness", "epoch" => req.get_epoch(), "validators" => req.get_validator_indices().len());
+
+        let result = match self.beacon_chain.read() {
+            Ok(chain) => chain
+                .live_validators(req.get_epoch(), req.get_validator_indices())
+                .map_err(|e| RpcStatus::new(RpcStatusCode::Internal, Some(format!("{:?}", e)))),
+            Err(_) => Err(RpcStatus::new(
+                RpcStatusCode::Internal,
+                Some("BeaconChain lock poisoned".to_string()),
+            )),
+        };
+
+        // The validators are not reported as not live until the whole epoch is known.
+        let f = match result {
+            Ok(Some(live_indices)) => {
+                let mut resp = ValidatorLivenessResponse::new();
+                resp.set_live_indices(live_indices);
+                sink.success(resp)
+            }
+            Ok(None) => sink.fail(RpcStatus::new(
+                RpcStatusCode::Unavailable,
+                Some(format!("Epoch {} is not yet known", req.get_epoch())),
+            )),
+            Err(status) => sink.fail(status),
+        }
+        .map_err(move |e| println!("failed to reply {:?}: {:?}", req, e));
+        ctx.spawn(f)
+    }
+
+    fn publish_exit(
+        &mut self,
+        ctx: RpcContext,
+        req: PublishExitRequest,
+        sink: UnarySink<PublishExitResponse>,
+    ) {
+        let exit = req.get_exit();
+        debug!(self.log, "RPC request"; "endpoint" => "PublishExit", "slot" => exit.get_slot(), "validator_index" => exit.get_validator_index());
+
+        let result = decode_exit(exit).and_then(|exit| match self.beacon_chain.write() {
+            Ok(mut chain) => chain
+                .process_exit(exit)
+                .map_err(|e| RpcStatus::new(RpcStatusCode::Internal, Some(format!("{:?}", e)))),
+            Err(_) => Err(RpcStatus::new(
+                RpcStatusCode::Internal,
+                Some("BeaconChain lock poisoned".to_string()),
+            )),
+        });
+
+        let f = match result {
+            Ok(outcome) => {
+                debug!(self.log, "Processed exit"; "outcome" => format!("{:?}", outcome));
+                let mut resp = PublishExitResponse::new();
+                resp.set_success(outcome == ExitProcessingOutcome::Accepted);
+                resp.set_msg(format!("{:?}", outcome).into_bytes());
+                sink.success(resp)
+            }
+            Err(status) => sink.fail(status),
+        }
+        .map_err(move |e| println!("failed to reply {:?}: {:?}", req, e));
+        ctx.spawn(f)
+    }
+}
+
+/// Decodes an `Exit` from its protobuf representation.
+fn decode_exit(exit: &ExitProto) -> Result<Exit, RpcStatus> {
+    let invalid = |msg: &str| RpcStatus::new(RpcStatusCode::InvalidArgument, Some(msg.to_string()));
+
+    if exit.get_validator_index() > u64::from(u32::max_value()) {
+        return Err(invalid("Invalid validator_index"));
+    }
+    let signature = decode_ssz_exact::<Signature>(exit.get_signature())
+        .map_err(|_| invalid("Invalid signature"))?;
+
+    Ok(Exit {
+        slot: exit.get_slot(),
+        validator_index: exit.get_validator_index() as u32,
+        signature,
+    })
+}
+
+/// Returns the root of the block upon which the shuffling of `epoch` depends.
+///
+/// Fails with `Unavailable` if the dependent block is not yet known.
+fn dependent_root<T: ClientDB, U: SlotClock>(
+    beacon_chain: &RwLock<BeaconChain<T, U>>,
+    epoch: u64,
+) -> Result<Vec<u8>, RpcStatus> {
+    let chain = beacon_chain.read().map_err(|_| {
+        RpcStatus::new(
+            RpcStatusCode::Internal,
+            Some("BeaconChain lock poisoned".to_string()),
+        )
+    })?;
+    match chain.shuffling_dependent_root(epoch) {
+        Ok(Some(root)) => Ok(root.to_vec()),
+        Ok(None) => Err(RpcStatus::new(
+            RpcStatusCode::Unavailable,
+            Some(format!("Shuffling of epoch {} is not yet known", epoch)),
+        )),
+        Err(e) => Err(RpcStatus::new(
+            RpcStatusCode::Internal,
+            Some(format!("{:?}", e)),
+        )),
+    }
 }
@@ -0,0 +1,238 @@
+use beacon_node::beacon_chain::{BeaconChain, BlockProcessingOutcome, ExitProcessingOutcome};
+use bls::{Keypair, Signature};
+use db::{
+    stores::{BeaconBlockStore, BeaconStateStore},
+    MemoryDB,
+};
+use slot_clock::TestingSlotClock;
+use spec::ChainSpec;
+use ssz::ssz_encode;
+use std::sync::Arc;
+use types::{BeaconBlock, Exit};
+
+fn in_memory_test_stores() -> (
+    Arc<MemoryDB>,
+    Arc<BeaconBlockStore<MemoryDB>>,
+    Arc<BeaconStateStore<MemoryDB>>,
+) {
+    let db = Arc::new(MemoryDB::open());
+    let block_store = Arc::new(BeaconBlockStore::new(db.clone()));
+    let state_store = Arc::new(BeaconStateStore::new(db.clone()));
+    (db, block_store, state_store)
+}
+
+fn in_memory_test_chain(
+    spec: ChainSpec,
+) -> (Arc<MemoryDB>, BeaconChain<MemoryDB, TestingSlotClock>) {
+    let (db, block_store, state_store) = in_memory_test_stores();
+    let slot_clock = TestingSlotClock::new(0);
+
+    let chain = BeaconChain::genesis(state_store, block_store, slot_clock, spec);
+    (db, chain.unwrap())
+}
+
+/// Returns a foundation spec where there is one active validator per slot of the epoch, along with
+/// the keypairs of those validators.
+fn spec_with_active_validators() -> (ChainSpec, Vec<Keypair>) {
+    let mut spec = ChainSpec::foundation();
+    let keypairs: Vec<Keypair> = (0..spec.epoch_length).map(|_| Keypair::random()).collect();
+    let template = spec.initial_validators[0].clone();
+
+    spec.initial_validators = keypairs
+        .iter()
+        .map(|keypair| {
+            let mut validator = template.clone();
+            validator.pubkey = keypair.pk.clone();
+            validator.activation_slot = spec.genesis_slot;
+            validator
+        })
+        .collect();
+    spec.initial_balances = vec![spec.initial_balances[0]; keypairs.len()];
+
+    (spec, keypairs)
+}
+
+/// Sets the RANDAO reveal and signature of `block` as if it were produced by `keypair`.
+fn sign_block(
+    chain: &BeaconChain<MemoryDB, TestingSlotClock>,
+    block: &mut BeaconBlock,
+    keypair: &Keypair,
+) {
+    let fork = chain.head_state().unwrap().fork_data;
+    let epoch = block.slot / chain.spec.epoch_length;
+
+    block.randao_reveal = Signature::new_with_domain(
+        &ssz_encode(&epoch),
+        fork.get_domain(block.slot, chain.spec.domain_randao),
+        &keypair.sk,
+    );
+    block.signature = Signature::new_with_domain(
+        &block.proposal_root(chain.spec.beacon_chain_shard_number),
+        fork.get_domain(block.slot, chain.spec.domain_proposal),
+        &keypair.sk,
+    );
+}
+
+#[test]
+fn it_constructs() {
+    let (_db, _chain) = in_memory_test_chain(ChainSpec::foundation());
+}
+
+#[test]
+fn it_produces() {
+    let (_db, mut chain) = in_memory_test_chain(ChainSpec::foundation());
+    let (_block, _state) = chain.produce_block().unwrap();
+}
+
+#[test]
+fn it_finds_the_shuffling_dependent_root() {
+    let (_db, chain) = in_memory_test_chain(ChainSpec::foundation());
+    let (genesis_root, _) = chain.head_block().unwrap();
+
+    // The shufflings of the first epochs depend upon the genesis block...
+    assert_eq!(chain.shuffling_dependent_root(0), Ok(Some(genesis_root)));
+    assert_eq!(chain.shuffling_dependent_root(1), Ok(Some(genesis_root)));
+    // ...whilst later shufflings depend upon blocks which are not yet known.
+    assert_eq!(chain.shuffling_dependent_root(2), Ok(None));
+}
+
+#[test]
+fn it_processes_a_block_it_produces() {
+    let (spec, keypairs) = spec_with_active_validators();
+    let (_db, mut chain) = in_memory_test_chain(spec);
+    let (mut block, _state) = chain.produce_block().unwrap();
+
+    let head_state = chain.head_state().unwrap();
+    let proposer = chain
+        .block_proposer_index(&head_state, block.slot)
+        .unwrap()
+        .unwrap();
+    sign_block(&chain, &mut block, &keypairs[proposer]);
+
+    let (outcome, new_block_hash) = chain.process_block(&block).unwrap();
+    assert_eq!(outcome, BlockProcessingOutcome::Processed);
+    assert_eq!(chain.canonical_leaf_block, new_block_hash);
+}
+
+#[test]
+fn it_finds_live_validators() {
+    let (spec, keypairs) = spec_with_active_validators();
+    let (_db, mut chain) = in_memory_test_chain(spec);
+    let indices: Vec<u64> = (0..keypairs.len() as u64).collect();
+
+    chain.slot_clock.set_slot(1);
+    let (mut block, _state) = chain.produce_block().unwrap();
+    let head_state = chain.head_state().unwrap();
+    let proposer = chain
+        .block_proposer_index(&head_state, block.slot)
+        .unwrap()
+        .unwrap();
+    sign_block(&chain, &mut block, &keypairs[proposer]);
+    chain.process_block(&block).unwrap();
+
+    // Validators may yet be seen live until the epoch has ended...
+    assert_eq!(chain.live_validators(0, &indices), Ok(None));
+
+    // ...after which only the proposer was live.
+    chain.slot_clock.set_slot(chain.spec.epoch_length);
+    assert_eq!(
+        chain.live_validators(0, &indices),
+        Ok(Some(vec![proposer as u64]))
+    );
+}
+
+/// Returns an exit for the validator at `validator_index`, signed by `keypair`.
+fn signed_exit(
+    chain: &BeaconChain<MemoryDB, TestingSlotClock>,
+    validator_index: u32,
+    keypair: &Keypair,
+) -> Exit {
+    let fork = chain.head_state().unwrap().fork_data;
+    let mut exit = Exit {
+        slot: 0,
+        validator_index,
+        signature: Signature::empty_signature(),
+    };
+    exit.signature = Signature::new_with_domain(
+        &exit.signing_root(),
+        fork.get_domain(exit.slot, chain.spec.domain_exit),
+        &keypair.sk,
+    );
+    exit
+}
+
+#[test]
+fn it_pools_valid_exits_for_inclusion() {
+    let (spec, keypairs) = spec_with_active_validators();
+    let (_db, mut chain) = in_memory_test_chain(spec);
+
+    // An exit signed by another validator is rejected.
+    let badly_signed = signed_exit(&chain, 0, &keypairs[1]);
+    assert_eq!(
+        chain.process_exit(badly_signed),
+        Ok(ExitProcessingOutcome::InvalidSignature)
+    );
+    let unknown = signed_exit(&chain, keypairs.len() as u32, &keypairs[0]);
+    assert_eq!(
+        chain.process_exit(unknown),
+        Ok(ExitProcessingOutcome::UnknownValidator)
+    );
+    assert!(chain.exit_pool.is_empty());
+
+    // A correctly signed exit is pooled and included in the next block.
+    let exit = signed_exit(&chain, 0, &keypairs[0]);
+    assert_eq!(
+        chain.process_exit(exit.clone()),
+        Ok(ExitProcessingOutcome::Accepted)
+    );
+    let (block, _state) = chain.produce_block().unwrap();
+    assert_eq!(block.body.exits, vec![exit]);
+}
+
+#[test]
+fn it_rejects_a_block_signed_by_another_validator() {
+    let (spec, keypairs) = spec_with_active_validators();
+    let (_db, mut chain) = in_memory_test_chain(spec);
+    let (mut block, _state) = chain.produce_block().unwrap();
+
+    let head_state = chain.head_state().unwrap();
+    let proposer = chain
+        .block_proposer_index(&head_state, block.slot)
+        .unwrap()
+        .unwrap();
+    let other = (proposer + 1) % keypairs.len();
+    sign_block(&chain, &mut block, &keypairs[other]);
+
+    let (outcome, _) = chain.process_block(&block).unwrap();
+    assert_eq!(outcome, BlockProcessingOutcome::InvalidBlockSignature);
+}
+
+#[test]
+fn it_rejects_a_block_with_an_invalid_randao_reveal() {
+    let (spec, keypairs) = spec_with_active_validators();
+    let (_db, mut chain) = in_memory_test_chain(spec);
+    let (mut block, _state) = chain.produce_block().unwrap();
+
+    let head_state = chain.head_state().unwrap();
+    let proposer = chain
+        .block_proposer_index(&head_state, block.slot)
+        .unwrap()
+        .unwrap();
+
+    // Reveal the wrong epoch, then sign the block (which commits to the reveal) correctly.
+    let fork = head_state.fork_data;
+    let epoch = block.slot / chain.spec.epoch_length;
+    block.randao_reveal = Signature::new_with_domain(
+        &ssz_encode(&(epoch + 1)),
+        fork.get_domain(block.slot, chain.spec.domain_randao),
+        &keypairs[proposer].sk,
+    );
+    block.signature = Signature::new_with_domain(
+        &block.proposal_root(chain.spec.beacon_chain_shard_number),
+        fork.get_domain(block.slot, chain.spec.domain_proposal),
+        &keypairs[proposer].sk,
+    );
+
+    let (outcome, _) = chain.process_block(&block).unwrap();
+    assert_eq!(outcome, BlockProcessingOutcome::InvalidRandaoReveal);
+}
@@ -4,9 +4,10 @@ extern crate types;
 
 use db::stores::BeaconBlockStore;
 use db::{ClientDB, DBError};
-use ssz::{Decodable, DecodeError};
+use ssz::DecodeError;
 use std::sync::Arc;
-use types::{BeaconBlock, Hash256};
+use types::readers::{BeaconBlockReader, SszBeaconBlock};
+use types::Hash256;
 
 pub enum ForkChoiceError {
     BadSszInDatabase,
@@ -21,25 +22,25 @@ pub fn naive_fork_choice<T>(
 where
     T: ClientDB + Sized,
 {
-    let mut head_blocks: Vec<(usize, BeaconBlock)> = vec![];
+    let mut head_slots: Vec<(usize, u64)> = vec![];
 
     /*
-     * Load all the head_block hashes from the DB as SszBeaconBlocks.
+     * Load all the head_block hashes from the DB and read their slots as SszBeaconBlocks.
      */
     for (index, block_hash) in head_block_hashes.iter().enumerate() {
         let ssz = block_store
             .get(&block_hash)?
             .ok_or(ForkChoiceError::MissingBlock)?;
-        let (block, _) = BeaconBlock::ssz_decode(&ssz, 0)?;
-        head_blocks.push((index, block));
+        let block = SszBeaconBlock::from_slice(&ssz)?;
+        head_slots.push((index, block.slot()));
     }
 
     /*
      * Loop through all the head blocks and find the highest slot.
      */
     let highest_slot: Option<u64> = None;
-    for (_, block) in &head_blocks {
-        let slot = block.slot;
+    for (_, slot) in &head_slots {
+        let slot = *slot;
 
         match highest_slot {
             None => Some(slot),
@@ -63,9 +64,9 @@ where
         None => Ok(None),
         Some(highest_slot) => {
             let mut highest_blocks = vec![];
-            for (index, block) in head_blocks {
-                if block.slot == highest_slot {
-                    highest_blocks.push((index, block))
+            for (index, slot) in head_slots {
+                if slot == highest_slot {
+                    highest_blocks.push((index, slot))
                 }
             }
 
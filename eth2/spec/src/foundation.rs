@@ -1,6 +1,11 @@
 use super::ChainSpec;
 use bls::{Keypair, PublicKey, SecretKey, Signature};
 
+use types::ssz_limits::{
+    DEPOSIT_CONTRACT_TREE_DEPTH, LATEST_BLOCK_ROOTS_LENGTH, LATEST_PENALIZED_EXIT_LENGTH,
+    LATEST_RANDAO_MIXES_LENGTH, MAX_ATTESTATIONS, MAX_CASPER_SLASHINGS, MAX_CASPER_VOTES,
+    MAX_DEPOSITS, MAX_EXITS, MAX_PROPOSER_SLASHINGS, SHARD_COUNT,
+};
 use types::{Address, Eth1Data, Hash256, Validator};
 
 /// The size of a validators deposit in GWei.
@@ -16,21 +21,21 @@ impl ChainSpec {
             /*
              * Misc
              */
-            shard_count: 1_024,
+            shard_count: SHARD_COUNT as u64,
             target_committee_size: 128,
             ejection_balance: 16 * u64::pow(10, 9),
             max_balance_churn_quotient: 32,
             beacon_chain_shard_number: u64::max_value(),
-            max_casper_votes: 1_024,
-            latest_block_roots_length: 8_192,
-            latest_randao_mixes_length: 8_192,
-            latest_penalized_exit_length: 8_192,
+            max_casper_votes: MAX_CASPER_VOTES as u64,
+            latest_block_roots_length: LATEST_BLOCK_ROOTS_LENGTH as u64,
+            latest_randao_mixes_length: LATEST_RANDAO_MIXES_LENGTH as u64,
+            latest_penalized_exit_length: LATEST_PENALIZED_EXIT_LENGTH as u64,
             max_withdrawals_per_epoch: 4,
             /*
              *  Deposit contract
              */
             deposit_contract_address: Address::from("TBD".as_bytes()),
-            deposit_contract_tree_depth: 32,
+            deposit_contract_tree_depth: DEPOSIT_CONTRACT_TREE_DEPTH as u64,
             min_deposit: 1 * u64::pow(10, 9),
             max_deposit: 32 * u64::pow(10, 9),
             /*
@@ -63,11 +68,19 @@ impl ChainSpec {
             /*
              * Max operations per block
              */
-            max_proposer_slashings: 16,
-            max_casper_slashings: 16,
-            max_attestations: 128,
-            max_deposits: 16,
-            max_exits: 16,
+            max_proposer_slashings: MAX_PROPOSER_SLASHINGS as u64,
+            max_casper_slashings: MAX_CASPER_SLASHINGS as u64,
+            max_attestations: MAX_ATTESTATIONS as u64,
+            max_deposits: MAX_DEPOSITS as u64,
+            max_exits: MAX_EXITS as u64,
+            /*
+             * Signature domains
+             */
+            domain_deposit: 0,
+            domain_attestation: 1,
+            domain_proposal: 2,
+            domain_exit: 3,
+            domain_randao: 4,
             /*
              * Intialization parameters
              */
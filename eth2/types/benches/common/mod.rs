@@ -0,0 +1,54 @@
+//! Builds the states shared by the benchmarks, each of which uses only some of these items.
+#![allow(dead_code)]
+
+use bls::{Keypair, PublicKey};
+use types::{BeaconState, Crosslink, Hash256, Validator};
+
+pub const VALIDATOR_COUNT: usize = 100_000;
+// Lengths from the foundation `ChainSpec`.
+const SHARD_COUNT: usize = 1_024;
+pub const LATEST_BLOCK_ROOTS_LENGTH: usize = 8_192;
+pub const LATEST_RANDAO_MIXES_LENGTH: usize = 8_192;
+const LATEST_PENALIZED_EXIT_LENGTH: usize = 8_192;
+
+/// Returns a state with `validator_count` validators and full-length recent state lists.
+pub fn build_state(validator_count: usize) -> BeaconState {
+    // Generating a keypair per validator is slow and irrelevant to hashing, so a few are shared.
+    let pubkeys: Vec<PublicKey> = (0..16).map(|_| Keypair::random().pk).collect();
+
+    let validator_registry = (0..validator_count)
+        .map(|i| Validator {
+            pubkey: pubkeys[i % pubkeys.len()].clone(),
+            withdrawal_credentials: Hash256::from(i as u64),
+            proposer_slots: 0,
+            activation_slot: 0,
+            exit_slot: u64::max_value(),
+            withdrawal_slot: u64::max_value(),
+            penalized_slot: u64::max_value(),
+            exit_count: 0,
+            status_flags: None,
+            latest_custody_reseed_slot: 0,
+            penultimate_custody_reseed_slot: 0,
+        })
+        .collect();
+
+    BeaconState {
+        validator_registry,
+        validator_balances: vec![32_000_000_000; validator_count],
+        latest_randao_mixes: (0..LATEST_RANDAO_MIXES_LENGTH)
+            .map(|i| Hash256::from(i as u64))
+            .collect(),
+        latest_crosslinks: vec![
+            Crosslink {
+                slot: 0,
+                shard_block_root: Hash256::zero(),
+            };
+            SHARD_COUNT
+        ],
+        latest_block_roots: (0..LATEST_BLOCK_ROOTS_LENGTH)
+            .map(|i| Hash256::from(i as u64))
+            .collect(),
+        latest_penalized_exit_balances: vec![0; LATEST_PENALIZED_EXIT_LENGTH],
+        ..BeaconState::default()
+    }
+}
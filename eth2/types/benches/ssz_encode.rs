@@ -0,0 +1,93 @@
+use criterion::{criterion_group, criterion_main, Benchmark, Criterion};
+use ssz::{ssz_encode, ssz_encode_into, Encodable, SszStream};
+use types::BeaconState;
+
+mod common;
+
+use crate::common::{build_state, VALIDATOR_COUNT};
+
+/// Reimplements the encoder prior to size hints, as the baseline for comparison: the stream is
+/// not pre-sized, and each list is encoded into a nested buffer before being copied into its
+/// parent. The elements of each list are encoded with their present implementations.
+mod legacy {
+    use super::*;
+
+    pub fn ssz_encode(state: &BeaconState) -> Vec<u8> {
+        let mut s = SszStream::new();
+        s.append(&state.slot);
+        s.append(&state.genesis_time);
+        s.append(&state.fork_data);
+        append_vec(&mut s, &state.validator_registry);
+        append_vec(&mut s, &state.validator_balances);
+        s.append(&state.validator_registry_update_slot);
+        s.append(&state.validator_registry_exit_count);
+        s.append(&state.validator_registry_delta_chain_tip);
+        append_fixed_vec(&mut s, &state.latest_randao_mixes);
+        append_vec(&mut s, &state.latest_vdf_outputs);
+        s.append(&state.previous_epoch_start_shard);
+        s.append(&state.current_epoch_start_shard);
+        s.append(&state.previous_epoch_calculation_slot);
+        s.append(&state.current_epoch_calculation_slot);
+        s.append(&state.previous_epoch_randao_mix);
+        s.append(&state.current_epoch_randao_mix);
+        append_vec(&mut s, &state.custody_challenges);
+        s.append(&state.previous_justified_slot);
+        s.append(&state.justified_slot);
+        s.append(&state.justification_bitfield);
+        s.append(&state.finalized_slot);
+        append_fixed_vec(&mut s, &state.latest_crosslinks);
+        append_fixed_vec(&mut s, &state.latest_block_roots);
+        append_fixed_vec(&mut s, &state.latest_penalized_exit_balances);
+        append_vec(&mut s, &state.latest_attestations);
+        append_vec(&mut s, &state.batched_block_roots);
+        s.append(&state.latest_eth1_data);
+        append_vec(&mut s, &state.eth1_data_votes);
+        s.drain()
+    }
+
+    fn nested<E: Encodable>(vec: &[E]) -> Vec<u8> {
+        let mut list_stream = SszStream::new();
+        for item in vec {
+            item.ssz_append(&mut list_stream);
+        }
+        list_stream.drain()
+    }
+
+    fn append_vec<E: Encodable>(s: &mut SszStream, vec: &[E]) {
+        s.append_encoded_val(&nested(vec));
+    }
+
+    fn append_fixed_vec<E: Encodable>(s: &mut SszStream, vec: &[E]) {
+        s.append_encoded_raw(&nested(vec));
+    }
+}
+
+fn ssz_encode_state(c: &mut Criterion) {
+    let state = build_state(VALIDATOR_COUNT);
+
+    // Ensures the baseline has not drifted from the `BeaconState` encoding.
+    assert_eq!(legacy::ssz_encode(&state), ssz_encode(&state));
+
+    let legacy_state = state.clone();
+    let buffer_state = state.clone();
+    let mut buffer = Vec::with_capacity(ssz_encode(&state).len());
+
+    c.bench(
+        "beacon_state_ssz_encode_100k_validators",
+        Benchmark::new("legacy_nested_buffers", move |b| {
+            b.iter(|| legacy::ssz_encode(&legacy_state))
+        })
+        .with_function("ssz_encode", move |b| b.iter(|| ssz_encode(&state)))
+        .with_function("reused_buffer", move |b| {
+            b.iter(|| {
+                buffer.clear();
+                ssz_encode_into(&buffer_state, &mut buffer);
+                buffer.len()
+            })
+        })
+        .sample_size(10),
+    );
+}
+
+criterion_group!(benches, ssz_encode_state);
+criterion_main!(benches);
@@ -0,0 +1,43 @@
+use criterion::{criterion_group, criterion_main, Benchmark, Criterion};
+use types::{BeaconStateTreeHashCache, Hash256};
+
+mod common;
+
+use crate::common::{
+    build_state, LATEST_BLOCK_ROOTS_LENGTH, LATEST_RANDAO_MIXES_LENGTH, VALIDATOR_COUNT,
+};
+
+fn tree_hash(c: &mut Criterion) {
+    let state = build_state(VALIDATOR_COUNT);
+
+    let uncached_state = state.clone();
+
+    let mut cached_state = state;
+    let mut cache = BeaconStateTreeHashCache::default();
+    cached_state.cached_canonical_root(&mut cache);
+
+    c.bench(
+        "beacon_state_root_100k_validators",
+        Benchmark::new("uncached", move |b| {
+            b.iter(|| uncached_state.canonical_root())
+        })
+        .with_function("cached", move |b| {
+            b.iter(|| {
+                // Advance the slot and change a few entries, as when producing the next state.
+                cached_state.slot += 1;
+                let i = cached_state.slot as usize;
+                cached_state.validator_balances[i % VALIDATOR_COUNT] += 1;
+                cached_state.latest_block_roots[i % LATEST_BLOCK_ROOTS_LENGTH] =
+                    Hash256::from(cached_state.slot);
+                cached_state.latest_randao_mixes[i % LATEST_RANDAO_MIXES_LENGTH] =
+                    Hash256::from(cached_state.slot);
+
+                cached_state.cached_canonical_root(&mut cache)
+            })
+        })
+        .sample_size(10),
+    );
+}
+
+criterion_group!(benches, tree_hash);
+criterion_main!(benches);
@@ -1,10 +1,13 @@
 use super::bls::AggregateSignature;
-use super::ssz::{hash, Decodable, DecodeError, Encodable, SszStream, TreeHash};
 use super::{AttestationData, Bitfield};
 use crate::test_utils::TestRandom;
 use rand::RngCore;
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode, TreeHash};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TreeHash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Attestation {
     pub data: AttestationData,
     pub aggregation_bitfield: Bitfield,
@@ -12,32 +15,6 @@ pub struct Attestation {
     pub aggregate_signature: AggregateSignature,
 }
 
-impl Encodable for Attestation {
-    fn ssz_append(&self, s: &mut SszStream) {
-        s.append(&self.data);
-        s.append(&self.aggregation_bitfield);
-        s.append(&self.custody_bitfield);
-        s.append(&self.aggregate_signature);
-    }
-}
-
-impl Decodable for Attestation {
-    fn ssz_decode(bytes: &[u8], i: usize) -> Result<(Self, usize), DecodeError> {
-        let (data, i) = AttestationData::ssz_decode(bytes, i)?;
-        let (aggregation_bitfield, i) = Bitfield::ssz_decode(bytes, i)?;
-        let (custody_bitfield, i) = Bitfield::ssz_decode(bytes, i)?;
-        let (aggregate_signature, i) = AggregateSignature::ssz_decode(bytes, i)?;
-
-        let attestation_record = Self {
-            data,
-            aggregation_bitfield,
-            custody_bitfield,
-            aggregate_signature,
-        };
-        Ok((attestation_record, i))
-    }
-}
-
 impl Attestation {
     pub fn zero() -> Self {
         Self {
@@ -49,17 +26,6 @@ impl Attestation {
     }
 }
 
-impl TreeHash for Attestation {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        let mut result: Vec<u8> = vec![];
-        result.append(&mut self.data.hash_tree_root());
-        result.append(&mut self.aggregation_bitfield.hash_tree_root());
-        result.append(&mut self.custody_bitfield.hash_tree_root());
-        result.append(&mut self.aggregate_signature.hash_tree_root());
-        hash(&result)
-    }
-}
-
 impl<T: RngCore> TestRandom<T> for Attestation {
     fn random_for_test(rng: &mut T) -> Self {
         Self {
@@ -73,7 +39,7 @@ impl<T: RngCore> TestRandom<T> for Attestation {
 
 #[cfg(test)]
 mod tests {
-    use super::super::ssz::ssz_encode;
+    use super::super::ssz::{ssz_encode, Decodable, TreeHash};
     use super::*;
     use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
 
@@ -1,7 +1,10 @@
-use super::ssz::{hash, Decodable, DecodeError, Encodable, SszStream, TreeHash};
-use super::Hash256;
+use super::ssz::TreeHash;
+use super::{AttestationDataAndCustodyBit, Hash256};
 use crate::test_utils::TestRandom;
 use rand::RngCore;
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode, TreeHash};
 
 pub const SSZ_ATTESTION_DATA_LENGTH: usize = {
     8 +             // slot
@@ -14,7 +17,8 @@ pub const SSZ_ATTESTION_DATA_LENGTH: usize = {
     32 // justified_block_root
 };
 
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, Encode, Decode, TreeHash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct AttestationData {
     pub slot: u64,
     pub shard: u64,
@@ -40,63 +44,18 @@ impl AttestationData {
         }
     }
 
-    // TODO: Implement this as a merkle root, once tree_ssz is implemented.
-    // https://github.com/sigp/lighthouse/issues/92
     pub fn canonical_root(&self) -> Hash256 {
-        Hash256::zero()
+        Hash256::from(&self.hash_tree_root()[..])
     }
-}
-
-impl Encodable for AttestationData {
-    fn ssz_append(&self, s: &mut SszStream) {
-        s.append(&self.slot);
-        s.append(&self.shard);
-        s.append(&self.beacon_block_root);
-        s.append(&self.epoch_boundary_root);
-        s.append(&self.shard_block_root);
-        s.append(&self.latest_crosslink_root);
-        s.append(&self.justified_slot);
-        s.append(&self.justified_block_root);
-    }
-}
 
-impl Decodable for AttestationData {
-    fn ssz_decode(bytes: &[u8], i: usize) -> Result<(Self, usize), DecodeError> {
-        let (slot, i) = u64::ssz_decode(bytes, i)?;
-        let (shard, i) = u64::ssz_decode(bytes, i)?;
-        let (beacon_block_root, i) = Hash256::ssz_decode(bytes, i)?;
-        let (epoch_boundary_root, i) = Hash256::ssz_decode(bytes, i)?;
-        let (shard_block_root, i) = Hash256::ssz_decode(bytes, i)?;
-        let (latest_crosslink_root, i) = Hash256::ssz_decode(bytes, i)?;
-        let (justified_slot, i) = u64::ssz_decode(bytes, i)?;
-        let (justified_block_root, i) = Hash256::ssz_decode(bytes, i)?;
-
-        let attestation_data = AttestationData {
-            slot,
-            shard,
-            beacon_block_root,
-            epoch_boundary_root,
-            shard_block_root,
-            latest_crosslink_root,
-            justified_slot,
-            justified_block_root,
+    /// Returns the root signed by an attester: the `hash_tree_root` of the data together with the
+    /// `custody_bit`.
+    pub fn signing_root(&self, custody_bit: bool) -> Hash256 {
+        let data_and_custody_bit = AttestationDataAndCustodyBit {
+            data: self.clone(),
+            custody_bit,
         };
-        Ok((attestation_data, i))
-    }
-}
-
-impl TreeHash for AttestationData {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        let mut result: Vec<u8> = vec![];
-        result.append(&mut self.slot.hash_tree_root());
-        result.append(&mut self.shard.hash_tree_root());
-        result.append(&mut self.beacon_block_root.hash_tree_root());
-        result.append(&mut self.epoch_boundary_root.hash_tree_root());
-        result.append(&mut self.shard_block_root.hash_tree_root());
-        result.append(&mut self.latest_crosslink_root.hash_tree_root());
-        result.append(&mut self.justified_slot.hash_tree_root());
-        result.append(&mut self.justified_block_root.hash_tree_root());
-        hash(&result)
+        Hash256::from(&data_and_custody_bit.hash_tree_root()[..])
     }
 }
 
@@ -117,7 +76,7 @@ impl<T: RngCore> TestRandom<T> for AttestationData {
 
 #[cfg(test)]
 mod tests {
-    use super::super::ssz::ssz_encode;
+    use super::super::ssz::{ssz_encode, Decodable};
     use super::*;
     use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
 
@@ -1,45 +1,18 @@
-use super::ssz::{Decodable, DecodeError, Encodable, hash, TreeHash, SszStream};
-use rand::RngCore;
-use crate::test_utils::TestRandom;
 use super::AttestationData;
+use crate::test_utils::TestRandom;
+use rand::RngCore;
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode, TreeHash};
 
-#[derive(Debug, Clone, PartialEq, Default)]
+/// The message signed by an attester: the `AttestationData` along with the custody bit.
+#[derive(Debug, Clone, PartialEq, Default, Encode, Decode, TreeHash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct AttestationDataAndCustodyBit {
     pub data: AttestationData,
     pub custody_bit: bool,
 }
 
-impl Encodable for AttestationDataAndCustodyBit {
-    fn ssz_append(&self, s: &mut SszStream) {
-        s.append(&self.data);
-        s.append(&self.custody_bit);
-    }
-}
-
-impl Decodable for AttestationDataAndCustodyBit {
-    fn ssz_decode(bytes: &[u8], i: usize) -> Result<(Self, usize), DecodeError> {
-        let (data, i) = <_>::ssz_decode(bytes, i)?;
-        let (custody_bit, i) = <_>::ssz_decode(bytes, i)?;
-
-        let attestation_data_and_custody_bit = AttestationDataAndCustodyBit {
-            data,
-            custody_bit,
-        };
-
-        Ok((attestation_data_and_custody_bit, i))
-    }
-}
-
-impl TreeHash for AttestationDataAndCustodyBit {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        let result: Vec<u8> = vec![];
-        result.append(&mut self.data.hash_tree_root());
-        // TODO: add bool ssz
-        // result.append(custody_bit.hash_tree_root());
-        ssz::hash(&result)
-    }
-}
-
 impl<T: RngCore> TestRandom<T> for AttestationDataAndCustodyBit {
     fn random_for_test(rng: &mut T) -> Self {
         Self {
@@ -51,9 +24,9 @@ impl<T: RngCore> TestRandom<T> for AttestationDataAndCustodyBit {
 
 #[cfg(test)]
 mod test {
-    use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use super::super::ssz::{ssz_encode, Decodable, TreeHash};
     use super::*;
-    use super::super::ssz::ssz_encode;
+    use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
 
     #[test]
     pub fn test_ssz_round_trip() {
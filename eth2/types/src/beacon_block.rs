@@ -1,11 +1,14 @@
-use super::ssz::{hash, ssz_encode, Decodable, DecodeError, Encodable, SszStream, TreeHash};
-use super::{BeaconBlockBody, Eth1Data, Hash256};
+use super::ssz::TreeHash;
+use super::{BeaconBlockBody, Eth1Data, Hash256, ProposalSignedData};
 use crate::test_utils::TestRandom;
 use bls::Signature;
-use hashing::canonical_hash;
 use rand::RngCore;
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode, TreeHash};
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Encode, Decode, TreeHash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct BeaconBlock {
     pub slot: u64,
     pub parent_root: Hash256,
@@ -18,60 +21,27 @@ pub struct BeaconBlock {
 
 impl BeaconBlock {
     pub fn canonical_root(&self) -> Hash256 {
-        // TODO: implement tree hashing.
-        // https://github.com/sigp/lighthouse/issues/70
-        Hash256::from(&canonical_hash(&ssz_encode(self))[..])
+        Hash256::from(&self.hash_tree_root()[..])
     }
-}
 
-impl Encodable for BeaconBlock {
-    fn ssz_append(&self, s: &mut SszStream) {
-        s.append(&self.slot);
-        s.append(&self.parent_root);
-        s.append(&self.state_root);
-        s.append(&self.randao_reveal);
-        s.append(&self.eth1_data);
-        s.append(&self.signature);
-        s.append(&self.body);
-    }
-}
+    /// Returns the root of the `ProposalSignedData` for this block; the message which is signed
+    /// by the block proposer.
+    ///
+    /// The `signature` of the block is not included in the root.
+    pub fn proposal_root(&self, beacon_chain_shard_number: u64) -> Hash256 {
+        let block_without_signature_root = {
+            let mut block = self.clone();
+            block.signature = Signature::empty_signature();
+            block.canonical_root()
+        };
 
-impl Decodable for BeaconBlock {
-    fn ssz_decode(bytes: &[u8], i: usize) -> Result<(Self, usize), DecodeError> {
-        let (slot, i) = <_>::ssz_decode(bytes, i)?;
-        let (parent_root, i) = <_>::ssz_decode(bytes, i)?;
-        let (state_root, i) = <_>::ssz_decode(bytes, i)?;
-        let (randao_reveal, i) = <_>::ssz_decode(bytes, i)?;
-        let (eth1_data, i) = <_>::ssz_decode(bytes, i)?;
-        let (signature, i) = <_>::ssz_decode(bytes, i)?;
-        let (body, i) = <_>::ssz_decode(bytes, i)?;
-
-        Ok((
-            Self {
-                slot,
-                parent_root,
-                state_root,
-                randao_reveal,
-                eth1_data,
-                signature,
-                body,
-            },
-            i,
-        ))
-    }
-}
+        let proposal = ProposalSignedData {
+            slot: self.slot,
+            shard: beacon_chain_shard_number,
+            block_root: block_without_signature_root,
+        };
 
-impl TreeHash for BeaconBlock {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        let mut result: Vec<u8> = vec![];
-        result.append(&mut self.slot.hash_tree_root());
-        result.append(&mut self.parent_root.hash_tree_root());
-        result.append(&mut self.state_root.hash_tree_root());
-        result.append(&mut self.randao_reveal.hash_tree_root());
-        result.append(&mut self.eth1_data.hash_tree_root());
-        result.append(&mut self.signature.hash_tree_root());
-        result.append(&mut self.body.hash_tree_root());
-        hash(&result)
+        Hash256::from(&proposal.hash_tree_root()[..])
     }
 }
 
@@ -91,7 +61,7 @@ impl<T: RngCore> TestRandom<T> for BeaconBlock {
 
 #[cfg(test)]
 mod tests {
-    use super::super::ssz::ssz_encode;
+    use super::super::ssz::{ssz_encode, Decodable};
     use super::*;
     use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
 
@@ -106,6 +76,32 @@ mod tests {
         assert_eq!(original, decoded);
     }
 
+    #[test]
+    #[cfg(feature = "serialize")]
+    pub fn test_json_round_trip() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let original = BeaconBlock::random_for_test(&mut rng);
+
+        let json = serde_json::to_string(&original).unwrap();
+        let decoded: BeaconBlock = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(original, decoded);
+    }
+
+    #[test]
+    pub fn test_proposal_root_excludes_signature() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let mut block = BeaconBlock::random_for_test(&mut rng);
+
+        let root = block.proposal_root(0);
+        block.signature = Signature::random_for_test(&mut rng);
+        assert_eq!(block.proposal_root(0), root);
+
+        assert_ne!(block.proposal_root(1), root);
+        block.slot += 1;
+        assert_ne!(block.proposal_root(0), root);
+    }
+
     #[test]
     pub fn test_hash_tree_root() {
         let mut rng = XorShiftRng::from_seed([42; 16]);
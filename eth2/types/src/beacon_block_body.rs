@@ -1,7 +1,12 @@
-use super::ssz::{hash, Decodable, DecodeError, Encodable, SszStream, TreeHash};
 use super::{Attestation, CasperSlashing, Deposit, Exit, ProposerSlashing};
+use crate::ssz_limits::{
+    MAX_ATTESTATIONS, MAX_CASPER_SLASHINGS, MAX_DEPOSITS, MAX_EXITS, MAX_PROPOSER_SLASHINGS,
+};
 use crate::test_utils::TestRandom;
 use rand::RngCore;
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode, TreeHash};
 
 // The following types are just dummy classes as they will not be defined until
 // Phase 1 (Sharding phase)
@@ -9,73 +14,24 @@ type CustodyReseed = usize;
 type CustodyChallenge = usize;
 type CustodyResponse = usize;
 
-#[derive(Debug, PartialEq, Clone, Default)]
+#[derive(Debug, PartialEq, Clone, Default, Encode, Decode, TreeHash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct BeaconBlockBody {
+    #[ssz(max_length = "MAX_PROPOSER_SLASHINGS")]
     pub proposer_slashings: Vec<ProposerSlashing>,
+    #[ssz(max_length = "MAX_CASPER_SLASHINGS")]
     pub casper_slashings: Vec<CasperSlashing>,
+    #[ssz(max_length = "MAX_ATTESTATIONS")]
     pub attestations: Vec<Attestation>,
     pub custody_reseeds: Vec<CustodyReseed>,
     pub custody_challenges: Vec<CustodyChallenge>,
     pub custody_responses: Vec<CustodyResponse>,
+    #[ssz(max_length = "MAX_DEPOSITS")]
     pub deposits: Vec<Deposit>,
+    #[ssz(max_length = "MAX_EXITS")]
     pub exits: Vec<Exit>,
 }
 
-impl Encodable for BeaconBlockBody {
-    fn ssz_append(&self, s: &mut SszStream) {
-        s.append_vec(&self.proposer_slashings);
-        s.append_vec(&self.casper_slashings);
-        s.append_vec(&self.attestations);
-        s.append_vec(&self.custody_reseeds);
-        s.append_vec(&self.custody_challenges);
-        s.append_vec(&self.custody_responses);
-        s.append_vec(&self.deposits);
-        s.append_vec(&self.exits);
-    }
-}
-
-impl Decodable for BeaconBlockBody {
-    fn ssz_decode(bytes: &[u8], i: usize) -> Result<(Self, usize), DecodeError> {
-        let (proposer_slashings, i) = <_>::ssz_decode(bytes, i)?;
-        let (casper_slashings, i) = <_>::ssz_decode(bytes, i)?;
-        let (attestations, i) = <_>::ssz_decode(bytes, i)?;
-        let (custody_reseeds, i) = <_>::ssz_decode(bytes, i)?;
-        let (custody_challenges, i) = <_>::ssz_decode(bytes, i)?;
-        let (custody_responses, i) = <_>::ssz_decode(bytes, i)?;
-        let (deposits, i) = <_>::ssz_decode(bytes, i)?;
-        let (exits, i) = <_>::ssz_decode(bytes, i)?;
-
-        Ok((
-            Self {
-                proposer_slashings,
-                casper_slashings,
-                attestations,
-                custody_reseeds,
-                custody_challenges,
-                custody_responses,
-                deposits,
-                exits,
-            },
-            i,
-        ))
-    }
-}
-
-impl TreeHash for BeaconBlockBody {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        let mut result: Vec<u8> = vec![];
-        result.append(&mut self.proposer_slashings.hash_tree_root());
-        result.append(&mut self.casper_slashings.hash_tree_root());
-        result.append(&mut self.attestations.hash_tree_root());
-        result.append(&mut self.custody_reseeds.hash_tree_root());
-        result.append(&mut self.custody_challenges.hash_tree_root());
-        result.append(&mut self.custody_responses.hash_tree_root());
-        result.append(&mut self.deposits.hash_tree_root());
-        result.append(&mut self.exits.hash_tree_root());
-        hash(&result)
-    }
-}
-
 impl<T: RngCore> TestRandom<T> for BeaconBlockBody {
     fn random_for_test(rng: &mut T) -> Self {
         Self {
@@ -93,7 +49,7 @@ impl<T: RngCore> TestRandom<T> for BeaconBlockBody {
 
 #[cfg(test)]
 mod tests {
-    use super::super::ssz::ssz_encode;
+    use super::super::ssz::{ssz_encode, Decodable, DecodeError, TreeHash};
     use super::*;
     use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
 
@@ -108,6 +64,28 @@ mod tests {
         assert_eq!(original, decoded);
     }
 
+    #[test]
+    pub fn test_ssz_decode_too_many_attestations() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let mut original = BeaconBlockBody::random_for_test(&mut rng);
+        let attestation = Attestation::random_for_test(&mut rng);
+        original.attestations = vec![attestation; MAX_ATTESTATIONS + 1];
+
+        let bytes = ssz_encode(&original);
+        let offset = ssz_encode(&original.proposer_slashings).len()
+            + ssz_encode(&original.casper_slashings).len();
+        assert_eq!(
+            BeaconBlockBody::ssz_decode(&bytes, 0),
+            Err(DecodeError::InField {
+                path: "attestations".to_string(),
+                offset,
+                error: Box::new(DecodeError::TooManyElements {
+                    max_length: MAX_ATTESTATIONS
+                }),
+            })
+        );
+    }
+
     #[test]
     pub fn test_hash_tree_root() {
         let mut rng = XorShiftRng::from_seed([42; 16]);
@@ -3,32 +3,47 @@ use super::eth1_data::Eth1Data;
 use super::eth1_data_vote::Eth1DataVote;
 use super::fork::Fork;
 use super::pending_attestation::PendingAttestation;
-use super::ssz::{hash, ssz_encode, Decodable, DecodeError, Encodable, SszStream, TreeHash};
+use super::ssz::TreeHash;
+use super::ssz_limits::{
+    LATEST_BLOCK_ROOTS_LENGTH, LATEST_PENALIZED_EXIT_LENGTH, LATEST_RANDAO_MIXES_LENGTH,
+    SHARD_COUNT,
+};
 use super::validator::Validator;
 use super::Hash256;
-use crate::test_utils::TestRandom;
-use hashing::canonical_hash;
+use crate::test_utils::{random_fixed_vec, TestRandom};
 use rand::RngCore;
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{CachedTreeHash, Decode, Encode, TreeHash};
 
 // Custody will not be added to the specs until Phase 1 (Sharding Phase) so dummy class used.
 type CustodyChallenge = usize;
 
-#[derive(Debug, PartialEq, Clone, Default)]
+/// The merkle trees of the fields with the `tree_hash(cached)` attribute are held in a
+/// `BeaconStateTreeHashCache`, which is generated by the `CachedTreeHash` derive.
+#[derive(Debug, PartialEq, Clone, Default, Encode, Decode, TreeHash, CachedTreeHash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct BeaconState {
     // Misc
     pub slot: u64,
     pub genesis_time: u64,
+    #[cfg_attr(feature = "serialize", serde(rename = "fork"))]
     pub fork_data: Fork,
 
     // Validator registry
+    #[tree_hash(cached)]
     pub validator_registry: Vec<Validator>,
+    #[tree_hash(cached)]
     pub validator_balances: Vec<u64>,
     pub validator_registry_update_slot: u64,
     pub validator_registry_exit_count: u64,
     pub validator_registry_delta_chain_tip: Hash256,
 
     // Randomness and committees
+    #[ssz(fixed_length = "LATEST_RANDAO_MIXES_LENGTH")]
+    #[tree_hash(cached)]
     pub latest_randao_mixes: Vec<Hash256>,
+    #[tree_hash(cached)]
     pub latest_vdf_outputs: Vec<Hash256>,
     pub previous_epoch_start_shard: u64,
     pub current_epoch_start_shard: u64,
@@ -47,10 +62,18 @@ pub struct BeaconState {
     pub finalized_slot: u64,
 
     // Recent state
+    #[ssz(fixed_length = "SHARD_COUNT")]
+    #[tree_hash(cached)]
     pub latest_crosslinks: Vec<Crosslink>,
+    #[ssz(fixed_length = "LATEST_BLOCK_ROOTS_LENGTH")]
+    #[tree_hash(cached)]
     pub latest_block_roots: Vec<Hash256>,
+    #[ssz(fixed_length = "LATEST_PENALIZED_EXIT_LENGTH")]
+    #[tree_hash(cached)]
     pub latest_penalized_exit_balances: Vec<u64>,
+    #[tree_hash(cached)]
     pub latest_attestations: Vec<PendingAttestation>,
+    #[tree_hash(cached)]
     pub batched_block_roots: Vec<Hash256>,
 
     // Ethereum 1.0 chain data
@@ -60,144 +83,13 @@ pub struct BeaconState {
 
 impl BeaconState {
     pub fn canonical_root(&self) -> Hash256 {
-        // TODO: implement tree hashing.
-        // https://github.com/sigp/lighthouse/issues/70
-        Hash256::from(&canonical_hash(&ssz_encode(self))[..])
+        Hash256::from(&self.hash_tree_root()[..])
     }
-}
-
-impl Encodable for BeaconState {
-    fn ssz_append(&self, s: &mut SszStream) {
-        s.append(&self.slot);
-        s.append(&self.genesis_time);
-        s.append(&self.fork_data);
-        s.append(&self.validator_registry);
-        s.append(&self.validator_balances);
-        s.append(&self.validator_registry_update_slot);
-        s.append(&self.validator_registry_exit_count);
-        s.append(&self.validator_registry_delta_chain_tip);
-        s.append(&self.latest_randao_mixes);
-        s.append(&self.latest_vdf_outputs);
-        s.append(&self.previous_epoch_start_shard);
-        s.append(&self.current_epoch_start_shard);
-        s.append(&self.previous_epoch_calculation_slot);
-        s.append(&self.current_epoch_calculation_slot);
-        s.append(&self.previous_epoch_randao_mix);
-        s.append(&self.current_epoch_randao_mix);
-        s.append(&self.custody_challenges);
-        s.append(&self.previous_justified_slot);
-        s.append(&self.justified_slot);
-        s.append(&self.justification_bitfield);
-        s.append(&self.finalized_slot);
-        s.append(&self.latest_crosslinks);
-        s.append(&self.latest_block_roots);
-        s.append(&self.latest_penalized_exit_balances);
-        s.append(&self.latest_attestations);
-        s.append(&self.batched_block_roots);
-        s.append(&self.latest_eth1_data);
-        s.append(&self.eth1_data_votes);
-    }
-}
-
-impl Decodable for BeaconState {
-    fn ssz_decode(bytes: &[u8], i: usize) -> Result<(Self, usize), DecodeError> {
-        let (slot, i) = <_>::ssz_decode(bytes, i)?;
-        let (genesis_time, i) = <_>::ssz_decode(bytes, i)?;
-        let (fork_data, i) = <_>::ssz_decode(bytes, i)?;
-        let (validator_registry, i) = <_>::ssz_decode(bytes, i)?;
-        let (validator_balances, i) = <_>::ssz_decode(bytes, i)?;
-        let (validator_registry_update_slot, i) = <_>::ssz_decode(bytes, i)?;
-        let (validator_registry_exit_count, i) = <_>::ssz_decode(bytes, i)?;
-        let (validator_registry_delta_chain_tip, i) = <_>::ssz_decode(bytes, i)?;
-        let (latest_randao_mixes, i) = <_>::ssz_decode(bytes, i)?;
-        let (latest_vdf_outputs, i) = <_>::ssz_decode(bytes, i)?;
-        let (previous_epoch_start_shard, i) = <_>::ssz_decode(bytes, i)?;
-        let (current_epoch_start_shard, i) = <_>::ssz_decode(bytes, i)?;
-        let (previous_epoch_calculation_slot, i) = <_>::ssz_decode(bytes, i)?;
-        let (current_epoch_calculation_slot, i) = <_>::ssz_decode(bytes, i)?;
-        let (previous_epoch_randao_mix, i) = <_>::ssz_decode(bytes, i)?;
-        let (current_epoch_randao_mix, i) = <_>::ssz_decode(bytes, i)?;
-        let (custody_challenges, i) = <_>::ssz_decode(bytes, i)?;
-        let (previous_justified_slot, i) = <_>::ssz_decode(bytes, i)?;
-        let (justified_slot, i) = <_>::ssz_decode(bytes, i)?;
-        let (justification_bitfield, i) = <_>::ssz_decode(bytes, i)?;
-        let (finalized_slot, i) = <_>::ssz_decode(bytes, i)?;
-        let (latest_crosslinks, i) = <_>::ssz_decode(bytes, i)?;
-        let (latest_block_roots, i) = <_>::ssz_decode(bytes, i)?;
-        let (latest_penalized_exit_balances, i) = <_>::ssz_decode(bytes, i)?;
-        let (latest_attestations, i) = <_>::ssz_decode(bytes, i)?;
-        let (batched_block_roots, i) = <_>::ssz_decode(bytes, i)?;
-        let (latest_eth1_data, i) = <_>::ssz_decode(bytes, i)?;
-        let (eth1_data_votes, i) = <_>::ssz_decode(bytes, i)?;
-
-        Ok((
-            Self {
-                slot,
-                genesis_time,
-                fork_data,
-                validator_registry,
-                validator_balances,
-                validator_registry_update_slot,
-                validator_registry_exit_count,
-                validator_registry_delta_chain_tip,
-                latest_randao_mixes,
-                latest_vdf_outputs,
-                previous_epoch_start_shard,
-                current_epoch_start_shard,
-                previous_epoch_calculation_slot,
-                current_epoch_calculation_slot,
-                previous_epoch_randao_mix,
-                current_epoch_randao_mix,
-                custody_challenges,
-                previous_justified_slot,
-                justified_slot,
-                justification_bitfield,
-                finalized_slot,
-                latest_crosslinks,
-                latest_block_roots,
-                latest_penalized_exit_balances,
-                latest_attestations,
-                batched_block_roots,
-                latest_eth1_data,
-                eth1_data_votes,
-            },
-            i,
-        ))
-    }
-}
 
-impl TreeHash for BeaconState {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        let mut result: Vec<u8> = vec![];
-        result.append(&mut self.slot.hash_tree_root());
-        result.append(&mut self.genesis_time.hash_tree_root());
-        result.append(&mut self.fork_data.hash_tree_root());
-        result.append(&mut self.validator_registry.hash_tree_root());
-        result.append(&mut self.validator_balances.hash_tree_root());
-        result.append(&mut self.validator_registry_update_slot.hash_tree_root());
-        result.append(&mut self.validator_registry_exit_count.hash_tree_root());
-        result.append(&mut self.validator_registry_delta_chain_tip.hash_tree_root());
-        result.append(&mut self.latest_randao_mixes.hash_tree_root());
-        result.append(&mut self.latest_vdf_outputs.hash_tree_root());
-        result.append(&mut self.previous_epoch_start_shard.hash_tree_root());
-        result.append(&mut self.current_epoch_start_shard.hash_tree_root());
-        result.append(&mut self.previous_epoch_calculation_slot.hash_tree_root());
-        result.append(&mut self.current_epoch_calculation_slot.hash_tree_root());
-        result.append(&mut self.previous_epoch_randao_mix.hash_tree_root());
-        result.append(&mut self.current_epoch_randao_mix.hash_tree_root());
-        result.append(&mut self.custody_challenges.hash_tree_root());
-        result.append(&mut self.previous_justified_slot.hash_tree_root());
-        result.append(&mut self.justified_slot.hash_tree_root());
-        result.append(&mut self.justification_bitfield.hash_tree_root());
-        result.append(&mut self.finalized_slot.hash_tree_root());
-        result.append(&mut self.latest_crosslinks.hash_tree_root());
-        result.append(&mut self.latest_block_roots.hash_tree_root());
-        result.append(&mut self.latest_penalized_exit_balances.hash_tree_root());
-        result.append(&mut self.latest_attestations.hash_tree_root());
-        result.append(&mut self.batched_block_roots.hash_tree_root());
-        result.append(&mut self.latest_eth1_data.hash_tree_root());
-        result.append(&mut self.eth1_data_votes.hash_tree_root());
-        hash(&result)
+    /// Returns the same root as `canonical_root`, using and updating the intermediate roots held
+    /// in `cache`.
+    pub fn cached_canonical_root(&self, cache: &mut BeaconStateTreeHashCache) -> Hash256 {
+        Hash256::from(&self.cached_hash_tree_root(cache)[..])
     }
 }
 
@@ -212,7 +104,7 @@ impl<T: RngCore> TestRandom<T> for BeaconState {
             validator_registry_update_slot: <_>::random_for_test(rng),
             validator_registry_exit_count: <_>::random_for_test(rng),
             validator_registry_delta_chain_tip: <_>::random_for_test(rng),
-            latest_randao_mixes: <_>::random_for_test(rng),
+            latest_randao_mixes: random_fixed_vec(rng, LATEST_RANDAO_MIXES_LENGTH),
             latest_vdf_outputs: <_>::random_for_test(rng),
             previous_epoch_start_shard: <_>::random_for_test(rng),
             current_epoch_start_shard: <_>::random_for_test(rng),
@@ -225,9 +117,9 @@ impl<T: RngCore> TestRandom<T> for BeaconState {
             justified_slot: <_>::random_for_test(rng),
             justification_bitfield: <_>::random_for_test(rng),
             finalized_slot: <_>::random_for_test(rng),
-            latest_crosslinks: <_>::random_for_test(rng),
-            latest_block_roots: <_>::random_for_test(rng),
-            latest_penalized_exit_balances: <_>::random_for_test(rng),
+            latest_crosslinks: random_fixed_vec(rng, SHARD_COUNT),
+            latest_block_roots: random_fixed_vec(rng, LATEST_BLOCK_ROOTS_LENGTH),
+            latest_penalized_exit_balances: random_fixed_vec(rng, LATEST_PENALIZED_EXIT_LENGTH),
             latest_attestations: <_>::random_for_test(rng),
             batched_block_roots: <_>::random_for_test(rng),
             latest_eth1_data: <_>::random_for_test(rng),
@@ -238,7 +130,7 @@ impl<T: RngCore> TestRandom<T> for BeaconState {
 
 #[cfg(test)]
 mod tests {
-    use super::super::ssz::ssz_encode;
+    use super::super::ssz::{ssz_encode, Decodable, TreeHash};
     use super::*;
     use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
 
@@ -253,6 +145,51 @@ mod tests {
         assert_eq!(original, decoded);
     }
 
+    #[test]
+    #[cfg(feature = "serialize")]
+    pub fn test_json_round_trip() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let original = BeaconState::random_for_test(&mut rng);
+
+        let json = serde_json::to_value(&original).unwrap();
+        assert_eq!(
+            json["fork"]["pre_fork_version"],
+            original.fork_data.pre_fork_version
+        );
+        assert_eq!(
+            json["latest_eth1_data"]["deposit_root"],
+            format!("{:?}", original.latest_eth1_data.deposit_root)
+        );
+
+        let decoded: BeaconState = serde_json::from_value(json).unwrap();
+        assert_eq!(original, decoded);
+    }
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    pub fn test_yaml_round_trip() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let original = BeaconState::random_for_test(&mut rng);
+
+        let yaml = serde_yaml::to_string(&original).unwrap();
+        let decoded: BeaconState = serde_yaml::from_str(&yaml).unwrap();
+
+        assert_eq!(original, decoded);
+    }
+
+    #[test]
+    pub fn test_ssz_fixed_length_vectors() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let mut state = BeaconState::random_for_test(&mut rng);
+        let bytes = ssz_encode(&state);
+
+        // Removing an element of a vector does not change the encoding of its length.
+        state.latest_block_roots.pop();
+        assert_eq!(ssz_encode(&state).len(), bytes.len() - 32);
+        let decoded: Result<(BeaconState, usize), _> = <_>::ssz_decode(&ssz_encode(&state), 0);
+        assert!(decoded.is_err());
+    }
+
     #[test]
     pub fn test_hash_tree_root() {
         let mut rng = XorShiftRng::from_seed([42; 16]);
@@ -264,4 +201,33 @@ mod tests {
         // TODO: Add further tests
         // https://github.com/sigp/lighthouse/issues/170
     }
+
+    #[test]
+    pub fn test_cached_canonical_root() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let mut state = BeaconState::random_for_test(&mut rng);
+        let mut cache = BeaconStateTreeHashCache::default();
+
+        assert_eq!(
+            state.cached_canonical_root(&mut cache),
+            state.canonical_root()
+        );
+
+        state.slot += 1;
+        state.validator_balances.push(42);
+        state.validator_registry[0] = <_>::random_for_test(&mut rng);
+        state.latest_block_roots[1] = <_>::random_for_test(&mut rng);
+        assert_eq!(
+            state.cached_canonical_root(&mut cache),
+            state.canonical_root()
+        );
+
+        state.validator_registry.pop();
+        state.latest_attestations.clear();
+        state.latest_randao_mixes[0] = Hash256::zero();
+        assert_eq!(
+            state.cached_canonical_root(&mut cache),
+            state.canonical_root()
+        );
+    }
 }
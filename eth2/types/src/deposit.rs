@@ -1,50 +1,20 @@
-use super::ssz::{hash, Decodable, DecodeError, Encodable, SszStream, TreeHash};
 use super::{DepositData, Hash256};
+use crate::ssz_limits::DEPOSIT_CONTRACT_TREE_DEPTH;
 use crate::test_utils::TestRandom;
 use rand::RngCore;
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode, TreeHash};
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Encode, Decode, TreeHash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Deposit {
+    #[ssz(max_length = "DEPOSIT_CONTRACT_TREE_DEPTH")]
     pub merkle_branch: Vec<Hash256>,
     pub merkle_tree_index: u64,
     pub deposit_data: DepositData,
 }
 
-impl Encodable for Deposit {
-    fn ssz_append(&self, s: &mut SszStream) {
-        s.append_vec(&self.merkle_branch);
-        s.append(&self.merkle_tree_index);
-        s.append(&self.deposit_data);
-    }
-}
-
-impl Decodable for Deposit {
-    fn ssz_decode(bytes: &[u8], i: usize) -> Result<(Self, usize), DecodeError> {
-        let (merkle_branch, i) = <_>::ssz_decode(bytes, i)?;
-        let (merkle_tree_index, i) = <_>::ssz_decode(bytes, i)?;
-        let (deposit_data, i) = <_>::ssz_decode(bytes, i)?;
-
-        Ok((
-            Self {
-                merkle_branch,
-                merkle_tree_index,
-                deposit_data,
-            },
-            i,
-        ))
-    }
-}
-
-impl TreeHash for Deposit {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        let mut result: Vec<u8> = vec![];
-        result.append(&mut self.merkle_branch.hash_tree_root());
-        result.append(&mut self.merkle_tree_index.hash_tree_root());
-        result.append(&mut self.deposit_data.hash_tree_root());
-        hash(&result)
-    }
-}
-
 impl<T: RngCore> TestRandom<T> for Deposit {
     fn random_for_test(rng: &mut T) -> Self {
         Self {
@@ -57,7 +27,7 @@ impl<T: RngCore> TestRandom<T> for Deposit {
 
 #[cfg(test)]
 mod tests {
-    use super::super::ssz::ssz_encode;
+    use super::super::ssz::{ssz_encode, Decodable, TreeHash};
     use super::*;
     use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
 
@@ -1,47 +1,28 @@
-use super::ssz::{hash, Decodable, DecodeError, Encodable, SszStream, TreeHash};
+use super::ssz::TreeHash;
+use super::Hash256;
 use crate::test_utils::TestRandom;
 use bls::Signature;
 use rand::RngCore;
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode, TreeHash};
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Encode, Decode, TreeHash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Exit {
     pub slot: u64,
     pub validator_index: u32,
     pub signature: Signature,
 }
 
-impl Encodable for Exit {
-    fn ssz_append(&self, s: &mut SszStream) {
-        s.append(&self.slot);
-        s.append(&self.validator_index);
-        s.append(&self.signature);
-    }
-}
-
-impl Decodable for Exit {
-    fn ssz_decode(bytes: &[u8], i: usize) -> Result<(Self, usize), DecodeError> {
-        let (slot, i) = <_>::ssz_decode(bytes, i)?;
-        let (validator_index, i) = <_>::ssz_decode(bytes, i)?;
-        let (signature, i) = <_>::ssz_decode(bytes, i)?;
-
-        Ok((
-            Self {
-                slot,
-                validator_index,
-                signature,
-            },
-            i,
-        ))
-    }
-}
-
-impl TreeHash for Exit {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        let mut result: Vec<u8> = vec![];
-        result.append(&mut self.slot.hash_tree_root());
-        result.append(&mut self.validator_index.hash_tree_root());
-        result.append(&mut self.signature.hash_tree_root());
-        hash(&result)
+impl Exit {
+    /// Returns the root signed by the exiting validator.
+    ///
+    /// The `signature` of the exit is not included in the root.
+    pub fn signing_root(&self) -> Hash256 {
+        let mut exit = self.clone();
+        exit.signature = Signature::empty_signature();
+        Hash256::from(&exit.hash_tree_root()[..])
     }
 }
 
@@ -57,7 +38,7 @@ impl<T: RngCore> TestRandom<T> for Exit {
 
 #[cfg(test)]
 mod tests {
-    use super::super::ssz::ssz_encode;
+    use super::super::ssz::{ssz_encode, Decodable};
     use super::*;
     use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
 
@@ -83,4 +64,15 @@ mod tests {
         // TODO: Add further tests
         // https://github.com/sigp/lighthouse/issues/170
     }
+
+    #[test]
+    pub fn test_signing_root_ignores_signature() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let original = Exit::random_for_test(&mut rng);
+
+        let mut resigned = original.clone();
+        resigned.signature = Signature::random_for_test(&mut rng);
+
+        assert_eq!(original.signing_root(), resigned.signing_root());
+    }
 }
@@ -1,46 +1,31 @@
-use super::ssz::{hash, Decodable, DecodeError, Encodable, SszStream, TreeHash};
 use crate::test_utils::TestRandom;
 use rand::RngCore;
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode, TreeHash};
 
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, Encode, Decode, TreeHash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Fork {
     pub pre_fork_version: u64,
     pub post_fork_version: u64,
     pub fork_slot: u64,
 }
 
-impl Encodable for Fork {
-    fn ssz_append(&self, s: &mut SszStream) {
-        s.append(&self.pre_fork_version);
-        s.append(&self.post_fork_version);
-        s.append(&self.fork_slot);
-    }
-}
-
-impl Decodable for Fork {
-    fn ssz_decode(bytes: &[u8], i: usize) -> Result<(Self, usize), DecodeError> {
-        let (pre_fork_version, i) = <_>::ssz_decode(bytes, i)?;
-        let (post_fork_version, i) = <_>::ssz_decode(bytes, i)?;
-        let (fork_slot, i) = <_>::ssz_decode(bytes, i)?;
-
-        Ok((
-            Self {
-                pre_fork_version,
-                post_fork_version,
-                fork_slot,
-            },
-            i,
-        ))
+impl Fork {
+    /// Returns the fork version which applies at the given `slot`.
+    pub fn version_at_slot(&self, slot: u64) -> u64 {
+        if slot < self.fork_slot {
+            self.pre_fork_version
+        } else {
+            self.post_fork_version
+        }
     }
-}
 
-impl TreeHash for Fork {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        let mut result: Vec<u8> = vec![];
-        result.append(&mut self.pre_fork_version.hash_tree_root());
-        result.append(&mut self.post_fork_version.hash_tree_root());
-        result.append(&mut self.fork_slot.hash_tree_root());
-        hash(&result)
+    /// Returns the signature domain for the given `domain_type` (e.g., `spec.domain_proposal`)
+    /// at the given `slot`.
+    pub fn get_domain(&self, slot: u64, domain_type: u64) -> u64 {
+        (self.version_at_slot(slot) << 32) + domain_type
     }
 }
 
@@ -56,7 +41,7 @@ impl<T: RngCore> TestRandom<T> for Fork {
 
 #[cfg(test)]
 mod tests {
-    use super::super::ssz::ssz_encode;
+    use super::super::ssz::{ssz_encode, Decodable, TreeHash};
     use super::*;
     use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
 
@@ -71,6 +56,31 @@ mod tests {
         assert_eq!(original, decoded);
     }
 
+    #[test]
+    pub fn test_version_at_slot() {
+        let fork = Fork {
+            pre_fork_version: 1,
+            post_fork_version: 2,
+            fork_slot: 10,
+        };
+
+        assert_eq!(fork.version_at_slot(9), 1);
+        assert_eq!(fork.version_at_slot(10), 2);
+        assert_eq!(fork.version_at_slot(11), 2);
+    }
+
+    #[test]
+    pub fn test_get_domain() {
+        let fork = Fork {
+            pre_fork_version: 1,
+            post_fork_version: 2,
+            fork_slot: 10,
+        };
+
+        assert_eq!(fork.get_domain(9, 3), (1 << 32) + 3);
+        assert_eq!(fork.get_domain(10, 3), (2 << 32) + 3);
+    }
+
     #[test]
     pub fn test_hash_tree_root() {
         let mut rng = XorShiftRng::from_seed([42; 16]);
@@ -7,6 +7,7 @@ pub mod test_utils;
 
 pub mod attestation;
 pub mod attestation_data;
+pub mod attestation_data_and_custody_bit;
 pub mod beacon_block;
 pub mod beacon_block_body;
 pub mod beacon_state;
@@ -19,6 +20,7 @@ pub mod eth1_data;
 pub mod eth1_data_vote;
 pub mod exit;
 pub mod fork;
+pub mod free_attestation;
 pub mod pending_attestation;
 pub mod proposal_signed_data;
 pub mod proposer_slashing;
@@ -26,6 +28,7 @@ pub mod shard_committee;
 pub mod shard_reassignment_record;
 pub mod slashable_vote_data;
 pub mod special_record;
+pub mod ssz_limits;
 pub mod validator;
 pub mod validator_registry;
 pub mod validator_registry_delta_block;
@@ -37,9 +40,10 @@ use std::collections::HashMap;
 
 pub use crate::attestation::Attestation;
 pub use crate::attestation_data::AttestationData;
+pub use crate::attestation_data_and_custody_bit::AttestationDataAndCustodyBit;
 pub use crate::beacon_block::BeaconBlock;
 pub use crate::beacon_block_body::BeaconBlockBody;
-pub use crate::beacon_state::BeaconState;
+pub use crate::beacon_state::{BeaconState, BeaconStateTreeHashCache};
 pub use crate::casper_slashing::CasperSlashing;
 pub use crate::crosslink::Crosslink;
 pub use crate::deposit::Deposit;
@@ -49,6 +53,7 @@ pub use crate::eth1_data::Eth1Data;
 pub use crate::eth1_data_vote::Eth1DataVote;
 pub use crate::exit::Exit;
 pub use crate::fork::Fork;
+pub use crate::free_attestation::FreeAttestation;
 pub use crate::pending_attestation::PendingAttestation;
 pub use crate::proposal_signed_data::ProposalSignedData;
 pub use crate::proposer_slashing::ProposerSlashing;
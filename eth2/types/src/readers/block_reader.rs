@@ -1,19 +1,20 @@
 use crate::{BeaconBlock, Hash256};
+use bls::Signature;
 use std::fmt::Debug;
 
 /// The `BeaconBlockReader` provides interfaces for reading a subset of fields of a `BeaconBlock`.
 ///
 /// The purpose of this trait is to allow reading from either;
 ///  - a standard `BeaconBlock` struct, or
-///  - a SSZ serialized byte array.
-///
-/// Note: presently, direct SSZ reading has not been implemented so this trait is being used for
-/// "future proofing".
+///  - a SSZ serialized byte array (see `SszBeaconBlock`).
 pub trait BeaconBlockReader: Debug + PartialEq {
     fn slot(&self) -> u64;
     fn parent_root(&self) -> Hash256;
     fn state_root(&self) -> Hash256;
+    fn randao_reveal(&self) -> Signature;
+    fn signature(&self) -> Signature;
     fn canonical_root(&self) -> Hash256;
+    fn proposal_root(&self, beacon_chain_shard_number: u64) -> Hash256;
     fn into_beacon_block(self) -> Option<BeaconBlock>;
 }
 
@@ -30,10 +31,22 @@ impl BeaconBlockReader for BeaconBlock {
         self.state_root
     }
 
+    fn randao_reveal(&self) -> Signature {
+        self.randao_reveal.clone()
+    }
+
+    fn signature(&self) -> Signature {
+        self.signature.clone()
+    }
+
     fn canonical_root(&self) -> Hash256 {
         self.canonical_root()
     }
 
+    fn proposal_root(&self, beacon_chain_shard_number: u64) -> Hash256 {
+        self.proposal_root(beacon_chain_shard_number)
+    }
+
     fn into_beacon_block(self) -> Option<BeaconBlock> {
         Some(self)
     }
@@ -1,5 +1,9 @@
 mod block_reader;
+mod ssz_block_reader;
+mod ssz_state_reader;
 mod state_reader;
 
 pub use self::block_reader::BeaconBlockReader;
+pub use self::ssz_block_reader::{SszBeaconBlock, SszBeaconBlockBuf};
+pub use self::ssz_state_reader::{SszBeaconState, SszBeaconStateBuf};
 pub use self::state_reader::BeaconStateReader;
@@ -0,0 +1,241 @@
+use super::BeaconBlockReader;
+use crate::{BeaconBlock, Hash256};
+use bls::Signature;
+use ssz::decode::decode_length;
+use ssz::{Decodable, DecodeError, LENGTH_BYTES};
+
+const SLOT_START: usize = 0;
+const SLOT_BYTES: usize = 8;
+const HASH_BYTES: usize = 32;
+const PARENT_ROOT_START: usize = SLOT_START + SLOT_BYTES;
+const STATE_ROOT_START: usize = PARENT_ROOT_START + HASH_BYTES;
+const RANDAO_REVEAL_START: usize = STATE_ROOT_START + HASH_BYTES;
+const ETH1_DATA_BYTES: usize = 2 * HASH_BYTES;
+const MIN_SSZ_BLOCK_LENGTH: usize = RANDAO_REVEAL_START + LENGTH_BYTES;
+
+/// A view over the SSZ serialization of a `BeaconBlock`, reading fields directly from the bytes
+/// instead of deserializing the whole block.
+///
+/// `from_slice` only checks that the fixed-length fields and the length-prefixed signatures fit
+/// within the bytes. The `slot`, `parent_root` and `state_root` are then read without
+/// allocating, whilst the remaining fields are decoded when they are requested.
+///
+/// As such, the `randao_reveal`, `signature`, `canonical_root` and `proposal_root` methods panic
+/// if the relevant bytes do not decode. Use `into_beacon_block` to decode the whole block
+/// fallibly.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SszBeaconBlock<'a> {
+    ssz: &'a [u8],
+    eth1_data_start: usize,
+    signature_start: usize,
+    body_start: usize,
+}
+
+impl<'a> SszBeaconBlock<'a> {
+    /// Creates a view over the `BeaconBlock` serialized at the start of `ssz`.
+    ///
+    /// Bytes following the `BeaconBlock` are ignored.
+    pub fn from_slice(ssz: &'a [u8]) -> Result<Self, DecodeError> {
+        if ssz.len() < MIN_SSZ_BLOCK_LENGTH {
+            return Err(DecodeError::TooShort);
+        }
+
+        let eth1_data_start = list_end(ssz, RANDAO_REVEAL_START)?;
+        let signature_start = eth1_data_start + ETH1_DATA_BYTES;
+        let body_start = list_end(ssz, signature_start)?;
+
+        Ok(Self {
+            ssz,
+            eth1_data_start,
+            signature_start,
+            body_start,
+        })
+    }
+
+    /// Returns the serialized `randao_reveal`, including its length prefix.
+    pub fn randao_reveal_ssz(&self) -> &'a [u8] {
+        &self.ssz[RANDAO_REVEAL_START..self.eth1_data_start]
+    }
+
+    /// Returns the serialized `signature`, including its length prefix.
+    pub fn signature_ssz(&self) -> &'a [u8] {
+        &self.ssz[self.signature_start..self.body_start]
+    }
+
+    /// Returns the serialized `body`, along with any bytes following the block.
+    pub fn body_ssz(&self) -> &'a [u8] {
+        &self.ssz[self.body_start..]
+    }
+
+    fn decode_signature(&self, start: usize) -> Signature {
+        let (signature, _) =
+            Signature::ssz_decode(self.ssz, start).expect("Bad signature in SszBeaconBlock.");
+        signature
+    }
+
+    fn decode_block(&self) -> BeaconBlock {
+        self.into_beacon_block()
+            .expect("Bad BeaconBlock in SszBeaconBlock.")
+    }
+}
+
+/// An `SszBeaconBlock` which owns its bytes, such that it may be returned from a database read.
+///
+/// The bytes are checked by `SszBeaconBlock::from_slice` on creation, and each method reads them
+/// through a new `SszBeaconBlock`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SszBeaconBlockBuf {
+    ssz: Vec<u8>,
+}
+
+impl SszBeaconBlockBuf {
+    /// Takes ownership of the `BeaconBlock` serialized at the start of `ssz`.
+    pub fn from_vec(ssz: Vec<u8>) -> Result<Self, DecodeError> {
+        SszBeaconBlock::from_slice(&ssz)?;
+        Ok(Self { ssz })
+    }
+
+    /// Returns a view over the bytes of the block.
+    pub fn as_view(&self) -> SszBeaconBlock {
+        SszBeaconBlock::from_slice(&self.ssz).expect("Checked on creation.")
+    }
+}
+
+/// Returns the index following the length-prefixed list which starts at `index`.
+fn list_end(ssz: &[u8], index: usize) -> Result<usize, DecodeError> {
+    let end = index + LENGTH_BYTES + decode_length(ssz, index, LENGTH_BYTES)?;
+    if end > ssz.len() {
+        Err(DecodeError::TooShort)
+    } else {
+        Ok(end)
+    }
+}
+
+impl<'a> BeaconBlockReader for SszBeaconBlock<'a> {
+    fn slot(&self) -> u64 {
+        let (slot, _) = u64::ssz_decode(self.ssz, SLOT_START).expect("Length checked on creation.");
+        slot
+    }
+
+    fn parent_root(&self) -> Hash256 {
+        Hash256::from(&self.ssz[PARENT_ROOT_START..STATE_ROOT_START])
+    }
+
+    fn state_root(&self) -> Hash256 {
+        Hash256::from(&self.ssz[STATE_ROOT_START..RANDAO_REVEAL_START])
+    }
+
+    fn randao_reveal(&self) -> Signature {
+        self.decode_signature(RANDAO_REVEAL_START)
+    }
+
+    fn signature(&self) -> Signature {
+        self.decode_signature(self.signature_start)
+    }
+
+    fn canonical_root(&self) -> Hash256 {
+        self.decode_block().canonical_root()
+    }
+
+    fn proposal_root(&self, beacon_chain_shard_number: u64) -> Hash256 {
+        self.decode_block().proposal_root(beacon_chain_shard_number)
+    }
+
+    fn into_beacon_block(self) -> Option<BeaconBlock> {
+        BeaconBlock::ssz_decode(self.ssz, 0)
+            .ok()
+            .map(|(block, _)| block)
+    }
+}
+
+impl BeaconBlockReader for SszBeaconBlockBuf {
+    fn slot(&self) -> u64 {
+        self.as_view().slot()
+    }
+
+    fn parent_root(&self) -> Hash256 {
+        self.as_view().parent_root()
+    }
+
+    fn state_root(&self) -> Hash256 {
+        self.as_view().state_root()
+    }
+
+    fn randao_reveal(&self) -> Signature {
+        self.as_view().randao_reveal()
+    }
+
+    fn signature(&self) -> Signature {
+        self.as_view().signature()
+    }
+
+    fn canonical_root(&self) -> Hash256 {
+        self.as_view().canonical_root()
+    }
+
+    fn proposal_root(&self, beacon_chain_shard_number: u64) -> Hash256 {
+        self.as_view().proposal_root(beacon_chain_shard_number)
+    }
+
+    fn into_beacon_block(self) -> Option<BeaconBlock> {
+        self.as_view().into_beacon_block()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use ssz::ssz_encode;
+
+    #[test]
+    fn test_read_ssz_block() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let block = BeaconBlock::random_for_test(&mut rng);
+        let ssz = ssz_encode(&block);
+
+        let reader = SszBeaconBlock::from_slice(&ssz).unwrap();
+        assert_eq!(reader.slot(), block.slot);
+        assert_eq!(reader.parent_root(), block.parent_root);
+        assert_eq!(reader.state_root(), block.state_root);
+        assert_eq!(reader.randao_reveal(), block.randao_reveal);
+        assert_eq!(reader.signature(), block.signature);
+        assert_eq!(reader.canonical_root(), block.canonical_root());
+        assert_eq!(reader.proposal_root(3), block.proposal_root(3));
+        assert_eq!(reader.body_ssz(), &ssz_encode(&block.body)[..]);
+        assert_eq!(reader.into_beacon_block(), Some(block.clone()));
+
+        let buf = SszBeaconBlockBuf::from_vec(ssz.clone()).unwrap();
+        assert_eq!(buf.as_view(), reader);
+        assert_eq!(buf.slot(), block.slot);
+        assert_eq!(buf.parent_root(), block.parent_root);
+        assert_eq!(buf.into_beacon_block(), Some(block));
+        assert_eq!(
+            SszBeaconBlockBuf::from_vec(ssz[..MIN_SSZ_BLOCK_LENGTH - 1].to_vec()),
+            Err(DecodeError::TooShort)
+        );
+    }
+
+    #[test]
+    fn test_read_truncated_ssz_block() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let block = BeaconBlock::random_for_test(&mut rng);
+        let ssz = ssz_encode(&block);
+
+        let signature_end = ssz.len() - ssz_encode(&block.body).len();
+        assert!(SszBeaconBlock::from_slice(&ssz[..signature_end]).is_ok());
+        assert_eq!(
+            SszBeaconBlock::from_slice(&ssz[..signature_end - 1]),
+            Err(DecodeError::TooShort)
+        );
+        assert_eq!(
+            SszBeaconBlock::from_slice(&ssz[..MIN_SSZ_BLOCK_LENGTH - 1]),
+            Err(DecodeError::TooShort)
+        );
+
+        // The body is not checked until the block is decoded.
+        let reader = SszBeaconBlock::from_slice(&ssz[..ssz.len() - 1]).unwrap();
+        assert_eq!(reader.slot(), block.slot);
+        assert_eq!(reader.into_beacon_block(), None);
+    }
+}
@@ -0,0 +1,112 @@
+use super::BeaconStateReader;
+use crate::{BeaconState, Hash256};
+use ssz::{Decodable, DecodeError};
+
+const SLOT_START: usize = 0;
+const SLOT_BYTES: usize = 8;
+
+/// A view over the SSZ serialization of a `BeaconState`, reading fields directly from the bytes
+/// instead of deserializing the whole state.
+///
+/// `from_slice` only checks that the `slot` fits within the bytes, so `canonical_root` panics if
+/// the state does not decode. Use `into_beacon_state` to decode the whole state fallibly.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SszBeaconState<'a> {
+    ssz: &'a [u8],
+}
+
+impl<'a> SszBeaconState<'a> {
+    /// Creates a view over the `BeaconState` serialized at the start of `ssz`.
+    pub fn from_slice(ssz: &'a [u8]) -> Result<Self, DecodeError> {
+        if ssz.len() < SLOT_START + SLOT_BYTES {
+            return Err(DecodeError::TooShort);
+        }
+
+        Ok(Self { ssz })
+    }
+}
+
+/// An `SszBeaconState` which owns its bytes, such that it may be returned from a database read.
+///
+/// The bytes are checked by `SszBeaconState::from_slice` on creation, and each method reads them
+/// through a new `SszBeaconState`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SszBeaconStateBuf {
+    ssz: Vec<u8>,
+}
+
+impl SszBeaconStateBuf {
+    /// Takes ownership of the `BeaconState` serialized at the start of `ssz`.
+    pub fn from_vec(ssz: Vec<u8>) -> Result<Self, DecodeError> {
+        SszBeaconState::from_slice(&ssz)?;
+        Ok(Self { ssz })
+    }
+
+    /// Returns a view over the bytes of the state.
+    pub fn as_view(&self) -> SszBeaconState {
+        SszBeaconState::from_slice(&self.ssz).expect("Checked on creation.")
+    }
+}
+
+impl<'a> BeaconStateReader for SszBeaconState<'a> {
+    fn slot(&self) -> u64 {
+        let (slot, _) = u64::ssz_decode(self.ssz, SLOT_START).expect("Length checked on creation.");
+        slot
+    }
+
+    fn canonical_root(&self) -> Hash256 {
+        self.into_beacon_state()
+            .expect("Bad BeaconState in SszBeaconState.")
+            .canonical_root()
+    }
+
+    fn into_beacon_state(self) -> Option<BeaconState> {
+        BeaconState::ssz_decode(self.ssz, 0)
+            .ok()
+            .map(|(state, _)| state)
+    }
+}
+
+impl BeaconStateReader for SszBeaconStateBuf {
+    fn slot(&self) -> u64 {
+        self.as_view().slot()
+    }
+
+    fn canonical_root(&self) -> Hash256 {
+        self.as_view().canonical_root()
+    }
+
+    fn into_beacon_state(self) -> Option<BeaconState> {
+        self.as_view().into_beacon_state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use ssz::ssz_encode;
+
+    #[test]
+    fn test_read_ssz_state() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let state = BeaconState::random_for_test(&mut rng);
+        let ssz = ssz_encode(&state);
+
+        let reader = SszBeaconState::from_slice(&ssz).unwrap();
+        assert_eq!(reader.slot(), state.slot);
+        assert_eq!(reader.canonical_root(), state.canonical_root());
+        assert_eq!(reader.into_beacon_state(), Some(state.clone()));
+
+        let buf = SszBeaconStateBuf::from_vec(ssz.clone()).unwrap();
+        assert_eq!(buf.slot(), state.slot);
+        assert_eq!(buf.into_beacon_state(), Some(state));
+
+        assert_eq!(
+            SszBeaconState::from_slice(&ssz[..SLOT_BYTES - 1]),
+            Err(DecodeError::TooShort)
+        );
+        let reader = SszBeaconState::from_slice(&ssz[..SLOT_BYTES]).unwrap();
+        assert_eq!(reader.into_beacon_state(), None);
+    }
+}
@@ -5,10 +5,7 @@ use std::fmt::Debug;
 ///
 /// The purpose of this trait is to allow reading from either;
 ///  - a standard `BeaconState` struct, or
-///  - a SSZ serialized byte array.
-///
-/// Note: presently, direct SSZ reading has not been implemented so this trait is being used for
-/// "future proofing".
+///  - a SSZ serialized byte array (see `SszBeaconState`).
 pub trait BeaconStateReader: Debug + PartialEq {
     fn slot(&self) -> u64;
     fn canonical_root(&self) -> Hash256;
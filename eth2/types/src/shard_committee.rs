@@ -1,38 +1,16 @@
-use super::ssz::{hash, Decodable, DecodeError, Encodable, SszStream, TreeHash};
 use crate::test_utils::TestRandom;
 use rand::RngCore;
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode, TreeHash};
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Encode, Decode, TreeHash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct ShardCommittee {
     pub shard: u64,
     pub committee: Vec<usize>,
 }
 
-impl Encodable for ShardCommittee {
-    fn ssz_append(&self, s: &mut SszStream) {
-        s.append(&self.shard);
-        s.append(&self.committee);
-    }
-}
-
-impl Decodable for ShardCommittee {
-    fn ssz_decode(bytes: &[u8], i: usize) -> Result<(Self, usize), DecodeError> {
-        let (shard, i) = <_>::ssz_decode(bytes, i)?;
-        let (committee, i) = <_>::ssz_decode(bytes, i)?;
-
-        Ok((Self { shard, committee }, i))
-    }
-}
-
-impl TreeHash for ShardCommittee {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        let mut result: Vec<u8> = vec![];
-        result.append(&mut self.shard.hash_tree_root());
-        result.append(&mut self.committee.hash_tree_root());
-        hash(&result)
-    }
-}
-
 impl<T: RngCore> TestRandom<T> for ShardCommittee {
     fn random_for_test(rng: &mut T) -> Self {
         Self {
@@ -44,7 +22,7 @@ impl<T: RngCore> TestRandom<T> for ShardCommittee {
 
 #[cfg(test)]
 mod tests {
-    use super::super::ssz::ssz_encode;
+    use super::super::ssz::{ssz_encode, Decodable, TreeHash};
     use super::*;
     use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
 
@@ -1,56 +1,23 @@
-use super::ssz::{hash, Decodable, DecodeError, Encodable, SszStream, TreeHash};
 use super::AttestationData;
+use crate::ssz_limits::MAX_CASPER_VOTES;
 use crate::test_utils::TestRandom;
 use bls::AggregateSignature;
 use rand::RngCore;
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode, TreeHash};
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Encode, Decode, TreeHash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct SlashableVoteData {
+    #[ssz(max_length = "MAX_CASPER_VOTES")]
     pub custody_bit_0_indices: Vec<u32>,
+    #[ssz(max_length = "MAX_CASPER_VOTES")]
     pub custody_bit_1_indices: Vec<u32>,
     pub data: AttestationData,
     pub aggregate_signature: AggregateSignature,
 }
 
-impl Encodable for SlashableVoteData {
-    fn ssz_append(&self, s: &mut SszStream) {
-        s.append_vec(&self.custody_bit_0_indices);
-        s.append_vec(&self.custody_bit_1_indices);
-        s.append(&self.data);
-        s.append(&self.aggregate_signature);
-    }
-}
-
-impl Decodable for SlashableVoteData {
-    fn ssz_decode(bytes: &[u8], i: usize) -> Result<(Self, usize), DecodeError> {
-        let (custody_bit_0_indices, i) = <_>::ssz_decode(bytes, i)?;
-        let (custody_bit_1_indices, i) = <_>::ssz_decode(bytes, i)?;
-        let (data, i) = <_>::ssz_decode(bytes, i)?;
-        let (aggregate_signature, i) = <_>::ssz_decode(bytes, i)?;
-
-        Ok((
-            SlashableVoteData {
-                custody_bit_0_indices,
-                custody_bit_1_indices,
-                data,
-                aggregate_signature,
-            },
-            i,
-        ))
-    }
-}
-
-impl TreeHash for SlashableVoteData {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        let mut result: Vec<u8> = vec![];
-        result.append(&mut self.custody_bit_0_indices.hash_tree_root());
-        result.append(&mut self.custody_bit_1_indices.hash_tree_root());
-        result.append(&mut self.data.hash_tree_root());
-        result.append(&mut self.aggregate_signature.hash_tree_root());
-        hash(&result)
-    }
-}
-
 impl<T: RngCore> TestRandom<T> for SlashableVoteData {
     fn random_for_test(rng: &mut T) -> Self {
         Self {
@@ -64,7 +31,7 @@ impl<T: RngCore> TestRandom<T> for SlashableVoteData {
 
 #[cfg(test)]
 mod tests {
-    use super::super::ssz::ssz_encode;
+    use super::super::ssz::{ssz_encode, Decodable, TreeHash};
     use super::*;
     use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
 
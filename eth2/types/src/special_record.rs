@@ -1,9 +1,12 @@
-use ssz::{hash, Decodable, DecodeError, Encodable, SszStream, TreeHash};
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode, TreeHash};
 
 /// The value of the "type" field of SpecialRecord.
 ///
 /// Note: this value must serialize to a u8 and therefore must not be greater than 255.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum SpecialRecordKind {
     Logout = 0,
     CasperSlashing = 1,
@@ -11,7 +14,8 @@ pub enum SpecialRecordKind {
 }
 
 /// The structure used in the `BeaconBlock.specials` field.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Encode, Decode, TreeHash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct SpecialRecord {
     pub kind: u8,
     pub data: Vec<u8>,
@@ -56,33 +60,10 @@ impl SpecialRecord {
     }
 }
 
-impl Encodable for SpecialRecord {
-    fn ssz_append(&self, s: &mut SszStream) {
-        s.append(&self.kind);
-        s.append_vec(&self.data);
-    }
-}
-
-impl Decodable for SpecialRecord {
-    fn ssz_decode(bytes: &[u8], i: usize) -> Result<(Self, usize), DecodeError> {
-        let (kind, i) = u8::ssz_decode(bytes, i)?;
-        let (data, i) = Decodable::ssz_decode(bytes, i)?;
-        Ok((SpecialRecord { kind, data }, i))
-    }
-}
-
-impl TreeHash for SpecialRecord {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        let mut result: Vec<u8> = vec![];
-        result.append(&mut self.kind.hash_tree_root());
-        result.append(&mut self.data.as_slice().hash_tree_root());
-        hash(&result)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ssz::{Decodable, SszStream};
 
     #[test]
     pub fn test_special_record_ssz_encode() {
@@ -0,0 +1,26 @@
+//! The maximum number of elements permitted in each list when decoding SSZ, and the lengths of
+//! fixed-length vectors.
+//!
+//! Decoding is independent of any `ChainSpec`, so these are constants. They are the values of
+//! the foundation `ChainSpec`, which is built from them.
+
+/*
+ * Max operations per block
+ */
+pub const MAX_PROPOSER_SLASHINGS: usize = 16;
+pub const MAX_CASPER_SLASHINGS: usize = 16;
+pub const MAX_ATTESTATIONS: usize = 128;
+pub const MAX_DEPOSITS: usize = 16;
+pub const MAX_EXITS: usize = 16;
+/// The maximum number of validator indices in each list of a `SlashableVoteData`.
+pub const MAX_CASPER_VOTES: usize = 1_024;
+/// The maximum length of the merkle branch of a `Deposit`.
+pub const DEPOSIT_CONTRACT_TREE_DEPTH: usize = 32;
+
+/*
+ * Fixed-length vectors in the `BeaconState`
+ */
+pub const SHARD_COUNT: usize = 1_024;
+pub const LATEST_BLOCK_ROOTS_LENGTH: usize = 8_192;
+pub const LATEST_RANDAO_MIXES_LENGTH: usize = 8_192;
+pub const LATEST_PENALIZED_EXIT_LENGTH: usize = 8_192;
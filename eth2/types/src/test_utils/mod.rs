@@ -17,6 +17,12 @@ where
     fn random_for_test(rng: &mut T) -> Self;
 }
 
+impl<T: RngCore> TestRandom<T> for bool {
+    fn random_for_test(rng: &mut T) -> Self {
+        (rng.next_u32() % 2) == 1
+    }
+}
+
 impl<T: RngCore> TestRandom<T> for u64 {
     fn random_for_test(rng: &mut T) -> Self {
         rng.next_u64()
@@ -35,6 +41,15 @@ impl<T: RngCore> TestRandom<T> for usize {
     }
 }
 
+/// Returns `length` random values, for a fixed-length vector.
+pub fn random_fixed_vec<T, U>(rng: &mut T, length: usize) -> Vec<U>
+where
+    T: RngCore,
+    U: TestRandom<T>,
+{
+    (0..length).map(|_| U::random_for_test(rng)).collect()
+}
+
 impl<T: RngCore, U> TestRandom<T> for Vec<U>
 where
     U: TestRandom<T>,
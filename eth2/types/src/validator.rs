@@ -2,48 +2,59 @@ use super::bls::PublicKey;
 use super::Hash256;
 use crate::test_utils::TestRandom;
 use rand::RngCore;
-use ssz::{hash, Decodable, DecodeError, Encodable, SszStream, TreeHash};
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+use ssz::{Decodable, DecodeError, Encodable, SszStream, TreeHash};
+use ssz_derive::{Decode, Encode, TreeHash};
 
 const STATUS_FLAG_INITIATED_EXIT: u8 = 1;
 const STATUS_FLAG_WITHDRAWABLE: u8 = 2;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
 pub enum StatusFlags {
     InitiatedExit,
     Withdrawable,
 }
 
-struct StatusFlagsDecodeError;
+impl StatusFlags {
+    fn as_byte(self) -> u8 {
+        match self {
+            StatusFlags::InitiatedExit => STATUS_FLAG_INITIATED_EXIT,
+            StatusFlags::Withdrawable => STATUS_FLAG_WITHDRAWABLE,
+        }
+    }
+}
 
-impl From<StatusFlagsDecodeError> for DecodeError {
-    fn from(_: StatusFlagsDecodeError) -> DecodeError {
-        DecodeError::Invalid
+impl Encodable for StatusFlags {
+    fn ssz_append(&self, s: &mut SszStream) {
+        s.append(&self.as_byte());
+    }
+
+    fn ssz_size_hint(&self) -> usize {
+        1
     }
 }
 
-/// Handles the serialization logic for the `status_flags` field of the `Validator`.
-fn status_flag_to_byte(flag: Option<StatusFlags>) -> u8 {
-    if let Some(flag) = flag {
-        match flag {
-            StatusFlags::InitiatedExit => STATUS_FLAG_INITIATED_EXIT,
-            StatusFlags::Withdrawable => STATUS_FLAG_WITHDRAWABLE,
+impl Decodable for StatusFlags {
+    fn ssz_decode(bytes: &[u8], i: usize) -> Result<(Self, usize), DecodeError> {
+        match u8::ssz_decode(bytes, i)? {
+            (STATUS_FLAG_INITIATED_EXIT, i) => Ok((StatusFlags::InitiatedExit, i)),
+            (STATUS_FLAG_WITHDRAWABLE, i) => Ok((StatusFlags::Withdrawable, i)),
+            _ => Err(DecodeError::Invalid),
         }
-    } else {
-        0
     }
 }
 
-/// Handles the deserialization logic for the `status_flags` field of the `Validator`.
-fn status_flag_from_byte(flag: u8) -> Result<Option<StatusFlags>, StatusFlagsDecodeError> {
-    match flag {
-        0 => Ok(None),
-        1 => Ok(Some(StatusFlags::InitiatedExit)),
-        2 => Ok(Some(StatusFlags::Withdrawable)),
-        _ => Err(StatusFlagsDecodeError),
+impl TreeHash for StatusFlags {
+    fn hash_tree_root(&self) -> Vec<u8> {
+        self.as_byte().hash_tree_root()
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Encode, Decode, TreeHash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Validator {
     pub pubkey: PublicKey,
     pub withdrawal_credentials: Hash256,
@@ -91,75 +102,6 @@ impl<T: RngCore> TestRandom<T> for StatusFlags {
     }
 }
 
-impl Encodable for Validator {
-    fn ssz_append(&self, s: &mut SszStream) {
-        s.append(&self.pubkey);
-        s.append(&self.withdrawal_credentials);
-        s.append(&self.proposer_slots);
-        s.append(&self.activation_slot);
-        s.append(&self.exit_slot);
-        s.append(&self.withdrawal_slot);
-        s.append(&self.penalized_slot);
-        s.append(&self.exit_count);
-        s.append(&status_flag_to_byte(self.status_flags));
-        s.append(&self.latest_custody_reseed_slot);
-        s.append(&self.penultimate_custody_reseed_slot);
-    }
-}
-
-impl Decodable for Validator {
-    fn ssz_decode(bytes: &[u8], i: usize) -> Result<(Self, usize), DecodeError> {
-        let (pubkey, i) = <_>::ssz_decode(bytes, i)?;
-        let (withdrawal_credentials, i) = <_>::ssz_decode(bytes, i)?;
-        let (proposer_slots, i) = <_>::ssz_decode(bytes, i)?;
-        let (activation_slot, i) = <_>::ssz_decode(bytes, i)?;
-        let (exit_slot, i) = <_>::ssz_decode(bytes, i)?;
-        let (withdrawal_slot, i) = <_>::ssz_decode(bytes, i)?;
-        let (penalized_slot, i) = <_>::ssz_decode(bytes, i)?;
-        let (exit_count, i) = <_>::ssz_decode(bytes, i)?;
-        let (status_flags_byte, i): (u8, usize) = <_>::ssz_decode(bytes, i)?;
-        let (latest_custody_reseed_slot, i) = <_>::ssz_decode(bytes, i)?;
-        let (penultimate_custody_reseed_slot, i) = <_>::ssz_decode(bytes, i)?;
-
-        let status_flags = status_flag_from_byte(status_flags_byte)?;
-
-        Ok((
-            Self {
-                pubkey,
-                withdrawal_credentials,
-                proposer_slots,
-                activation_slot,
-                exit_slot,
-                withdrawal_slot,
-                penalized_slot,
-                exit_count,
-                status_flags,
-                latest_custody_reseed_slot,
-                penultimate_custody_reseed_slot,
-            },
-            i,
-        ))
-    }
-}
-
-impl TreeHash for Validator {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        let mut result: Vec<u8> = vec![];
-        result.append(&mut self.pubkey.hash_tree_root());
-        result.append(&mut self.withdrawal_credentials.hash_tree_root());
-        result.append(&mut self.proposer_slots.hash_tree_root());
-        result.append(&mut self.activation_slot.hash_tree_root());
-        result.append(&mut self.exit_slot.hash_tree_root());
-        result.append(&mut self.withdrawal_slot.hash_tree_root());
-        result.append(&mut self.penalized_slot.hash_tree_root());
-        result.append(&mut self.exit_count.hash_tree_root());
-        result.append(&mut (status_flag_to_byte(self.status_flags) as u64).hash_tree_root());
-        result.append(&mut self.latest_custody_reseed_slot.hash_tree_root());
-        result.append(&mut self.penultimate_custody_reseed_slot.hash_tree_root());
-        hash(&result)
-    }
-}
-
 impl<T: RngCore> TestRandom<T> for Validator {
     fn random_for_test(rng: &mut T) -> Self {
         Self {
@@ -180,7 +122,7 @@ impl<T: RngCore> TestRandom<T> for Validator {
 
 #[cfg(test)]
 mod tests {
-    use super::super::ssz::ssz_encode;
+    use super::super::ssz::{ssz_encode, Decodable, TreeHash};
     use super::*;
     use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
 
@@ -195,6 +137,23 @@ mod tests {
         assert_eq!(original, decoded);
     }
 
+    #[test]
+    pub fn test_ssz_status_flags() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let mut original = Validator::random_for_test(&mut rng);
+        original.status_flags = None;
+
+        let bytes = ssz_encode(&original);
+        let (decoded, _) = <_>::ssz_decode(&bytes, 0).unwrap();
+        assert_eq!(original, decoded);
+
+        assert_eq!(
+            ssz_encode(&Some(StatusFlags::Withdrawable)),
+            vec![0, 0, 0, 1, 2]
+        );
+        assert_eq!(StatusFlags::ssz_decode(&[3], 0), Err(DecodeError::Invalid));
+    }
+
     #[test]
     fn test_validator_can_be_active() {
         let mut rng = XorShiftRng::from_seed([42; 16]);
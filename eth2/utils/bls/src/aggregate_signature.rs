@@ -1,6 +1,15 @@
-use super::ssz::{decode_ssz_list, hash, Decodable, DecodeError, Encodable, SszStream, TreeHash};
-use super::{AggregatePublicKey, Signature};
+use super::ssz::{
+    decode_ssz_list, merkleize, Decodable, DecodeError, Encodable, SszStream, TreeHash,
+    LENGTH_BYTES,
+};
+use super::{AggregatePublicKey, Signature, BLS_AGG_SIG_BYTE_SIZE};
+#[cfg(feature = "serialize")]
+use crate::serde_hex;
 use bls_aggregates::AggregateSignature as RawAggregateSignature;
+#[cfg(feature = "serialize")]
+use serde::de::{Deserialize, Deserializer, Error as DeserializeError};
+#[cfg(feature = "serialize")]
+use serde::ser::{Serialize, Serializer};
 
 /// A BLS aggregate signature.
 ///
@@ -31,7 +40,11 @@ impl AggregateSignature {
 
 impl Encodable for AggregateSignature {
     fn ssz_append(&self, s: &mut SszStream) {
-        s.append_vec(&self.0.as_bytes());
+        s.append_encoded_val(&self.0.as_bytes());
+    }
+
+    fn ssz_size_hint(&self) -> usize {
+        LENGTH_BYTES + BLS_AGG_SIG_BYTE_SIZE
     }
 }
 
@@ -44,9 +57,34 @@ impl Decodable for AggregateSignature {
     }
 }
 
+#[cfg(feature = "serialize")]
+impl Serialize for AggregateSignature {
+    /// Serializes the signature as a `0x`-prefixed hex string of its raw bytes.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&serde_hex::encode(&self.0.as_bytes()))
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<'de> Deserialize<'de> for AggregateSignature {
+    /// Deserializes the signature from a `0x`-prefixed hex string of its raw bytes.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = serde_hex::decode(deserializer)?;
+        let raw = RawAggregateSignature::from_bytes(&bytes)
+            .map_err(|_| D::Error::custom("invalid signature bytes"))?;
+        Ok(AggregateSignature(raw))
+    }
+}
+
 impl TreeHash for AggregateSignature {
     fn hash_tree_root(&self) -> Vec<u8> {
-        hash(&self.0.as_bytes())
+        merkleize(&self.0.as_bytes())
     }
 }
 
@@ -1,8 +1,14 @@
 use super::SecretKey;
+#[cfg(feature = "serialize")]
+use crate::serde_hex;
 use bls_aggregates::PublicKey as RawPublicKey;
-use hex::encode as hex_encode;
+use hex::{decode as hex_decode, encode as hex_encode};
+#[cfg(feature = "serialize")]
+use serde::de::{Deserialize, Deserializer, Error as DeserializeError};
+#[cfg(feature = "serialize")]
+use serde::ser::{Serialize, Serializer};
 use ssz::{
-    decode_ssz_list, hash, ssz_encode, Decodable, DecodeError, Encodable, SszStream, TreeHash,
+    decode_ssz_list, merkleize, ssz_encode, Decodable, DecodeError, Encodable, SszStream, TreeHash,
 };
 use std::default;
 use std::hash::{Hash, Hasher};
@@ -32,6 +38,19 @@ impl PublicKey {
         let end_bytes = &bytes[bytes.len().saturating_sub(6)..bytes.len()];
         hex_encode(end_bytes)
     }
+
+    /// Returns the raw bytes of the public key as a `0x`-prefixed hex string.
+    pub fn as_hex_string(&self) -> String {
+        format!("0x{}", hex_encode(self.0.as_bytes()))
+    }
+
+    /// Parses a public key from the format returned by `as_hex_string`.
+    ///
+    /// Returns `None` if the string is not a valid public key.
+    pub fn from_hex_string(string: &str) -> Option<Self> {
+        let bytes = hex_decode(string.trim_start_matches("0x")).ok()?;
+        RawPublicKey::from_bytes(&bytes).ok().map(PublicKey)
+    }
 }
 
 impl default::Default for PublicKey {
@@ -43,7 +62,7 @@ impl default::Default for PublicKey {
 
 impl Encodable for PublicKey {
     fn ssz_append(&self, s: &mut SszStream) {
-        s.append_vec(&self.0.as_bytes());
+        s.append_encoded_val(&self.0.as_bytes());
     }
 }
 
@@ -55,9 +74,34 @@ impl Decodable for PublicKey {
     }
 }
 
+#[cfg(feature = "serialize")]
+impl Serialize for PublicKey {
+    /// Serializes the public key as a `0x`-prefixed hex string of its raw bytes.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&serde_hex::encode(&self.0.as_bytes()))
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<'de> Deserialize<'de> for PublicKey {
+    /// Deserializes the public key from a `0x`-prefixed hex string of its raw bytes.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = serde_hex::decode(deserializer)?;
+        let raw = RawPublicKey::from_bytes(&bytes)
+            .map_err(|_| D::Error::custom("invalid public key bytes"))?;
+        Ok(PublicKey(raw))
+    }
+}
+
 impl TreeHash for PublicKey {
     fn hash_tree_root(&self) -> Vec<u8> {
-        hash(&self.0.as_bytes())
+        merkleize(&self.0.as_bytes())
     }
 }
 
@@ -88,4 +132,15 @@ mod tests {
 
         assert_eq!(original, decoded);
     }
+
+    #[test]
+    pub fn test_hex_string_round_trip() {
+        let sk = SecretKey::random();
+        let original = PublicKey::from_secret_key(&sk);
+
+        let decoded = PublicKey::from_hex_string(&original.as_hex_string());
+
+        assert_eq!(decoded, Some(original));
+        assert_eq!(PublicKey::from_hex_string("0xcafe"), None);
+    }
 }
@@ -1,5 +1,5 @@
 use bls_aggregates::{DecodeError as BlsDecodeError, SecretKey as RawSecretKey};
-use ssz::{decode_ssz_list, Decodable, DecodeError, Encodable, SszStream, TreeHash};
+use ssz::{decode_ssz_list, merkleize, Decodable, DecodeError, Encodable, SszStream, TreeHash};
 
 /// A single BLS signature.
 ///
@@ -28,7 +28,7 @@ impl SecretKey {
 
 impl Encodable for SecretKey {
     fn ssz_append(&self, s: &mut SszStream) {
-        s.append_vec(&self.0.as_bytes());
+        s.append_encoded_val(&self.0.as_bytes());
     }
 }
 
@@ -42,7 +42,7 @@ impl Decodable for SecretKey {
 
 impl TreeHash for SecretKey {
     fn hash_tree_root(&self) -> Vec<u8> {
-        self.0.as_bytes().clone()
+        merkleize(&self.0.as_bytes())
     }
 }
 
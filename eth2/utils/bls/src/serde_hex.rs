@@ -0,0 +1,20 @@
+//! Helpers for serializing the BLS types as `0x`-prefixed hex strings of their raw bytes.
+use hex::{decode as hex_decode, encode as hex_encode};
+use serde::de::{Deserialize, Deserializer, Error};
+
+/// Returns `bytes` as a `0x`-prefixed hex string.
+pub fn encode(bytes: &[u8]) -> String {
+    format!("0x{}", hex_encode(bytes))
+}
+
+/// Deserializes a `0x`-prefixed hex string, returning the bytes it represents.
+pub fn decode<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let string = String::deserialize(deserializer)?;
+    if !string.starts_with("0x") {
+        return Err(D::Error::custom("hex string must start with 0x"));
+    }
+    hex_decode(&string[2..]).map_err(|e| D::Error::custom(format!("invalid hex: {:?}", e)))
+}
@@ -1,6 +1,15 @@
-use super::ssz::{decode_ssz_list, hash, Decodable, DecodeError, Encodable, SszStream, TreeHash};
-use super::{PublicKey, SecretKey};
+use super::ssz::{
+    decode_ssz_list, merkleize, ssz_encode, Decodable, DecodeError, Encodable, SszStream, TreeHash,
+    LENGTH_BYTES,
+};
+use super::{PublicKey, SecretKey, BLS_SIG_BYTE_SIZE};
+#[cfg(feature = "serialize")]
+use crate::serde_hex;
 use bls_aggregates::Signature as RawSignature;
+#[cfg(feature = "serialize")]
+use serde::de::{Deserialize, Deserializer, Error as DeserializeError};
+#[cfg(feature = "serialize")]
+use serde::ser::{Serialize, Serializer};
 
 /// A single BLS signature.
 ///
@@ -21,11 +30,26 @@ impl Signature {
         Signature(RawSignature::new_hashed(msg_hashed, sk.as_raw()))
     }
 
+    /// Instantiate a new Signature from a message, a signature domain and a SecretKey.
+    ///
+    /// Note: the underlying library does not support domains, so the SSZ-encoded domain is
+    /// appended to the message.
+    pub fn new_with_domain(msg: &[u8], domain: u64, sk: &SecretKey) -> Self {
+        Signature::new(&message_with_domain(msg, domain), sk)
+    }
+
     /// Verify the Signature against a PublicKey.
     pub fn verify(&self, msg: &[u8], pk: &PublicKey) -> bool {
         self.0.verify(msg, pk.as_raw())
     }
 
+    /// Verify the Signature against a PublicKey and a signature domain.
+    ///
+    /// See `Signature::new_with_domain`.
+    pub fn verify_with_domain(&self, msg: &[u8], domain: u64, pk: &PublicKey) -> bool {
+        self.verify(&message_with_domain(msg, domain), pk)
+    }
+
     /// Verify the Signature against a PublicKey, where the message has already been hashed.
     pub fn verify_hashed(&self, msg_hash: &[u8], pk: &PublicKey) -> bool {
         self.0.verify_hashed(msg_hash, pk.as_raw())
@@ -38,14 +62,24 @@ impl Signature {
 
     /// Returns a new empty signature.
     pub fn empty_signature() -> Self {
-        let empty: Vec<u8> = vec![0; 97];
+        let empty: Vec<u8> = vec![0; BLS_SIG_BYTE_SIZE];
         Signature(RawSignature::from_bytes(&empty).unwrap())
     }
 }
 
+fn message_with_domain(msg: &[u8], domain: u64) -> Vec<u8> {
+    let mut message = msg.to_vec();
+    message.append(&mut ssz_encode(&domain));
+    message
+}
+
 impl Encodable for Signature {
     fn ssz_append(&self, s: &mut SszStream) {
-        s.append_vec(&self.0.as_bytes());
+        s.append_encoded_val(&self.0.as_bytes());
+    }
+
+    fn ssz_size_hint(&self) -> usize {
+        LENGTH_BYTES + BLS_SIG_BYTE_SIZE
     }
 }
 
@@ -57,15 +91,39 @@ impl Decodable for Signature {
     }
 }
 
+#[cfg(feature = "serialize")]
+impl Serialize for Signature {
+    /// Serializes the signature as a `0x`-prefixed hex string of its raw bytes.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&serde_hex::encode(&self.0.as_bytes()))
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<'de> Deserialize<'de> for Signature {
+    /// Deserializes the signature from a `0x`-prefixed hex string of its raw bytes.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = serde_hex::decode(deserializer)?;
+        let raw = RawSignature::from_bytes(&bytes)
+            .map_err(|_| D::Error::custom("invalid signature bytes"))?;
+        Ok(Signature(raw))
+    }
+}
+
 impl TreeHash for Signature {
     fn hash_tree_root(&self) -> Vec<u8> {
-        hash(&self.0.as_bytes())
+        merkleize(&self.0.as_bytes())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::super::ssz::ssz_encode;
     use super::super::Keypair;
     use super::*;
 
@@ -79,6 +137,19 @@ mod tests {
         let (decoded, _) = Signature::ssz_decode(&bytes, 0).unwrap();
 
         assert_eq!(original, decoded);
+        assert_eq!(original.ssz_size_hint(), bytes.len());
+    }
+
+    #[test]
+    pub fn test_sign_and_verify_with_domain() {
+        let keypair = Keypair::random();
+        let message = [42, 42];
+
+        let sig = Signature::new_with_domain(&message, 2, &keypair.sk);
+
+        assert!(sig.verify_with_domain(&message, 2, &keypair.pk));
+        assert!(!sig.verify_with_domain(&message, 3, &keypair.pk));
+        assert!(!sig.verify(&message, &keypair.pk));
     }
 
     #[test]
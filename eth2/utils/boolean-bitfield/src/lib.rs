@@ -2,6 +2,10 @@ extern crate bit_vec;
 extern crate ssz;
 
 use bit_vec::BitVec;
+#[cfg(feature = "serialize")]
+use serde::de::{Deserialize, Deserializer, Error as DeserializeError};
+#[cfg(feature = "serialize")]
+use serde::ser::{Serialize, Serializer};
 
 use std::cmp;
 use std::default;
@@ -116,14 +120,19 @@ impl cmp::PartialEq for BooleanBitfield {
 impl ssz::Encodable for BooleanBitfield {
     // ssz_append encodes Self according to the `ssz` spec.
     fn ssz_append(&self, s: &mut ssz::SszStream) {
-        s.append_vec(&self.to_bytes())
+        s.append_encoded_val(&self.to_bytes())
+    }
+
+    fn ssz_size_hint(&self) -> usize {
+        // Equivalent to `num_bytes()`, without encoding the bitfield.
+        ssz::LENGTH_BYTES + (self.len() + 7) / 8
     }
 }
 
 impl ssz::Decodable for BooleanBitfield {
     fn ssz_decode(bytes: &[u8], index: usize) -> Result<(Self, usize), ssz::DecodeError> {
         let len = ssz::decode::decode_length(bytes, index, ssz::LENGTH_BYTES)?;
-        if (ssz::LENGTH_BYTES + len) > bytes.len() {
+        if (index + ssz::LENGTH_BYTES + len) > bytes.len() {
             return Err(ssz::DecodeError::TooShort);
         }
 
@@ -149,6 +158,35 @@ impl ssz::Decodable for BooleanBitfield {
     }
 }
 
+#[cfg(feature = "serialize")]
+impl Serialize for BooleanBitfield {
+    /// Serializes the bitfield as a `0x`-prefixed hex string of the bytes returned by `to_bytes`.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("0x{}", hex::encode(self.to_bytes())))
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<'de> Deserialize<'de> for BooleanBitfield {
+    /// Deserializes the bitfield from a `0x`-prefixed hex string of the bytes accepted by
+    /// `from_bytes`.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let string = String::deserialize(deserializer)?;
+        if !string.starts_with("0x") {
+            return Err(D::Error::custom("hex string must start with 0x"));
+        }
+        let bytes = hex::decode(&string[2..])
+            .map_err(|e| D::Error::custom(format!("invalid hex: {:?}", e)))?;
+        Ok(BooleanBitfield::from_bytes(&bytes))
+    }
+}
+
 impl ssz::TreeHash for BooleanBitfield {
     fn hash_tree_root(&self) -> Vec<u8> {
         self.to_bytes().hash_tree_root()
@@ -158,7 +196,7 @@ impl ssz::TreeHash for BooleanBitfield {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ssz::{ssz_encode, Decodable, SszStream};
+    use ssz::{ssz_encode, Decodable, Encodable, SszStream};
 
     #[test]
     fn test_new_bitfield() {
@@ -334,6 +372,14 @@ mod tests {
         assert_eq!(stream.drain(), vec![0, 0, 0, 3, 255, 255, 192]);
     }
 
+    #[test]
+    fn test_ssz_size_hint() {
+        for len in &[0, 1, 8, 13, 18] {
+            let field = BooleanBitfield::from_elem(*len, true);
+            assert_eq!(field.ssz_size_hint(), ssz_encode(&field).len());
+        }
+    }
+
     fn create_test_bitfield() -> BooleanBitfield {
         let count = 2 * 8;
         let mut field = BooleanBitfield::with_capacity(count);
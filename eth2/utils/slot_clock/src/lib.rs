@@ -4,8 +4,14 @@ mod testing_slot_clock;
 pub use crate::system_time_slot_clock::{Error as SystemTimeSlotClockError, SystemTimeSlotClock};
 pub use crate::testing_slot_clock::{Error as TestingSlotClockError, TestingSlotClock};
 
+use std::time::Duration;
+
 pub trait SlotClock: Send + Sync {
     type Error;
 
     fn present_slot(&self) -> Result<Option<u64>, Self::Error>;
+
+    /// Returns the time elapsed since the start of the present slot, or `None` if the present
+    /// slot is unknown (e.g., prior to genesis).
+    fn present_slot_offset(&self) -> Result<Option<Duration>, Self::Error>;
 }
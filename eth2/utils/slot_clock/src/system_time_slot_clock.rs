@@ -34,19 +34,31 @@ impl SystemTimeSlotClock {
     }
 }
 
+impl SystemTimeSlotClock {
+    /// Returns the time elapsed since genesis, or `None` if genesis is in the future.
+    fn duration_since_genesis(&self) -> Result<Option<Duration>, Error> {
+        let syslot_time = SystemTime::now();
+        let duration_since_epoch = syslot_time.duration_since(SystemTime::UNIX_EPOCH)?;
+        Ok(duration_since_epoch.checked_sub(Duration::from_secs(self.genesis_seconds)))
+    }
+}
+
 impl SlotClock for SystemTimeSlotClock {
     type Error = Error;
 
     fn present_slot(&self) -> Result<Option<u64>, Error> {
-        let syslot_time = SystemTime::now();
-        let duration_since_epoch = syslot_time.duration_since(SystemTime::UNIX_EPOCH)?;
-        let duration_since_genesis =
-            duration_since_epoch.checked_sub(Duration::from_secs(self.genesis_seconds));
-        match duration_since_genesis {
+        match self.duration_since_genesis()? {
             None => Ok(None),
             Some(d) => Ok(slot_from_duration(self.slot_duration_seconds, d)),
         }
     }
+
+    fn present_slot_offset(&self) -> Result<Option<Duration>, Error> {
+        match self.duration_since_genesis()? {
+            None => Ok(None),
+            Some(d) => Ok(slot_offset_from_duration(self.slot_duration_seconds, d)),
+        }
+    }
 }
 
 impl From<SystemTimeError> for Error {
@@ -59,6 +71,11 @@ fn slot_from_duration(slot_duration_seconds: u64, duration: Duration) -> Option<
     duration.as_secs().checked_div(slot_duration_seconds)
 }
 
+fn slot_offset_from_duration(slot_duration_seconds: u64, duration: Duration) -> Option<Duration> {
+    let seconds = duration.as_secs().checked_rem(slot_duration_seconds)?;
+    Some(Duration::new(seconds, duration.subsec_nanos()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,6 +138,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_slot_offset_from_duration() {
+        let slot_time = 100;
+
+        assert_eq!(
+            slot_offset_from_duration(slot_time, Duration::from_secs(0)),
+            Some(Duration::from_secs(0))
+        );
+        assert_eq!(
+            slot_offset_from_duration(slot_time, Duration::from_millis(10_500)),
+            Some(Duration::from_millis(10_500))
+        );
+        assert_eq!(
+            slot_offset_from_duration(slot_time, Duration::from_secs(1001)),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(slot_offset_from_duration(0, Duration::from_secs(10)), None);
+    }
+
     #[test]
     fn test_slot_from_duration_slot_time_zero() {
         let slot_time = 0;
@@ -1,4 +1,5 @@
 use super::SlotClock;
+use std::time::Duration;
 
 #[derive(Debug, PartialEq)]
 pub enum Error {}
@@ -6,6 +7,7 @@ pub enum Error {}
 /// Determines the present slot based upon the present system time.
 pub struct TestingSlotClock {
     slot: u64,
+    slot_offset: Duration,
 }
 
 impl TestingSlotClock {
@@ -13,12 +15,19 @@ impl TestingSlotClock {
     ///
     /// Returns an Error if `slot_duration_seconds == 0`.
     pub fn new(slot: u64) -> TestingSlotClock {
-        TestingSlotClock { slot }
+        TestingSlotClock {
+            slot,
+            slot_offset: Duration::from_secs(0),
+        }
     }
 
     pub fn set_slot(&mut self, slot: u64) {
         self.slot = slot;
     }
+
+    pub fn set_slot_offset(&mut self, slot_offset: Duration) {
+        self.slot_offset = slot_offset;
+    }
 }
 
 impl SlotClock for TestingSlotClock {
@@ -27,6 +36,10 @@ impl SlotClock for TestingSlotClock {
     fn present_slot(&self) -> Result<Option<u64>, Error> {
         Ok(Some(self.slot))
     }
+
+    fn present_slot_offset(&self) -> Result<Option<Duration>, Error> {
+        Ok(Some(self.slot_offset))
+    }
 }
 
 #[cfg(test)]
@@ -39,5 +52,14 @@ mod tests {
         assert_eq!(clock.present_slot(), Ok(Some(10)));
         clock.set_slot(123);
         assert_eq!(clock.present_slot(), Ok(Some(123)));
+        assert_eq!(
+            clock.present_slot_offset(),
+            Ok(Some(Duration::from_secs(0)))
+        );
+        clock.set_slot_offset(Duration::from_secs(3));
+        assert_eq!(
+            clock.present_slot_offset(),
+            Ok(Some(Duration::from_secs(3)))
+        );
     }
 }
@@ -0,0 +1,225 @@
+use super::{hash, mix_in_length, TreeHash, TreeHashType, BYTES_PER_CHUNK};
+
+/// A merkle tree which stores all of its intermediate nodes, such that after a change to some of
+/// its leaves the root may be recomputed by rehashing only the nodes above those leaves.
+///
+/// The tree is built identically to `merkleize`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MerkleCache {
+    /// The nodes of the tree, with the root at index `1` and the children of node `i` at `2i` and
+    /// `2i + 1`. Index `0` is unused.
+    nodes: Vec<u8>,
+    /// The number of leaves in the tree, including padding. Always a power of two.
+    num_leaves: usize,
+}
+
+impl MerkleCache {
+    /// Builds the tree over `bytes`, split into 32 byte chunks.
+    pub fn new(bytes: &[u8]) -> Self {
+        let num_leaves = num_leaves(bytes);
+
+        let mut nodes = vec![0; 2 * num_leaves * BYTES_PER_CHUNK];
+        let leaves_start = num_leaves * BYTES_PER_CHUNK;
+        nodes[leaves_start..leaves_start + bytes.len()].copy_from_slice(bytes);
+
+        let mut cache = Self { nodes, num_leaves };
+        for i in (1..num_leaves).rev() {
+            cache.rehash_node(i);
+        }
+        cache
+    }
+
+    /// Returns the merkle root of the tree.
+    ///
+    /// An empty (default) cache has no root.
+    pub fn root(&self) -> Option<&[u8]> {
+        if self.num_leaves == 0 {
+            None
+        } else {
+            Some(self.node(1))
+        }
+    }
+
+    /// Returns the `i`th leaf of the tree, if it exists.
+    pub fn leaf(&self, i: usize) -> Option<&[u8]> {
+        if i < self.num_leaves {
+            Some(self.node(self.num_leaves + i))
+        } else {
+            None
+        }
+    }
+
+    /// Updates the tree to be built over `bytes`, rehashing only the nodes above leaves which have
+    /// changed.
+    ///
+    /// If `bytes` requires a different number of leaves, the tree is rebuilt.
+    pub fn update(&mut self, bytes: &[u8]) {
+        if num_leaves(bytes) != self.num_leaves {
+            *self = Self::new(bytes);
+            return;
+        }
+
+        let mut padded = bytes.to_vec();
+        padded.resize(self.num_leaves * BYTES_PER_CHUNK, 0);
+
+        let mut dirty = vec![];
+        for (i, chunk) in padded.chunks(BYTES_PER_CHUNK).enumerate() {
+            let node = self.num_leaves + i;
+            if self.node(node) != chunk {
+                self.node_mut(node).copy_from_slice(chunk);
+                dirty.push(node);
+            }
+        }
+
+        // Each pass moves up one level of the tree, until the root has been rehashed.
+        while dirty.first().map_or(false, |&node| node > 1) {
+            dirty = dirty.iter().map(|node| node / 2).collect();
+            dirty.dedup();
+            for &node in &dirty {
+                self.rehash_node(node);
+            }
+        }
+    }
+
+    fn node(&self, i: usize) -> &[u8] {
+        &self.nodes[i * BYTES_PER_CHUNK..(i + 1) * BYTES_PER_CHUNK]
+    }
+
+    fn node_mut(&mut self, i: usize) -> &mut [u8] {
+        &mut self.nodes[i * BYTES_PER_CHUNK..(i + 1) * BYTES_PER_CHUNK]
+    }
+
+    /// Sets node `i` to the hash of its two children.
+    fn rehash_node(&mut self, i: usize) {
+        let children = 2 * i * BYTES_PER_CHUNK..(2 * i + 2) * BYTES_PER_CHUNK;
+        let root = hash(&self.nodes[children]);
+        self.node_mut(i).copy_from_slice(&root);
+    }
+}
+
+/// Returns the number of leaves in the tree built by `merkleize` over `bytes`.
+fn num_leaves(bytes: &[u8]) -> usize {
+    let num_chunks = (bytes.len() + BYTES_PER_CHUNK - 1) / BYTES_PER_CHUNK;
+    num_chunks.max(1).next_power_of_two()
+}
+
+/// Caches the tree hash of a list, such that after a change to some of its elements the root may
+/// be recomputed by rehashing only those elements and the nodes above them.
+///
+/// Changed elements are found by comparing each element to a copy held from the previous update.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TreeHashListCache<T> {
+    elements: Vec<T>,
+    tree: MerkleCache,
+}
+
+impl<T> TreeHashListCache<T>
+where
+    T: TreeHash + Clone + PartialEq,
+{
+    /// Returns the `hash_tree_root` of `list`, updating the cache to reflect it.
+    pub fn hash_tree_root(&mut self, list: &[T]) -> Vec<u8> {
+        let root = self.vector_root(list);
+        mix_in_length(&root, list.len())
+    }
+
+    /// Returns the root of `vector` as a fixed-length vector (i.e., without its length mixed in),
+    /// updating the cache to reflect it.
+    pub fn vector_root(&mut self, vector: &[T]) -> Vec<u8> {
+        let leaves = match T::tree_hash_type() {
+            // Packing basic values is cheap, so changes are found by comparing the leaves.
+            TreeHashType::Basic => vector
+                .iter()
+                .flat_map(|element| element.tree_hash_packed_encoding())
+                .collect(),
+            TreeHashType::Composite => self.composite_leaves(vector),
+        };
+
+        self.tree.update(&leaves);
+        self.tree
+            .root()
+            .expect("An updated tree has a root")
+            .to_vec()
+    }
+
+    /// Returns the roots of the elements of `list`, only rehashing elements which differ from the
+    /// previous update.
+    fn composite_leaves(&mut self, list: &[T]) -> Vec<u8> {
+        let mut leaves = Vec::with_capacity(list.len() * BYTES_PER_CHUNK);
+
+        for (i, element) in list.iter().enumerate() {
+            if self.elements.get(i) == Some(element) {
+                let leaf = self.tree.leaf(i).expect("Each cached element has a leaf");
+                leaves.extend_from_slice(leaf);
+            } else {
+                leaves.append(&mut element.hash_tree_root());
+                if i < self.elements.len() {
+                    self.elements[i] = element.clone();
+                } else {
+                    self.elements.push(element.clone());
+                }
+            }
+        }
+        self.elements.truncate(list.len());
+
+        leaves
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{merkleize, vector_root};
+    use super::*;
+    use ethereum_types::H256;
+
+    #[test]
+    fn test_merkle_cache() {
+        for len in &[0, 1, 31, 32, 33, 64, 100, 257] {
+            let bytes: Vec<u8> = (0..*len).map(|i| i as u8).collect();
+            let mut cache = MerkleCache::new(&bytes);
+            assert_eq!(cache.root(), Some(&merkleize(&bytes)[..]));
+
+            // Change the first and last bytes.
+            let mut changed = bytes.clone();
+            if let Some(first) = changed.first_mut() {
+                *first = 255;
+            }
+            if let Some(last) = changed.last_mut() {
+                *last = 254;
+            }
+            cache.update(&changed);
+            assert_eq!(cache.root(), Some(&merkleize(&changed)[..]));
+
+            // Grow the tree.
+            changed.append(&mut vec![7; 40]);
+            cache.update(&changed);
+            assert_eq!(cache.root(), Some(&merkleize(&changed)[..]));
+        }
+    }
+
+    #[test]
+    fn test_list_cache() {
+        let mut cache = TreeHashListCache::default();
+        let mut list: Vec<H256> = (0..10).map(|i| H256::from(i as u64)).collect();
+        assert_eq!(cache.hash_tree_root(&list), list.hash_tree_root());
+
+        list[3] = H256::from(42u64);
+        list.push(H256::from(43u64));
+        assert_eq!(cache.hash_tree_root(&list), list.hash_tree_root());
+
+        list.truncate(2);
+        assert_eq!(cache.hash_tree_root(&list), list.hash_tree_root());
+
+        list.clear();
+        assert_eq!(cache.hash_tree_root(&list), list.hash_tree_root());
+
+        let mut cache = TreeHashListCache::default();
+        let mut list: Vec<u16> = (0..100).collect();
+        assert_eq!(cache.hash_tree_root(&list), list.hash_tree_root());
+
+        list[50] = 7;
+        assert_eq!(cache.hash_tree_root(&list), list.hash_tree_root());
+        assert_ne!(cache.hash_tree_root(&list), vector_root(&list));
+        assert_eq!(cache.vector_root(&list), vector_root(&list));
+    }
+}
@@ -1,10 +1,78 @@
 use super::LENGTH_BYTES;
+use std::fmt;
 
 #[derive(Debug, PartialEq)]
 pub enum DecodeError {
     TooShort,
     TooLong,
     Invalid,
+    /// A list contained more than `max_length` elements.
+    TooManyElements {
+        max_length: usize,
+    },
+    /// An error occurred whilst decoding the value at `path` (e.g., `body.attestations[2].data`),
+    /// which begins at byte `offset` of the input.
+    InField {
+        path: String,
+        offset: usize,
+        error: Box<DecodeError>,
+    },
+}
+
+impl DecodeError {
+    /// Wraps the error with the name of the container field in which it occurred, where the field
+    /// begins at byte `offset`.
+    ///
+    /// The offset of the innermost field is kept, so it points at the value which failed.
+    pub fn in_field(self, field: &str, offset: usize) -> Self {
+        self.prepend_path(field, offset)
+    }
+
+    /// Wraps the error with the index of the list element in which it occurred, where the element
+    /// begins at byte `offset`.
+    pub fn in_element(self, index: usize, offset: usize) -> Self {
+        self.prepend_path(&format!("[{}]", index), offset)
+    }
+
+    fn prepend_path(self, prefix: &str, offset: usize) -> Self {
+        match self {
+            DecodeError::InField {
+                path,
+                offset: inner_offset,
+                error,
+            } => {
+                let separator = if path.starts_with('[') { "" } else { "." };
+                DecodeError::InField {
+                    path: format!("{}{}{}", prefix, separator, path),
+                    offset: inner_offset,
+                    error,
+                }
+            }
+            error => DecodeError::InField {
+                path: prefix.to_string(),
+                offset,
+                error: Box::new(error),
+            },
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::TooShort => write!(f, "too short"),
+            DecodeError::TooLong => write!(f, "too long"),
+            DecodeError::Invalid => write!(f, "invalid"),
+            DecodeError::TooManyElements { max_length } => {
+                write!(f, "more than {} elements", max_length)
+            }
+            DecodeError::InField {
+                path,
+                offset,
+                error,
+            } => write!(f, "{} (at byte {}): {}", path, offset, error),
+        }
+    }
 }
 
 pub trait Decodable: Sized {
@@ -25,10 +93,44 @@ where
     T::ssz_decode(ssz_bytes, index)
 }
 
+/// Decode the given bytes as a single value of the given type, returning an error if any bytes
+/// remain after the value.
+///
+/// This should be used for all top-level values (e.g., those received from the network or read
+/// from the database).
+pub fn decode_ssz_exact<T>(ssz_bytes: &[u8]) -> Result<T, DecodeError>
+where
+    T: Decodable,
+{
+    let (value, index) = T::ssz_decode(ssz_bytes, 0)?;
+    if index == ssz_bytes.len() {
+        Ok(value)
+    } else {
+        Err(DecodeError::TooLong)
+    }
+}
+
 /// Decode a vector (list) of encoded bytes.
 ///
 /// Each element in the list will be decoded and placed into the vector.
 pub fn decode_ssz_list<T>(ssz_bytes: &[u8], index: usize) -> Result<(Vec<T>, usize), DecodeError>
+where
+    T: Decodable,
+{
+    decode_ssz_list_with_max(ssz_bytes, index, usize::max_value())
+}
+
+/// Decode a vector (list) of encoded bytes, returning an error if it has more than `max_length`
+/// elements.
+///
+/// Decoding stops as soon as the limit is exceeded, so a length prefix declaring many elements
+/// cannot cause them all to be decoded. Elements may not extend beyond the declared length of the
+/// list.
+pub fn decode_ssz_list_with_max<T>(
+    ssz_bytes: &[u8],
+    index: usize,
+    max_length: usize,
+) -> Result<(Vec<T>, usize), DecodeError>
 where
     T: Decodable,
 {
@@ -37,10 +139,7 @@ where
     };
 
     // get the length
-    let serialized_length = match decode_length(ssz_bytes, index, LENGTH_BYTES) {
-        Err(v) => return Err(v),
-        Ok(v) => v,
-    };
+    let serialized_length = decode_length(ssz_bytes, index, LENGTH_BYTES)?;
 
     let final_len: usize = index + LENGTH_BYTES + serialized_length;
 
@@ -48,22 +147,47 @@ where
         return Err(DecodeError::TooShort);
     };
 
+    // Elements are decoded from the bytes of the list alone, so they cannot overrun it.
+    let list_bytes = &ssz_bytes[..final_len];
     let mut tmp_index = index + LENGTH_BYTES;
     let mut res_vec: Vec<T> = Vec::new();
 
     while tmp_index < final_len {
-        match T::ssz_decode(ssz_bytes, tmp_index) {
-            Err(v) => return Err(v),
-            Ok(v) => {
-                tmp_index = v.1;
-                res_vec.push(v.0);
-            }
-        };
+        if res_vec.len() == max_length {
+            return Err(DecodeError::TooManyElements { max_length });
+        }
+
+        let (element, next_index) = T::ssz_decode(list_bytes, tmp_index)
+            .map_err(|e| e.in_element(res_vec.len(), tmp_index))?;
+        tmp_index = next_index;
+        res_vec.push(element);
     }
 
     Ok((res_vec, final_len))
 }
 
+/// Decode a fixed-length vector of exactly `length` elements, which has no length prefix.
+pub fn decode_ssz_fixed_vec<T>(
+    ssz_bytes: &[u8],
+    index: usize,
+    length: usize,
+) -> Result<(Vec<T>, usize), DecodeError>
+where
+    T: Decodable,
+{
+    let mut tmp_index = index;
+    let mut res_vec: Vec<T> = Vec::new();
+
+    while res_vec.len() < length {
+        let (element, next_index) = T::ssz_decode(ssz_bytes, tmp_index)
+            .map_err(|e| e.in_element(res_vec.len(), tmp_index))?;
+        tmp_index = next_index;
+        res_vec.push(element);
+    }
+
+    Ok((res_vec, tmp_index))
+}
+
 /// Given some number of bytes, interpret the first four
 /// bytes as a 32-bit big-endian integer and return the
 /// result.
@@ -189,5 +313,82 @@ mod tests {
         let decoded: Result<(Vec<usize>, usize), DecodeError> =
             decode_ssz_list(&vec![0, 0, 0, 0, 0, 0, 0, 15], 16);
         assert_eq!(decoded, Err(DecodeError::TooShort));
+
+        // Check that an element cannot extend beyond the list
+        let decoded: Result<(Vec<u16>, usize), DecodeError> =
+            decode_ssz_list(&vec![0, 0, 0, 3, 0, 1, 0, 2], 0);
+        assert_eq!(
+            decoded,
+            Err(DecodeError::InField {
+                path: "[1]".to_string(),
+                offset: 6,
+                error: Box::new(DecodeError::TooShort),
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_ssz_list_with_max() {
+        let ssz = vec![0, 0, 0, 6, 0, 1, 0, 2, 0, 3];
+
+        let decoded: (Vec<u16>, usize) = decode_ssz_list_with_max(&ssz, 0, 3).unwrap();
+        assert_eq!(decoded, (vec![1, 2, 3], 10));
+
+        let decoded: Result<(Vec<u16>, usize), DecodeError> = decode_ssz_list_with_max(&ssz, 0, 2);
+        assert_eq!(decoded, Err(DecodeError::TooManyElements { max_length: 2 }));
+    }
+
+    #[test]
+    fn test_decode_ssz_fixed_vec() {
+        let ssz = vec![0, 1, 0, 2, 0, 3];
+
+        let decoded: (Vec<u16>, usize) = decode_ssz_fixed_vec(&ssz, 0, 2).unwrap();
+        assert_eq!(decoded, (vec![1, 2], 4));
+
+        let decoded: (Vec<u16>, usize) = decode_ssz_fixed_vec(&ssz, 2, 0).unwrap();
+        assert_eq!(decoded, (vec![], 2));
+
+        let decoded: Result<(Vec<u16>, usize), DecodeError> = decode_ssz_fixed_vec(&ssz, 2, 3);
+        assert_eq!(
+            decoded,
+            Err(DecodeError::InField {
+                path: "[2]".to_string(),
+                offset: 6,
+                error: Box::new(DecodeError::TooShort),
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_ssz_exact() {
+        let result: u16 = decode_ssz_exact(&vec![0, 1]).unwrap();
+        assert_eq!(result, 1);
+
+        let result: Result<u16, DecodeError> = decode_ssz_exact(&vec![0, 1, 2]);
+        assert_eq!(result, Err(DecodeError::TooLong));
+
+        let result: Result<u16, DecodeError> = decode_ssz_exact(&vec![0]);
+        assert_eq!(result, Err(DecodeError::TooShort));
+    }
+
+    #[test]
+    fn test_error_path() {
+        let error = DecodeError::TooShort
+            .in_field("slot", 40)
+            .in_element(2, 32)
+            .in_field("attestations", 8)
+            .in_field("body", 0);
+        assert_eq!(
+            error,
+            DecodeError::InField {
+                path: "body.attestations[2].slot".to_string(),
+                offset: 40,
+                error: Box::new(DecodeError::TooShort),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "body.attestations[2].slot (at byte 40): too short"
+        );
     }
 }
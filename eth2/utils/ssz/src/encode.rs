@@ -2,6 +2,23 @@ use super::LENGTH_BYTES;
 
 pub trait Encodable {
     fn ssz_append(&self, s: &mut SszStream);
+
+    /// Returns an estimate of the number of bytes `ssz_append` will write, used to pre-size the
+    /// buffer of an `SszStream`.
+    ///
+    /// The estimate need not be exact; the default of `0` is always safe, but may cause the
+    /// buffer to be reallocated as it grows.
+    fn ssz_size_hint(&self) -> usize {
+        0
+    }
+}
+
+/// Returns the sum of the size hints of each item in `vec`, excluding any length prefix.
+pub fn vec_size_hint<E>(vec: &[E]) -> usize
+where
+    E: Encodable,
+{
+    vec.iter().map(Encodable::ssz_size_hint).sum()
 }
 
 /// Provides a buffer for appending ssz-encodable values.
@@ -20,6 +37,31 @@ impl SszStream {
         SszStream { buffer: Vec::new() }
     }
 
+    /// Create a new, empty stream with space for `capacity` bytes before it must reallocate.
+    ///
+    /// Use `Encodable::ssz_size_hint` to find a suitable capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        SszStream {
+            buffer: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Create a stream which appends to the end of `buffer`, such that values are encoded directly
+    /// into a buffer owned by the caller.
+    pub fn from_buffer(buffer: Vec<u8>) -> Self {
+        SszStream { buffer }
+    }
+
+    /// Returns the number of bytes written to the stream.
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns `true` if no bytes have been written to the stream.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
     /// Append some ssz encodable value to the stream.
     pub fn append<E>(&mut self, value: &E) -> &mut Self
     where
@@ -50,15 +92,36 @@ impl SszStream {
     ///
     /// The length of the list will be concatenated to the stream, then
     /// each item in the vector will be encoded and concatenated.
+    ///
+    /// The items are written directly into the stream after a placeholder length, which is
+    /// filled once the encoded length of the items is known.
     pub fn append_vec<E>(&mut self, vec: &[E])
     where
         E: Encodable,
     {
-        let mut list_stream = SszStream::new();
+        let length_index = self.buffer.len();
+        self.buffer.extend_from_slice(&[0; LENGTH_BYTES]);
+
+        for item in vec {
+            item.ssz_append(self);
+        }
+
+        let len = self.buffer.len() - length_index - LENGTH_BYTES;
+        self.buffer[length_index..length_index + LENGTH_BYTES]
+            .copy_from_slice(&encode_length(len, LENGTH_BYTES));
+    }
+
+    /// Append some fixed-length vector of encodable values to the stream.
+    ///
+    /// Unlike `append_vec`, the length is not concatenated to the stream, so it must be known
+    /// when decoding (see `decode_ssz_fixed_vec`).
+    pub fn append_fixed_vec<E>(&mut self, vec: &[E])
+    where
+        E: Encodable,
+    {
         for item in vec {
-            item.ssz_append(&mut list_stream);
+            item.ssz_append(self);
         }
-        self.append_encoded_val(&list_stream.drain());
     }
 
     /// Consume the stream and return the underlying bytes.
@@ -121,4 +184,45 @@ mod tests {
         assert_eq!(ssz[0..4], *vec![0, 0, 0, 24]);
         assert_eq!(ssz[4..6], *vec![1, 0]);
     }
+
+    #[test]
+    fn test_encode_nested_list() {
+        let test_vec: Vec<Vec<u16>> = vec![vec![], vec![1, 2]];
+        let mut stream = SszStream::new();
+        stream.append_vec(&test_vec);
+        let ssz = stream.drain();
+
+        assert_eq!(ssz, vec![0, 0, 0, 12, 0, 0, 0, 0, 0, 0, 0, 4, 0, 1, 0, 2]);
+    }
+
+    #[test]
+    fn test_size_hint() {
+        let test_vec: Vec<Vec<u16>> = vec![vec![], vec![1, 2]];
+        let mut stream = SszStream::with_capacity(test_vec.ssz_size_hint());
+        stream.append(&test_vec);
+
+        assert_eq!(test_vec.ssz_size_hint(), 16);
+        assert_eq!(stream.len(), 16);
+        assert_eq!(vec_size_hint(&test_vec), 12);
+    }
+
+    #[test]
+    fn test_from_buffer() {
+        let mut stream = SszStream::from_buffer(vec![42]);
+        stream.append_vec(&[1_u8]);
+
+        assert_eq!(stream.len(), 6);
+        assert_eq!(stream.drain(), vec![42, 0, 0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_encode_fixed_vec() {
+        let test_vec: Vec<u16> = vec![256; 12];
+        let mut stream = SszStream::new();
+        stream.append_fixed_vec(&test_vec);
+        let ssz = stream.drain();
+
+        assert_eq!(ssz.len(), 12 * 2);
+        assert_eq!(ssz[0..2], *vec![1, 0]);
+    }
 }
@@ -39,6 +39,39 @@ impl Decodable for u8 {
     }
 }
 
+impl Decodable for bool {
+    fn ssz_decode(bytes: &[u8], index: usize) -> Result<(Self, usize), DecodeError> {
+        match u8::ssz_decode(bytes, index)? {
+            (0, i) => Ok((false, i)),
+            (1, i) => Ok((true, i)),
+            _ => Err(DecodeError::Invalid),
+        }
+    }
+}
+
+macro_rules! impl_decodable_for_u8_array {
+    ($len: expr) => {
+        impl Decodable for [u8; $len] {
+            fn ssz_decode(bytes: &[u8], index: usize) -> Result<(Self, usize), DecodeError> {
+                if bytes.len() < $len || bytes.len() - $len < index {
+                    Err(DecodeError::TooShort)
+                } else {
+                    let mut array = [0; $len];
+                    array.copy_from_slice(&bytes[index..(index + $len)]);
+                    Ok((array, index + $len))
+                }
+            }
+        }
+    };
+}
+
+impl_decodable_for_u8_array!(4);
+impl_decodable_for_u8_array!(8);
+impl_decodable_for_u8_array!(20);
+impl_decodable_for_u8_array!(32);
+impl_decodable_for_u8_array!(48);
+impl_decodable_for_u8_array!(96);
+
 impl Decodable for H256 {
     fn ssz_decode(bytes: &[u8], index: usize) -> Result<(Self, usize), DecodeError> {
         if bytes.len() < 32 || bytes.len() - 32 < index {
@@ -68,6 +101,23 @@ where
     }
 }
 
+/// Decodes the union encoding of an `Option`, as described by its `Encodable` impl.
+impl<T> Decodable for Option<T>
+where
+    T: Decodable,
+{
+    fn ssz_decode(bytes: &[u8], index: usize) -> Result<(Self, usize), DecodeError> {
+        match u32::ssz_decode(bytes, index)? {
+            (0, i) => Ok((None, i)),
+            (1, i) => {
+                let (value, i) = T::ssz_decode(bytes, i)?;
+                Ok((Some(value), i))
+            }
+            _ => Err(DecodeError::Invalid),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::{decode_ssz, DecodeError};
@@ -100,6 +150,45 @@ mod tests {
         assert_eq!(res, Err(DecodeError::TooShort));
     }
 
+    #[test]
+    fn test_ssz_decode_bool() {
+        let (result, index): (bool, usize) = decode_ssz(&vec![0, 1], 0).unwrap();
+        assert_eq!((result, index), (false, 1));
+
+        let (result, index): (bool, usize) = decode_ssz(&vec![0, 1], 1).unwrap();
+        assert_eq!((result, index), (true, 2));
+
+        let result: Result<(bool, usize), DecodeError> = decode_ssz(&vec![2], 0);
+        assert_eq!(result, Err(DecodeError::Invalid));
+    }
+
+    #[test]
+    fn test_ssz_decode_u8_array() {
+        let ssz = vec![0, 1, 2, 3, 4];
+        let (result, index): ([u8; 4], usize) = decode_ssz(&ssz, 1).unwrap();
+        assert_eq!((result, index), ([1, 2, 3, 4], 5));
+
+        let result: Result<([u8; 4], usize), DecodeError> = decode_ssz(&ssz, 2);
+        assert_eq!(result, Err(DecodeError::TooShort));
+    }
+
+    #[test]
+    fn test_ssz_decode_option() {
+        let (result, index): (Option<u16>, usize) = decode_ssz(&vec![0, 0, 0, 0], 0).unwrap();
+        assert_eq!((result, index), (None, 4));
+
+        let ssz = vec![0, 0, 0, 1, 0, 1];
+        let (result, index): (Option<u16>, usize) = decode_ssz(&ssz, 0).unwrap();
+        assert_eq!((result, index), (Some(1), 6));
+
+        let result: Result<(Option<u16>, usize), DecodeError> = decode_ssz(&ssz[..5], 0);
+        assert_eq!(result, Err(DecodeError::TooShort));
+
+        let result: Result<(Option<u16>, usize), DecodeError> =
+            decode_ssz(&vec![0, 0, 0, 2, 0, 1], 0);
+        assert_eq!(result, Err(DecodeError::Invalid));
+    }
+
     #[test]
     fn test_ssz_decode_u16() {
         let ssz = vec![0, 0];
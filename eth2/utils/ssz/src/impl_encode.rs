@@ -1,8 +1,9 @@
 extern crate bytes;
 
 use self::bytes::{BufMut, BytesMut};
+use super::encode::vec_size_hint;
 use super::ethereum_types::{Address, H256};
-use super::{Encodable, SszStream};
+use super::{Encodable, SszStream, LENGTH_BYTES};
 
 /*
  * Note: there is a "to_bytes" function for integers
@@ -34,7 +35,11 @@ macro_rules! impl_encodable_for_uint {
                 }
 
                 // Append bytes to the SszStream
-                s.append_encoded_raw(&buf.to_vec());
+                s.append_encoded_raw(&buf);
+            }
+
+            fn ssz_size_hint(&self) -> usize {
+                $bit_size / 8
             }
         }
     };
@@ -46,15 +51,54 @@ impl_encodable_for_uint!(u32, 32);
 impl_encodable_for_uint!(u64, 64);
 impl_encodable_for_uint!(usize, 64);
 
+impl Encodable for bool {
+    fn ssz_append(&self, s: &mut SszStream) {
+        s.append_encoded_raw(&[*self as u8]);
+    }
+
+    fn ssz_size_hint(&self) -> usize {
+        1
+    }
+}
+
+macro_rules! impl_encodable_for_u8_array {
+    ($len: expr) => {
+        impl Encodable for [u8; $len] {
+            fn ssz_append(&self, s: &mut SszStream) {
+                s.append_encoded_raw(&self[..]);
+            }
+
+            fn ssz_size_hint(&self) -> usize {
+                $len
+            }
+        }
+    };
+}
+
+impl_encodable_for_u8_array!(4);
+impl_encodable_for_u8_array!(8);
+impl_encodable_for_u8_array!(20);
+impl_encodable_for_u8_array!(32);
+impl_encodable_for_u8_array!(48);
+impl_encodable_for_u8_array!(96);
+
 impl Encodable for H256 {
     fn ssz_append(&self, s: &mut SszStream) {
-        s.append_encoded_raw(&self.to_vec());
+        s.append_encoded_raw(&self[..]);
+    }
+
+    fn ssz_size_hint(&self) -> usize {
+        32
     }
 }
 
 impl Encodable for Address {
     fn ssz_append(&self, s: &mut SszStream) {
-        s.append_encoded_raw(&self.to_vec());
+        s.append_encoded_raw(&self[..]);
+    }
+
+    fn ssz_size_hint(&self) -> usize {
+        20
     }
 }
 
@@ -65,6 +109,33 @@ where
     fn ssz_append(&self, s: &mut SszStream) {
         s.append_vec(&self);
     }
+
+    fn ssz_size_hint(&self) -> usize {
+        LENGTH_BYTES + vec_size_hint(self)
+    }
+}
+
+/// An `Option` is encoded as a union of "null" and `T`: the `u32` type index of the value (`0`
+/// for `None` and `1` for `Some`), followed by the value itself.
+impl<T> Encodable for Option<T>
+where
+    T: Encodable,
+{
+    fn ssz_append(&self, s: &mut SszStream) {
+        match self {
+            None => {
+                s.append(&0_u32);
+            }
+            Some(value) => {
+                s.append(&1_u32);
+                s.append(value);
+            }
+        }
+    }
+
+    fn ssz_size_hint(&self) -> usize {
+        4 + self.as_ref().map_or(0, Encodable::ssz_size_hint)
+    }
 }
 
 #[cfg(test)]
@@ -87,6 +158,35 @@ mod tests {
         assert_eq!(ssz.drain(), vec![0; 20]);
     }
 
+    #[test]
+    fn test_ssz_encode_bool() {
+        let mut ssz = SszStream::new();
+        ssz.append(&true).append(&false);
+        assert_eq!(ssz.drain(), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_ssz_encode_u8_array() {
+        let mut ssz = SszStream::new();
+        ssz.append(&[1_u8, 2, 3, 4]);
+        assert_eq!(ssz.drain(), vec![1, 2, 3, 4]);
+
+        let mut ssz = SszStream::new();
+        ssz.append(&[42_u8; 48]);
+        assert_eq!(ssz.drain(), vec![42; 48]);
+    }
+
+    #[test]
+    fn test_ssz_encode_option() {
+        let mut ssz = SszStream::new();
+        ssz.append(&None::<u16>);
+        assert_eq!(ssz.drain(), vec![0, 0, 0, 0]);
+
+        let mut ssz = SszStream::new();
+        ssz.append(&Some(1_u16));
+        assert_eq!(ssz.drain(), vec![0, 0, 0, 1, 0, 1]);
+    }
+
     #[test]
     fn test_ssz_encode_u8() {
         let x: u8 = 0;
@@ -206,4 +306,26 @@ mod tests {
         ssz.append(&x);
         assert_eq!(ssz.drain(), vec![255, 255, 255, 255, 255, 255, 255, 255]);
     }
+
+    #[test]
+    fn test_ssz_size_hint_is_exact() {
+        fn check<E: Encodable>(value: E) {
+            let mut ssz = SszStream::new();
+            ssz.append(&value);
+            assert_eq!(value.ssz_size_hint(), ssz.len());
+        }
+
+        check(1_u8);
+        check(1_u16);
+        check(1_u32);
+        check(1_u64);
+        check(1_usize);
+        check(true);
+        check([1_u8; 48]);
+        check(H256::zero());
+        check(Address::zero());
+        check(vec![vec![1_u16, 2], vec![]]);
+        check(Some(1_u64));
+        check(None::<u64>);
+    }
 }
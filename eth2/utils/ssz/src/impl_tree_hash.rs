@@ -1,54 +1,94 @@
 use super::ethereum_types::{Address, H256};
-use super::{hash, merkle_hash, ssz_encode, TreeHash};
+use super::{merkleize, mix_in_length, mix_in_type, vector_root, TreeHash, TreeHashType};
 
-impl TreeHash for u8 {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        ssz_encode(self)
-    }
+macro_rules! impl_tree_hash_for_uint {
+    ($type: ident) => {
+        impl TreeHash for $type {
+            fn tree_hash_type() -> TreeHashType {
+                TreeHashType::Basic
+            }
+
+            fn tree_hash_packed_encoding(&self) -> Vec<u8> {
+                self.to_le_bytes().to_vec()
+            }
+
+            fn hash_tree_root(&self) -> Vec<u8> {
+                merkleize(&self.tree_hash_packed_encoding())
+            }
+        }
+    };
 }
 
-impl TreeHash for u16 {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        ssz_encode(self)
+impl_tree_hash_for_uint!(u8);
+impl_tree_hash_for_uint!(u16);
+impl_tree_hash_for_uint!(u32);
+impl_tree_hash_for_uint!(u64);
+
+impl TreeHash for bool {
+    fn tree_hash_type() -> TreeHashType {
+        TreeHashType::Basic
     }
-}
 
-impl TreeHash for u32 {
-    fn hash_tree_root(&self) -> Vec<u8> {
-        ssz_encode(self)
+    fn tree_hash_packed_encoding(&self) -> Vec<u8> {
+        vec![*self as u8]
     }
-}
 
-impl TreeHash for u64 {
     fn hash_tree_root(&self) -> Vec<u8> {
-        ssz_encode(self)
+        merkleize(&self.tree_hash_packed_encoding())
     }
 }
 
+/// A `[u8; N]` is a fixed-length vector of `N` bytes.
+macro_rules! impl_tree_hash_for_u8_array {
+    ($len: expr) => {
+        impl TreeHash for [u8; $len] {
+            fn hash_tree_root(&self) -> Vec<u8> {
+                merkleize(&self[..])
+            }
+        }
+    };
+}
+
+impl_tree_hash_for_u8_array!(4);
+impl_tree_hash_for_u8_array!(8);
+impl_tree_hash_for_u8_array!(20);
+impl_tree_hash_for_u8_array!(32);
+impl_tree_hash_for_u8_array!(48);
+impl_tree_hash_for_u8_array!(96);
+
+/// A `usize` is hashed as a `u64`, the same as its serialization.
 impl TreeHash for usize {
+    fn tree_hash_type() -> TreeHashType {
+        TreeHashType::Basic
+    }
+
+    fn tree_hash_packed_encoding(&self) -> Vec<u8> {
+        (*self as u64).tree_hash_packed_encoding()
+    }
+
     fn hash_tree_root(&self) -> Vec<u8> {
-        ssz_encode(self)
+        (*self as u64).hash_tree_root()
     }
 }
 
+/// An `Address` is a fixed-length vector of 20 bytes.
 impl TreeHash for Address {
     fn hash_tree_root(&self) -> Vec<u8> {
-        ssz_encode(self)
+        merkleize(&self[..])
     }
 }
 
+/// A `H256` is a fixed-length vector of 32 bytes.
 impl TreeHash for H256 {
     fn hash_tree_root(&self) -> Vec<u8> {
-        ssz_encode(self)
+        merkleize(&self[..])
     }
 }
 
+/// A byte slice is a variable-length list of bytes.
 impl TreeHash for [u8] {
     fn hash_tree_root(&self) -> Vec<u8> {
-        if self.len() > 32 {
-            return hash(&self);
-        }
-        self.to_vec()
+        mix_in_length(&merkleize(self), self.len())
     }
 }
 
@@ -56,18 +96,28 @@ impl<T> TreeHash for Vec<T>
 where
     T: TreeHash,
 {
-    /// Returns the merkle_hash of a list of hash_tree_root values created
-    /// from the given list.
-    /// Note: A byte vector, Vec<u8>, must be converted to a slice (as_slice())
-    ///       to be handled properly (i.e. hashed) as byte array.
+    /// Returns the root of the elements of the list, mixed in with its length.
     fn hash_tree_root(&self) -> Vec<u8> {
-        let mut tree_hashes = self.iter().map(|x| x.hash_tree_root()).collect();
-        merkle_hash(&mut tree_hashes)
+        mix_in_length(&vector_root(self), self.len())
+    }
+}
+
+impl<T> TreeHash for Option<T>
+where
+    T: TreeHash,
+{
+    /// Returns the root of the union of "null" and `T`, where "null" has a root of zero.
+    fn hash_tree_root(&self) -> Vec<u8> {
+        match self {
+            None => mix_in_type(&merkleize(&[]), 0),
+            Some(value) => mix_in_type(&value.hash_tree_root(), 1),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::super::hash;
     use super::*;
 
     #[test]
@@ -75,4 +125,38 @@ mod tests {
         let result = vec![1u32, 2, 3, 4, 5, 6, 7].hash_tree_root();
         assert_eq!(result.len(), 32);
     }
+
+    #[test]
+    fn test_basic_types_are_packed() {
+        let mut expected = vec![1, 0, 2, 0, 3, 0];
+        expected.resize(32, 0);
+        let mut length = vec![3];
+        length.resize(32, 0);
+        expected.append(&mut length);
+
+        assert_eq!(vec![1u16, 2, 3].hash_tree_root(), hash(&expected));
+        assert_eq!(vec![1u8, 2].hash_tree_root(), [1u8, 2][..].hash_tree_root());
+
+        let mut expected = vec![1, 0, 1];
+        expected.resize(32, 0);
+        assert_eq!(vector_root(&[true, false, true]), expected);
+    }
+
+    #[test]
+    fn test_u8_array_is_a_vector() {
+        assert_eq!([42u8; 32].hash_tree_root(), vec![42; 32]);
+        assert_eq!([42u8; 48].hash_tree_root(), merkleize(&[42; 48]));
+    }
+
+    #[test]
+    fn test_option_is_a_union() {
+        let mut expected = vec![0; 32];
+        expected.append(&mut vec![0; 32]);
+        assert_eq!(None::<u64>.hash_tree_root(), hash(&expected));
+
+        let mut expected = 7u64.hash_tree_root();
+        expected.append(&mut vec![1]);
+        expected.resize(64, 0);
+        assert_eq!(Some(7u64).hash_tree_root(), hash(&expected));
+    }
 }
@@ -10,6 +10,10 @@
 extern crate bytes;
 extern crate ethereum_types;
 
+use std::io;
+use std::mem;
+
+pub mod cached_tree_hash;
 pub mod decode;
 pub mod encode;
 pub mod tree_hash;
@@ -18,19 +22,89 @@ mod impl_decode;
 mod impl_encode;
 mod impl_tree_hash;
 
-pub use crate::decode::{decode_ssz, decode_ssz_list, Decodable, DecodeError};
-pub use crate::encode::{Encodable, SszStream};
-pub use crate::tree_hash::{hash, merkle_hash, TreeHash};
+pub use crate::cached_tree_hash::{MerkleCache, TreeHashListCache};
+pub use crate::decode::{
+    decode_ssz, decode_ssz_exact, decode_ssz_fixed_vec, decode_ssz_list, decode_ssz_list_with_max,
+    Decodable, DecodeError,
+};
+pub use crate::encode::{vec_size_hint, Encodable, SszStream};
+pub use crate::tree_hash::{
+    hash, merkleize, mix_in_length, mix_in_type, vector_root, TreeHash, TreeHashType,
+    BYTES_PER_CHUNK,
+};
 
 pub const LENGTH_BYTES: usize = 4;
 pub const MAX_LIST_SIZE: usize = 1 << (4 * 8);
 
 /// Convenience function to SSZ encode an object supporting ssz::Encode.
+///
+/// The output is written to a single buffer, pre-sized with `Encodable::ssz_size_hint`.
 pub fn ssz_encode<T>(val: &T) -> Vec<u8>
 where
     T: Encodable,
 {
-    let mut ssz_stream = SszStream::new();
+    let mut ssz_stream = SszStream::with_capacity(val.ssz_size_hint());
     ssz_stream.append(val);
     ssz_stream.drain()
 }
+
+/// SSZ encode an object supporting ssz::Encode onto the end of `buffer`, such as a re-used
+/// `Vec<u8>`.
+///
+/// The object is encoded directly into `buffer`, which is first grown by
+/// `Encodable::ssz_size_hint` if required.
+pub fn ssz_encode_into<T>(val: &T, buffer: &mut Vec<u8>)
+where
+    T: Encodable,
+{
+    buffer.reserve(val.ssz_size_hint());
+    let mut ssz_stream = SszStream::from_buffer(mem::replace(buffer, vec![]));
+    ssz_stream.append(val);
+    *buffer = ssz_stream.drain();
+}
+
+/// SSZ encode an object supporting ssz::Encode into some `writer`, such as a file.
+///
+/// The length prefix of a list is only known once its items have been encoded, so the object is
+/// first encoded into a pre-sized buffer and the writer receives a single `write_all` call. Use
+/// `ssz_encode_into` to encode into a `Vec<u8>` without the intermediate buffer.
+pub fn ssz_encode_to_writer<T, W>(val: &T, writer: &mut W) -> io::Result<()>
+where
+    T: Encodable,
+    W: io::Write,
+{
+    writer.write_all(&ssz_encode(val))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ssz_encode_to_writer() {
+        let value: Vec<u16> = vec![1, 2];
+
+        let mut buffer = vec![42];
+        ssz_encode_to_writer(&value, &mut buffer).unwrap();
+        ssz_encode_to_writer(&value, &mut buffer).unwrap();
+
+        let mut expected = vec![42];
+        expected.append(&mut ssz_encode(&value));
+        expected.append(&mut ssz_encode(&value));
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn test_ssz_encode_into() {
+        let value: Vec<u16> = vec![1, 2];
+
+        let mut buffer = vec![42];
+        ssz_encode_into(&value, &mut buffer);
+        ssz_encode_into(&value, &mut buffer);
+
+        let mut expected = vec![42];
+        expected.append(&mut ssz_encode(&value));
+        expected.append(&mut ssz_encode(&value));
+        assert_eq!(buffer, expected);
+    }
+}
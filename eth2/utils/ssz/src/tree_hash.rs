@@ -1,67 +1,86 @@
 use hashing::canonical_hash;
 
-const SSZ_CHUNK_SIZE: usize = 128;
+pub const BYTES_PER_CHUNK: usize = 32;
 const HASHSIZE: usize = 32;
 
+/// Whether a type is a "basic" type, which is packed into chunks when it is an element of a
+/// vector or list, or a "composite" type, which is represented by its root.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TreeHashType {
+    Basic,
+    Composite,
+}
+
 pub trait TreeHash {
+    fn tree_hash_type() -> TreeHashType
+    where
+        Self: Sized,
+    {
+        TreeHashType::Composite
+    }
+
+    /// Returns the little-endian serialization of a basic type, which is packed alongside other
+    /// elements of a vector or list.
+    ///
+    /// Only basic types are packed, so composite types need not implement this.
+    fn tree_hash_packed_encoding(&self) -> Vec<u8> {
+        unreachable!("Composite types are not packed")
+    }
+
     fn hash_tree_root(&self) -> Vec<u8>;
 }
 
-/// Returns a 32 byte hash of 'list' - a vector of byte vectors.
-/// Note that this will consume 'list'.
-pub fn merkle_hash(list: &mut Vec<Vec<u8>>) -> Vec<u8> {
-    // flatten list
-    let (mut chunk_size, mut chunkz) = list_to_blob(list);
-
-    // get data_len as bytes. It will hashed will the merkle root
-    let datalen = list.len().to_le_bytes();
-
-    // Tree-hash
-    while chunkz.len() > HASHSIZE {
-        let mut new_chunkz: Vec<u8> = Vec::new();
-
-        for two_chunks in chunkz.chunks(chunk_size * 2) {
-            if two_chunks.len() == chunk_size {
-                // Odd number of chunks
-                let mut c = two_chunks.to_vec();
-                c.append(&mut vec![0; SSZ_CHUNK_SIZE]);
-                new_chunkz.append(&mut hash(&c));
-            } else {
-                // Hash two chuncks together
-                new_chunkz.append(&mut hash(two_chunks));
-            }
-            chunk_size = HASHSIZE;
-        }
-        chunkz = new_chunkz;
+/// Returns the merkle root of `bytes`, split into 32 byte chunks.
+///
+/// The final chunk is padded with zeros and the number of chunks is padded with zero chunks to
+/// the next power of two. A single chunk is its own root.
+pub fn merkleize(bytes: &[u8]) -> Vec<u8> {
+    let num_chunks = (bytes.len() + BYTES_PER_CHUNK - 1) / BYTES_PER_CHUNK;
+    let num_leaves = num_chunks.max(1).next_power_of_two();
+
+    let mut chunks = bytes.to_vec();
+    chunks.resize(num_leaves * BYTES_PER_CHUNK, 0);
+
+    while chunks.len() > HASHSIZE {
+        chunks = chunks.chunks(HASHSIZE * 2).flat_map(hash).collect();
     }
 
-    chunkz.append(&mut datalen.to_vec());
-    hash(&chunkz)
+    chunks
+}
+
+/// Returns the root of a list: `root` hashed together with the number of elements in the list,
+/// as a 32 byte little-endian integer.
+pub fn mix_in_length(root: &[u8], length: usize) -> Vec<u8> {
+    let mut length_bytes = (length as u64).to_le_bytes().to_vec();
+    length_bytes.resize(BYTES_PER_CHUNK, 0);
+
+    let mut data = root.to_vec();
+    data.append(&mut length_bytes);
+    hash(&data)
 }
 
-fn list_to_blob(list: &mut Vec<Vec<u8>>) -> (usize, Vec<u8>) {
-    let chunk_size = if list.is_empty() {
-        SSZ_CHUNK_SIZE
-    } else if list[0].len() < SSZ_CHUNK_SIZE {
-        let items_per_chunk = SSZ_CHUNK_SIZE / list[0].len();
-        items_per_chunk * list[0].len()
-    } else {
-        list[0].len()
+/// Returns the root of a union: the `root` of its value hashed together with the index of the
+/// value's type, as a 32 byte little-endian integer.
+pub fn mix_in_type(root: &[u8], type_index: usize) -> Vec<u8> {
+    mix_in_length(root, type_index)
+}
+
+/// Returns the root of a fixed-length vector of `values`.
+///
+/// Basic values are packed into chunks, whilst composite values contribute their roots.
+pub fn vector_root<T: TreeHash>(values: &[T]) -> Vec<u8> {
+    let leaves: Vec<u8> = match T::tree_hash_type() {
+        TreeHashType::Basic => values
+            .iter()
+            .flat_map(|value| value.tree_hash_packed_encoding())
+            .collect(),
+        TreeHashType::Composite => values
+            .iter()
+            .flat_map(|value| value.hash_tree_root())
+            .collect(),
     };
 
-    let mut data = Vec::new();
-    if list.is_empty() {
-        // handle and empty list
-        data.append(&mut vec![0; SSZ_CHUNK_SIZE]);
-    } else {
-        // just create a blob here; we'll divide into
-        // chunked slices when we merklize
-        data.reserve(list[0].len() * list.len());
-        for item in list.iter_mut() {
-            data.append(item);
-        }
-    }
-    (chunk_size, data)
+    merkleize(&leaves)
 }
 
 pub fn hash(data: &[u8]) -> Vec<u8> {
@@ -73,14 +92,28 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_merkle_hash() {
-        let data1 = vec![1; 100];
-        let data2 = vec![2; 100];
-        let data3 = vec![3; 100];
-        let mut list = vec![data1, data2, data3];
-        let result = merkle_hash(&mut list);
-
-        //note: should test againt a known test hash value
-        assert_eq!(HASHSIZE, result.len());
+    fn test_merkleize() {
+        // A single chunk is padded but not hashed.
+        assert_eq!(merkleize(&[]), vec![0; 32]);
+        let mut expected = vec![1, 2, 3];
+        expected.resize(32, 0);
+        assert_eq!(merkleize(&[1, 2, 3]), expected);
+
+        // Three chunks are padded to four.
+        let data = vec![1; 65];
+        let mut chunks = data.clone();
+        chunks.resize(128, 0);
+        let mut left = hash(&chunks[0..64]);
+        left.append(&mut hash(&chunks[64..128]));
+        assert_eq!(merkleize(&data), hash(&left));
+    }
+
+    #[test]
+    fn test_mix_in_length() {
+        let root = vec![7; 32];
+        let mut expected = root.clone();
+        expected.append(&mut vec![3]);
+        expected.resize(64, 0);
+        assert_eq!(mix_in_length(&root, 3), hash(&expected));
     }
 }
@@ -0,0 +1,321 @@
+//! Checks `hash_tree_root` against the regression snapshots in `tests/vectors/tree_hash.yaml`,
+//! and against a minimal reference implementation which computes roots directly from the YAML
+//! values.
+//!
+//! The spec test vectors hash with sha256, whilst this implementation uses keccak256, so they
+//! cannot be used here; see the header of the YAML file. The reference implementation shares no
+//! code with `ssz::tree_hash` beyond the hash function, so the two are only expected to agree if
+//! both follow the spec.
+use ethereum_types::{Address, H256};
+use hashing::canonical_hash;
+use serde_derive::Deserialize;
+use serde_yaml::{Number, Value};
+use ssz::{vector_root, TreeHash};
+use ssz_derive::TreeHash;
+use std::fs::File;
+use std::path::PathBuf;
+
+#[derive(Debug, Deserialize)]
+struct TestDoc {
+    test_cases: Vec<TestCase>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TestCase {
+    #[serde(rename = "type")]
+    type_name: String,
+    value: Value,
+    root: String,
+}
+
+#[derive(TreeHash)]
+struct SmallContainer {
+    a: u16,
+    b: u16,
+}
+
+#[derive(TreeHash)]
+struct VarContainer {
+    a: u16,
+    b: Vec<u16>,
+    c: u8,
+}
+
+#[derive(TreeHash)]
+struct ComplexContainer {
+    a: u16,
+    b: Vec<u16>,
+    c: u8,
+    d: Vec<u8>,
+    e: VarContainer,
+    f: Vec<SmallContainer>,
+}
+
+/// Builds a value of a test type from its YAML representation.
+trait FromYaml {
+    fn from_yaml(value: &Value) -> Self;
+}
+
+fn uint(value: &Value) -> u64 {
+    value.as_u64().expect("Expected an integer")
+}
+
+fn bytes(value: &Value) -> Vec<u8> {
+    hex_bytes(value.as_str().expect("Expected a hex string"))
+}
+
+fn hex_bytes(string: &str) -> Vec<u8> {
+    hex::decode(string.trim_start_matches("0x")).expect("Invalid hex string")
+}
+
+impl FromYaml for u16 {
+    fn from_yaml(value: &Value) -> Self {
+        uint(value) as u16
+    }
+}
+
+impl FromYaml for u64 {
+    fn from_yaml(value: &Value) -> Self {
+        uint(value)
+    }
+}
+
+impl FromYaml for H256 {
+    fn from_yaml(value: &Value) -> Self {
+        H256::from(&bytes(value)[..])
+    }
+}
+
+impl<T: FromYaml> FromYaml for Vec<T> {
+    fn from_yaml(value: &Value) -> Self {
+        value
+            .as_sequence()
+            .expect("Expected a sequence")
+            .iter()
+            .map(T::from_yaml)
+            .collect()
+    }
+}
+
+impl FromYaml for SmallContainer {
+    fn from_yaml(value: &Value) -> Self {
+        Self {
+            a: <_>::from_yaml(&value["a"]),
+            b: <_>::from_yaml(&value["b"]),
+        }
+    }
+}
+
+impl FromYaml for VarContainer {
+    fn from_yaml(value: &Value) -> Self {
+        Self {
+            a: <_>::from_yaml(&value["a"]),
+            b: <_>::from_yaml(&value["b"]),
+            c: uint(&value["c"]) as u8,
+        }
+    }
+}
+
+impl FromYaml for ComplexContainer {
+    fn from_yaml(value: &Value) -> Self {
+        Self {
+            a: <_>::from_yaml(&value["a"]),
+            b: <_>::from_yaml(&value["b"]),
+            c: uint(&value["c"]) as u8,
+            d: bytes(&value["d"]),
+            e: <_>::from_yaml(&value["e"]),
+            f: <_>::from_yaml(&value["f"]),
+        }
+    }
+}
+
+fn hash_tree_root(type_name: &str, value: &Value) -> Vec<u8> {
+    match type_name {
+        "uint8" => (uint(value) as u8).hash_tree_root(),
+        "uint16" => u16::from_yaml(value).hash_tree_root(),
+        "uint32" => (uint(value) as u32).hash_tree_root(),
+        "uint64" => u64::from_yaml(value).hash_tree_root(),
+        "bytes20" => Address::from(&bytes(value)[..]).hash_tree_root(),
+        "bytes32" => H256::from_yaml(value).hash_tree_root(),
+        "bytes48" | "bytes96" => vector_root(&bytes(value)),
+        "bytes" => bytes(value).hash_tree_root(),
+        "vector(uint16)" => vector_root(&Vec::<u16>::from_yaml(value)),
+        "vector(uint64)" => vector_root(&Vec::<u64>::from_yaml(value)),
+        "vector(bytes32)" => vector_root(&Vec::<H256>::from_yaml(value)),
+        "list(uint16)" => Vec::<u16>::from_yaml(value).hash_tree_root(),
+        "list(uint64)" => Vec::<u64>::from_yaml(value).hash_tree_root(),
+        "list(bytes32)" => Vec::<H256>::from_yaml(value).hash_tree_root(),
+        "SmallContainer" => SmallContainer::from_yaml(value).hash_tree_root(),
+        "VarContainer" => VarContainer::from_yaml(value).hash_tree_root(),
+        "ComplexContainer" => ComplexContainer::from_yaml(value).hash_tree_root(),
+        other => panic!("Unknown type in test vectors: {}", other),
+    }
+}
+
+/// A minimal `hash_tree_root`, written from the simple-serialize spec independently of
+/// `ssz::tree_hash`.
+mod reference {
+    use super::*;
+
+    type Chunk = [u8; 32];
+
+    fn chunk(bytes: &[u8]) -> Chunk {
+        let mut chunk = [0; 32];
+        chunk[..bytes.len()].copy_from_slice(bytes);
+        chunk
+    }
+
+    fn hash_pair(left: &Chunk, right: &Chunk) -> Chunk {
+        chunk(&canonical_hash(&[&left[..], &right[..]].concat()))
+    }
+
+    /// Returns the root of a subtree of `width` leaves (a power of two), the first of which are
+    /// `chunks` and the rest of which are zero.
+    fn subtree_root(chunks: &[Chunk], width: usize) -> Chunk {
+        if width == 1 {
+            return chunks.first().cloned().unwrap_or([0; 32]);
+        }
+        let half = width / 2;
+        let split = half.min(chunks.len());
+        hash_pair(
+            &subtree_root(&chunks[..split], half),
+            &subtree_root(&chunks[split..], half),
+        )
+    }
+
+    fn merkleize(bytes: &[u8]) -> Chunk {
+        let chunks: Vec<Chunk> = bytes.chunks(32).map(chunk).collect();
+        let mut width = 1;
+        while width < chunks.len() {
+            width *= 2;
+        }
+        subtree_root(&chunks, width)
+    }
+
+    fn mix_in_length(root: Chunk, length: usize) -> Chunk {
+        hash_pair(&root, &chunk(&(length as u64).to_le_bytes()))
+    }
+
+    /// Returns the byte length of a `uintN` type, or `None` if `type_name` is not a basic type.
+    fn basic_size(type_name: &str) -> Option<usize> {
+        match type_name {
+            "uint8" => Some(1),
+            "uint16" => Some(2),
+            "uint32" => Some(4),
+            "uint64" => Some(8),
+            _ => None,
+        }
+    }
+
+    fn fields(type_name: &str) -> &'static [(&'static str, &'static str)] {
+        match type_name {
+            "SmallContainer" => &[("a", "uint16"), ("b", "uint16")],
+            "VarContainer" => &[("a", "uint16"), ("b", "list(uint16)"), ("c", "uint8")],
+            "ComplexContainer" => &[
+                ("a", "uint16"),
+                ("b", "list(uint16)"),
+                ("c", "uint8"),
+                ("d", "bytes"),
+                ("e", "VarContainer"),
+                ("f", "list(SmallContainer)"),
+            ],
+            other => panic!("Unknown type in test vectors: {}", other),
+        }
+    }
+
+    /// Returns the leaves of a vector or list of `element_type`: packed basic values, or the
+    /// roots of composite values.
+    fn leaves(element_type: &str, value: &Value) -> (Vec<u8>, usize) {
+        let elements = value.as_sequence().expect("Expected a sequence");
+        let leaves = elements
+            .iter()
+            .flat_map(|element| match basic_size(element_type) {
+                Some(size) => uint(element).to_le_bytes()[..size].to_vec(),
+                None => root(element_type, element).to_vec(),
+            })
+            .collect();
+        (leaves, elements.len())
+    }
+
+    pub fn root(type_name: &str, value: &Value) -> Chunk {
+        if let Some(size) = basic_size(type_name) {
+            merkleize(&uint(value).to_le_bytes()[..size])
+        } else if type_name == "bytes" {
+            let bytes = bytes(value);
+            mix_in_length(merkleize(&bytes), bytes.len())
+        } else if type_name.starts_with("bytes") {
+            merkleize(&bytes(value))
+        } else if type_name.starts_with("vector(") {
+            let element_type = &type_name["vector(".len()..type_name.len() - 1];
+            merkleize(&leaves(element_type, value).0)
+        } else if type_name.starts_with("list(") {
+            let element_type = &type_name["list(".len()..type_name.len() - 1];
+            let (leaves, length) = leaves(element_type, value);
+            mix_in_length(merkleize(&leaves), length)
+        } else {
+            let field_roots: Vec<u8> = fields(type_name)
+                .iter()
+                .flat_map(|(name, field_type)| root(field_type, &value[*name]).to_vec())
+                .collect();
+            merkleize(&field_roots)
+        }
+    }
+}
+
+#[test]
+fn tree_hash_vectors() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/vectors/tree_hash.yaml");
+    let file = File::open(&path).expect("Unable to open test vectors");
+    let doc: TestDoc = serde_yaml::from_reader(file).expect("Unable to parse test vectors");
+
+    assert!(!doc.test_cases.is_empty());
+    for case in &doc.test_cases {
+        let root = hash_tree_root(&case.type_name, &case.value);
+        assert_eq!(
+            root,
+            hex_bytes(&case.root),
+            "{}: {:?}",
+            case.type_name,
+            case.value
+        );
+        assert_eq!(
+            root,
+            reference::root(&case.type_name, &case.value).to_vec(),
+            "{}: {:?}",
+            case.type_name,
+            case.value
+        );
+    }
+}
+
+#[test]
+fn tree_hash_matches_reference() {
+    // Lists of every length up to several levels of the tree, to cover the padding of each.
+    for length in 0..=70 {
+        let values: Vec<u64> = (0..length).map(|i| i * 0x0101_0101_0101).collect();
+        let value = Value::Sequence(
+            values
+                .iter()
+                .map(|v| Value::Number(Number::from(*v)))
+                .collect(),
+        );
+
+        for type_name in &["list(uint16)", "list(uint64)", "vector(uint64)"] {
+            assert_eq!(
+                hash_tree_root(type_name, &value),
+                reference::root(type_name, &value).to_vec(),
+                "{} of length {}",
+                type_name,
+                length
+            );
+        }
+
+        let bytes = Value::String(format!("0x{}", hex::encode(vec![0xab; length as usize])));
+        assert_eq!(
+            hash_tree_root("bytes", &bytes),
+            reference::root("bytes", &bytes).to_vec(),
+            "bytes of length {}",
+            length
+        );
+    }
+}
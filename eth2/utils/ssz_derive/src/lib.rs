@@ -0,0 +1,365 @@
+//! Provides `#[derive(Encode, Decode, TreeHash)]` for structs with named fields, generating
+//! implementations of `ssz::Encodable`, `ssz::Decodable` and `ssz::TreeHash` which process each
+//! field in the order in which it is declared.
+//!
+//! `#[derive(CachedTreeHash)]` additionally generates a `<Name>TreeHashCache` struct and a
+//! `cached_hash_tree_root` method, which returns the same root as `ssz::TreeHash` whilst holding
+//! the merkle trees of selected fields in the cache.
+//!
+//! The behaviour for individual fields may be altered with the following attributes:
+//!
+//! - `#[ssz(skip_serializing)]`: the field is not encoded, and so is set to `Default::default()`
+//!   when decoding.
+//! - `#[ssz(skip_deserializing)]`: the field is encoded, but its bytes are discarded when
+//!   decoding and it is set to `Default::default()`.
+//! - `#[ssz(with = "module")]`: the field is encoded with `module::ssz_append(&field, stream)`
+//!   and decoded with `module::ssz_decode(bytes, index)`.
+//! - `#[ssz(max_length = "expr")]`: the field, which must be a `Vec`, fails to decode if it has
+//!   more than `expr` elements.
+//! - `#[ssz(fixed_length = "expr")]`: the field, which must be a `Vec` of `expr` elements, is a
+//!   fixed-length vector. It is encoded without a length prefix, decoded as exactly `expr`
+//!   elements and hashed without its length mixed in.
+//! - `#[tree_hash(skip_hashing)]`: the field is not included in the tree hash.
+//! - `#[tree_hash(with = "module")]`: the field is hashed with `module::hash_tree_root(&field)`.
+//! - `#[tree_hash(cached)]`: the field, which must be a `Vec`, is hashed with an
+//!   `ssz::TreeHashListCache` by `cached_hash_tree_root`. It is ignored by `TreeHash`.
+//!
+//! The generated `ssz_size_hint` is the sum of the hints of each encoded field, except those
+//! encoded `with` a module, which are not counted.
+//!
+//! Errors decoding a field are wrapped with the name of the field and the byte at which it begins.
+//!
+//! The generated code refers to the `ssz` crate by name, so it must be a dependency of any crate
+//! using these derives.
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput};
+
+/// The options set on a single field by its `ssz` or `tree_hash` attributes.
+#[derive(Default)]
+struct FieldOpts {
+    skip: bool,
+    skip_deserializing: bool,
+    with: Option<syn::Path>,
+    max_length: Option<syn::Expr>,
+    fixed_length: Option<syn::Expr>,
+    cached: bool,
+}
+
+/// Returns the named fields of `item`, panicking if it is not a struct with named fields.
+fn named_fields<'a>(item: &'a DeriveInput, derive: &str) -> Vec<&'a syn::Field> {
+    match &item.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => fields.named.iter().collect(),
+        _ => panic!(
+            "{} may only be derived for structs with named fields",
+            derive
+        ),
+    }
+}
+
+/// Parses the `#[<attr_name>(...)]` attributes of `field`, where `skip_word` is the flag which
+/// excludes the field entirely.
+fn field_opts(field: &syn::Field, attr_name: &str, skip_word: &str) -> FieldOpts {
+    let mut opts = FieldOpts::default();
+
+    for attr in &field.attrs {
+        let list = match attr.parse_meta() {
+            Ok(syn::Meta::List(ref list)) if list.ident == attr_name => list.clone(),
+            _ => continue,
+        };
+
+        for nested in list.nested.iter() {
+            match nested {
+                syn::NestedMeta::Meta(syn::Meta::Word(word)) if word == skip_word => {
+                    opts.skip = true
+                }
+                syn::NestedMeta::Meta(syn::Meta::Word(word))
+                    if attr_name == "ssz" && word == "skip_deserializing" =>
+                {
+                    opts.skip_deserializing = true
+                }
+                syn::NestedMeta::Meta(syn::Meta::Word(word))
+                    if attr_name == "tree_hash" && word == "cached" =>
+                {
+                    opts.cached = true
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    ident,
+                    lit: syn::Lit::Str(lit),
+                    ..
+                })) if ident == "with" => {
+                    let path = syn::parse_str(&lit.value())
+                        .unwrap_or_else(|_| panic!("Invalid path in {}(with)", attr_name));
+                    opts.with = Some(path);
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    ident,
+                    lit: syn::Lit::Str(lit),
+                    ..
+                })) if attr_name == "ssz" && ident == "max_length" => {
+                    let expr = syn::parse_str(&lit.value())
+                        .unwrap_or_else(|_| panic!("Invalid expression in ssz(max_length)"));
+                    opts.max_length = Some(expr);
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    ident,
+                    lit: syn::Lit::Str(lit),
+                    ..
+                })) if attr_name == "ssz" && ident == "fixed_length" => {
+                    let expr = syn::parse_str(&lit.value())
+                        .unwrap_or_else(|_| panic!("Invalid expression in ssz(fixed_length)"));
+                    opts.fixed_length = Some(expr);
+                }
+                _ => panic!("Unknown {} attribute on field", attr_name),
+            }
+        }
+    }
+
+    opts
+}
+
+/// Implements `ssz::Encodable` for a struct.
+#[proc_macro_derive(Encode, attributes(ssz))]
+pub fn ssz_encode_derive(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as DeriveInput);
+
+    let name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    let mut appends = vec![];
+    let mut size_hints = vec![];
+
+    for field in named_fields(&item, "Encode") {
+        let ident = &field.ident;
+        let opts = field_opts(field, "ssz", "skip_serializing");
+
+        if opts.skip {
+            continue;
+        }
+
+        match (opts.with, opts.fixed_length) {
+            (Some(_), Some(_)) => panic!("ssz(with) and ssz(fixed_length) are mutually exclusive"),
+            // The size of a field encoded by a module is unknown, so it adds nothing to the hint.
+            (Some(with), None) => appends.push(quote! { #with::ssz_append(&self.#ident, s); }),
+            (None, Some(_)) => {
+                appends.push(quote! { s.append_fixed_vec(&self.#ident); });
+                size_hints.push(quote! { ssz::vec_size_hint(&self.#ident) });
+            }
+            (None, None) => {
+                appends.push(quote! { s.append(&self.#ident); });
+                size_hints.push(quote! { ssz::Encodable::ssz_size_hint(&self.#ident) });
+            }
+        }
+    }
+
+    let output = quote! {
+        impl #impl_generics ssz::Encodable for #name #ty_generics #where_clause {
+            fn ssz_append(&self, s: &mut ssz::SszStream) {
+                #(#appends)*
+            }
+
+            fn ssz_size_hint(&self) -> usize {
+                0 #(+ #size_hints)*
+            }
+        }
+    };
+    output.into()
+}
+
+/// Implements `ssz::Decodable` for a struct.
+#[proc_macro_derive(Decode, attributes(ssz))]
+pub fn ssz_decode_derive(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as DeriveInput);
+
+    let name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    let fields = named_fields(&item, "Decode");
+    let idents: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+
+    let decodes = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let ty = &field.ty;
+        let opts = field_opts(field, "ssz", "skip_serializing");
+
+        if opts.skip {
+            return quote! { let #ident = <#ty as Default>::default(); };
+        }
+
+        let decode = match (opts.with, opts.max_length, opts.fixed_length) {
+            (Some(with), None, None) => quote! { #with::ssz_decode(bytes, i) },
+            (None, Some(max_length), None) => {
+                quote! { ssz::decode_ssz_list_with_max(bytes, i, #max_length) }
+            }
+            (None, None, Some(fixed_length)) => {
+                quote! { ssz::decode_ssz_fixed_vec(bytes, i, #fixed_length) }
+            }
+            (None, None, None) => quote! { <#ty as ssz::Decodable>::ssz_decode(bytes, i) },
+            _ => panic!("ssz(with), ssz(max_length) and ssz(fixed_length) are mutually exclusive"),
+        };
+        let name = ident
+            .as_ref()
+            .expect("Named fields have idents")
+            .to_string();
+
+        if opts.skip_deserializing {
+            // The field is still encoded, so its bytes are decoded in order to be skipped.
+            quote! {
+                let (_, i) = #decode.map_err(|e| e.in_field(#name, i))?;
+                let #ident = <#ty as Default>::default();
+            }
+        } else {
+            quote! {
+                let (#ident, i) = #decode.map_err(|e| e.in_field(#name, i))?;
+            }
+        }
+    });
+
+    let output = quote! {
+        impl #impl_generics ssz::Decodable for #name #ty_generics #where_clause {
+            fn ssz_decode(bytes: &[u8], i: usize) -> Result<(Self, usize), ssz::DecodeError> {
+                #(#decodes)*
+
+                Ok((
+                    Self {
+                        #(#idents),*
+                    },
+                    i
+                ))
+            }
+        }
+    };
+    output.into()
+}
+
+/// Returns the statement appending the root of `field` to `result`, or `None` if the field is not
+/// hashed.
+///
+/// If `use_cache` is set, fields with the `tree_hash(cached)` attribute are hashed using the
+/// matching field of `cache`.
+fn field_root(field: &syn::Field, use_cache: bool) -> Option<proc_macro2::TokenStream> {
+    let ident = &field.ident;
+    let opts = field_opts(field, "tree_hash", "skip_hashing");
+    let fixed_length = field_opts(field, "ssz", "skip_serializing").fixed_length;
+
+    match (opts.skip, opts.with, fixed_length) {
+        (true, _, _) => None,
+        (false, Some(_), _) if opts.cached => {
+            panic!("tree_hash(with) and tree_hash(cached) are mutually exclusive")
+        }
+        (false, Some(with), _) => {
+            Some(quote! { result.append(&mut #with::hash_tree_root(&self.#ident)); })
+        }
+        (false, None, Some(_)) if use_cache && opts.cached => Some(quote! {
+            result.append(&mut cache.#ident.vector_root(&self.#ident));
+        }),
+        (false, None, Some(_)) => Some(quote! {
+            result.append(&mut ssz::vector_root(&self.#ident));
+        }),
+        (false, None, None) if use_cache && opts.cached => Some(quote! {
+            result.append(&mut cache.#ident.hash_tree_root(&self.#ident));
+        }),
+        (false, None, None) => Some(quote! {
+            result.append(&mut ssz::TreeHash::hash_tree_root(&self.#ident));
+        }),
+    }
+}
+
+/// Returns `T`, given the type `Vec<T>`.
+fn vec_element_type(ty: &syn::Type) -> &syn::Type {
+    if let syn::Type::Path(syn::TypePath { path, .. }) = ty {
+        if let Some(segment) = path.segments.iter().last() {
+            if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                if segment.ident == "Vec" {
+                    if let Some(syn::GenericArgument::Type(element)) = args.args.iter().next() {
+                        return element;
+                    }
+                }
+            }
+        }
+    }
+    panic!("tree_hash(cached) may only be set on a Vec")
+}
+
+/// Implements `ssz::TreeHash` for a struct, as the merkle root of the roots of its fields.
+///
+/// The `ssz(fixed_length)` attribute is also read, so that fixed-length vectors are hashed as
+/// such.
+#[proc_macro_derive(TreeHash, attributes(tree_hash, ssz))]
+pub fn ssz_tree_hash_derive(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as DeriveInput);
+
+    let name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    let roots = named_fields(&item, "TreeHash")
+        .into_iter()
+        .filter_map(|field| field_root(field, false));
+
+    let output = quote! {
+        impl #impl_generics ssz::TreeHash for #name #ty_generics #where_clause {
+            fn hash_tree_root(&self) -> Vec<u8> {
+                let mut result: Vec<u8> = vec![];
+                #(#roots)*
+                ssz::merkleize(&result)
+            }
+        }
+    };
+    output.into()
+}
+
+/// Generates a `<Name>TreeHashCache` struct, holding an `ssz::TreeHashListCache` for each field
+/// with the `tree_hash(cached)` attribute, along with a `cached_hash_tree_root` method returning
+/// the same root as the `TreeHash` derive.
+#[proc_macro_derive(CachedTreeHash, attributes(tree_hash, ssz))]
+pub fn ssz_cached_tree_hash_derive(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as DeriveInput);
+
+    if !item.generics.params.is_empty() {
+        panic!("CachedTreeHash may not be derived for generic structs");
+    }
+
+    let name = &item.ident;
+    let vis = &item.vis;
+    let cache_name = syn::Ident::new(&format!("{}TreeHashCache", name), name.span());
+    let cache_doc = format!(
+        "Holds the merkle trees of the cached fields of a `{}`, such that the root of a subsequent \
+         value may be found by rehashing only the parts of those fields which have changed.",
+        name
+    );
+
+    let fields = named_fields(&item, "CachedTreeHash");
+    let cache_fields = fields
+        .iter()
+        .filter(|field| field_opts(field, "tree_hash", "skip_hashing").cached)
+        .map(|field| {
+            let ident = &field.ident;
+            let element = vec_element_type(&field.ty);
+            quote! { #ident: ssz::TreeHashListCache<#element> }
+        });
+    let roots = fields.iter().filter_map(|field| field_root(field, true));
+
+    let output = quote! {
+        #[doc = #cache_doc]
+        #[derive(Debug, Clone, Default)]
+        #vis struct #cache_name {
+            #(#cache_fields),*
+        }
+
+        impl #name {
+            /// Returns the same root as `ssz::TreeHash::hash_tree_root`, using and updating the
+            /// intermediate roots held in `cache`.
+            pub fn cached_hash_tree_root(&self, cache: &mut #cache_name) -> Vec<u8> {
+                let mut result: Vec<u8> = vec![];
+                #(#roots)*
+                ssz::merkleize(&result)
+            }
+        }
+    };
+    output.into()
+}
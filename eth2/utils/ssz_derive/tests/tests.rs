@@ -0,0 +1,267 @@
+use ssz::{ssz_encode, Decodable, DecodeError, Encodable, SszStream, TreeHash};
+use ssz_derive::{CachedTreeHash, Decode, Encode, TreeHash};
+
+#[derive(Debug, PartialEq, Encode, Decode, TreeHash)]
+struct Plain {
+    a: u16,
+    b: Vec<u8>,
+    c: u64,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, TreeHash)]
+struct Attributed {
+    a: u16,
+    #[ssz(skip_serializing)]
+    #[tree_hash(skip_hashing)]
+    skipped: u32,
+    #[ssz(with = "as_byte")]
+    #[tree_hash(with = "as_byte")]
+    flag: bool,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Discarded {
+    a: u16,
+    #[ssz(skip_deserializing)]
+    discarded: Vec<u8>,
+    b: u16,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Limited {
+    a: u16,
+    #[ssz(max_length = "2")]
+    list: Vec<u16>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, TreeHash)]
+struct Fixed {
+    #[ssz(fixed_length = "2")]
+    vector: Vec<u16>,
+    a: u16,
+}
+
+#[derive(Debug, PartialEq, TreeHash, CachedTreeHash)]
+struct Cached {
+    a: u16,
+    #[tree_hash(cached)]
+    list: Vec<u64>,
+    #[ssz(fixed_length = "2")]
+    #[tree_hash(cached)]
+    vector: Vec<u16>,
+    #[tree_hash(skip_hashing)]
+    skipped: u32,
+}
+
+mod as_byte {
+    use super::*;
+
+    pub fn ssz_append(flag: &bool, s: &mut SszStream) {
+        s.append(&(*flag as u8));
+    }
+
+    pub fn ssz_decode(bytes: &[u8], i: usize) -> Result<(bool, usize), DecodeError> {
+        match u8::ssz_decode(bytes, i)? {
+            (0, i) => Ok((false, i)),
+            (1, i) => Ok((true, i)),
+            _ => Err(DecodeError::Invalid),
+        }
+    }
+
+    pub fn hash_tree_root(flag: &bool) -> Vec<u8> {
+        (*flag as u8).hash_tree_root()
+    }
+}
+
+#[test]
+fn fields_are_processed_in_order() {
+    let original = Plain {
+        a: 1,
+        b: vec![2, 3],
+        c: 4,
+    };
+
+    let mut s = SszStream::new();
+    s.append(&original.a);
+    s.append(&original.b);
+    s.append(&original.c);
+    let expected = s.drain();
+
+    let bytes = ssz_encode(&original);
+    assert_eq!(bytes, expected);
+
+    let (decoded, i) = Plain::ssz_decode(&bytes, 0).unwrap();
+    assert_eq!(decoded, original);
+    assert_eq!(i, bytes.len());
+
+    let mut roots = vec![];
+    roots.append(&mut original.a.hash_tree_root());
+    roots.append(&mut original.b.hash_tree_root());
+    roots.append(&mut original.c.hash_tree_root());
+    assert_eq!(original.hash_tree_root(), ssz::merkleize(&roots));
+}
+
+#[test]
+fn field_attributes() {
+    let original = Attributed {
+        a: 1,
+        skipped: 42,
+        flag: true,
+    };
+
+    let bytes = ssz_encode(&original);
+    assert_eq!(bytes, vec![0, 1, 1]);
+
+    let (decoded, _) = Attributed::ssz_decode(&bytes, 0).unwrap();
+    assert_eq!(
+        decoded,
+        Attributed {
+            skipped: 0,
+            ..original
+        }
+    );
+    assert_eq!(
+        Attributed::ssz_decode(&[0, 1, 2], 0),
+        Err(DecodeError::InField {
+            path: "flag".to_string(),
+            offset: 2,
+            error: Box::new(DecodeError::Invalid),
+        })
+    );
+
+    let other = Attributed {
+        skipped: 7,
+        ..original
+    };
+    assert_eq!(original.hash_tree_root(), other.hash_tree_root());
+}
+
+#[test]
+fn skip_deserializing() {
+    let original = Discarded {
+        a: 1,
+        discarded: vec![2, 3],
+        b: 4,
+    };
+
+    let bytes = ssz_encode(&original);
+    assert_eq!(bytes, vec![0, 1, 0, 0, 0, 2, 2, 3, 0, 4]);
+
+    let (decoded, i) = Discarded::ssz_decode(&bytes, 0).unwrap();
+    assert_eq!(
+        decoded,
+        Discarded {
+            discarded: vec![],
+            ..original
+        }
+    );
+    assert_eq!(i, bytes.len());
+
+    assert_eq!(
+        Discarded::ssz_decode(&bytes[..6], 0),
+        Err(DecodeError::InField {
+            path: "discarded".to_string(),
+            offset: 2,
+            error: Box::new(DecodeError::TooShort),
+        })
+    );
+}
+
+#[test]
+fn max_length() {
+    let original = Limited {
+        a: 1,
+        list: vec![2, 3],
+    };
+    let bytes = ssz_encode(&original);
+    let (decoded, _) = Limited::ssz_decode(&bytes, 0).unwrap();
+    assert_eq!(decoded, original);
+
+    let bytes = ssz_encode(&Limited {
+        a: 1,
+        list: vec![2, 3, 4],
+    });
+    assert_eq!(
+        Limited::ssz_decode(&bytes, 0),
+        Err(DecodeError::InField {
+            path: "list".to_string(),
+            offset: 2,
+            error: Box::new(DecodeError::TooManyElements { max_length: 2 }),
+        })
+    );
+}
+
+#[test]
+fn fixed_length() {
+    let original = Fixed {
+        vector: vec![2, 3],
+        a: 1,
+    };
+    let bytes = ssz_encode(&original);
+    assert_eq!(bytes, vec![0, 2, 0, 3, 0, 1]);
+
+    let (decoded, i) = Fixed::ssz_decode(&bytes, 0).unwrap();
+    assert_eq!(decoded, original);
+    assert_eq!(i, bytes.len());
+
+    let mut roots = ssz::vector_root(&original.vector);
+    roots.append(&mut original.a.hash_tree_root());
+    assert_eq!(original.hash_tree_root(), ssz::merkleize(&roots));
+
+    assert_eq!(
+        Fixed::ssz_decode(&bytes[..4], 0),
+        Err(DecodeError::InField {
+            path: "a".to_string(),
+            offset: 4,
+            error: Box::new(DecodeError::TooShort),
+        })
+    );
+}
+
+#[test]
+fn size_hint() {
+    let plain = Plain {
+        a: 1,
+        b: vec![2, 3],
+        c: 4,
+    };
+    assert_eq!(plain.ssz_size_hint(), ssz_encode(&plain).len());
+
+    let fixed = Fixed {
+        vector: vec![2, 3],
+        a: 1,
+    };
+    assert_eq!(fixed.ssz_size_hint(), ssz_encode(&fixed).len());
+
+    // Skipped fields and fields encoded by a module are not counted.
+    let attributed = Attributed {
+        a: 1,
+        skipped: 42,
+        flag: true,
+    };
+    assert_eq!(attributed.ssz_size_hint(), 2);
+}
+
+#[test]
+fn cached_tree_hash() {
+    let mut original = Cached {
+        a: 1,
+        list: vec![2, 3],
+        vector: vec![4, 5],
+        skipped: 6,
+    };
+    let mut cache = CachedTreeHashCache::default();
+    assert_eq!(
+        original.cached_hash_tree_root(&mut cache),
+        original.hash_tree_root()
+    );
+
+    original.a = 7;
+    original.list.push(8);
+    original.vector[1] = 9;
+    original.skipped = 10;
+    assert_eq!(
+        original.cached_hash_tree_root(&mut cache),
+        original.hash_tree_root()
+    );
+}
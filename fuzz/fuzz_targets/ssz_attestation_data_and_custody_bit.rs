@@ -0,0 +1,7 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+
+fuzz_target!(|data: &[u8]| {
+    lighthouse_fuzz::run_target("ssz_attestation_data_and_custody_bit", data);
+});
@@ -0,0 +1,18 @@
+//! Writes the seed corpus of each target to `fuzz/corpus/<target>/`, where `cargo fuzz` reads it.
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("../corpus");
+
+    for target in lighthouse_fuzz::targets() {
+        let dir = corpus.join(target);
+        fs::create_dir_all(&dir).unwrap();
+
+        let seeds = lighthouse_fuzz::seed_corpus(target).unwrap();
+        for (i, seed) in seeds.iter().enumerate() {
+            fs::write(dir.join(format!("test_random_{}", i)), seed).unwrap();
+        }
+        println!("Wrote {} seeds for {}", seeds.len(), target);
+    }
+}
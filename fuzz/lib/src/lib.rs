@@ -0,0 +1,205 @@
+//! Fuzz targets for SSZ decoding and block processing.
+//!
+//! This crate is a member of the root workspace; the `cargo-fuzz` binaries in the parent
+//! directory only call `run_target`. Run a target with `cargo fuzz run <target>` from `fuzz/`.
+//! The available targets are listed by `cargo fuzz list`, and are:
+//!
+//! - `ssz_<type>`: decodes the input as a `types` struct, checking that the decoded value
+//!   encodes back to the input.
+//! - `bitfield_from_bytes`: builds a `BooleanBitfield` from the input.
+//! - `process_block`: decodes the input as a `BeaconBlock` and processes it on top of a small
+//!   genesis chain.
+//!
+//! Each target only calls `run_target`, so any input which panics may be reproduced without the
+//! fuzzer. Save the input as `fuzz/lib/regressions/<target>/<name>` and it will be run by
+//! `cargo test`.
+//!
+//! A seed corpus of `TestRandom` values is written to `fuzz/corpus/<target>/` by
+//! `cargo run -p lighthouse_fuzz --example seed_corpus`.
+use beacon_node::beacon_chain::BeaconChain;
+use boolean_bitfield::BooleanBitfield;
+use db::stores::{BeaconBlockStore, BeaconStateStore};
+use db::MemoryDB;
+use slot_clock::TestingSlotClock;
+use spec::ChainSpec;
+use ssz::{decode_ssz_exact, ssz_encode, Decodable, Encodable};
+use std::sync::Arc;
+use types::shard_reassignment_record::ShardReassignmentRecord;
+use types::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+use types::{
+    Attestation, AttestationData, AttestationDataAndCustodyBit, BeaconBlock, BeaconBlockBody,
+    BeaconState, CasperSlashing, Crosslink, Deposit, DepositData, DepositInput, Eth1Data,
+    Eth1DataVote, Exit, Fork, FreeAttestation, PendingAttestation, ProposalSignedData,
+    ProposerSlashing, ShardCommittee, SlashableVoteData, SpecialRecord, Validator,
+    ValidatorRegistryDeltaBlock,
+};
+
+/// The number of seeds generated for each target.
+const SEEDS_PER_TARGET: usize = 4;
+
+macro_rules! ssz_targets {
+    ($($target: tt => $type: ty,)*) => {
+        /// The names of the targets which decode each `types` struct.
+        pub const SSZ_TARGETS: &[&str] = &[$($target),*];
+
+        fn run_ssz_target(target: &str, data: &[u8]) -> bool {
+            match target {
+                $($target => ssz_round_trip::<$type>(data),)*
+                _ => return false,
+            }
+            true
+        }
+
+        fn ssz_seeds(target: &str, rng: &mut XorShiftRng) -> Option<Vec<Vec<u8>>> {
+            match target {
+                $($target => Some(random_encodings::<$type>(rng)),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+ssz_targets! {
+    "ssz_attestation" => Attestation,
+    "ssz_attestation_data" => AttestationData,
+    "ssz_attestation_data_and_custody_bit" => AttestationDataAndCustodyBit,
+    "ssz_beacon_block" => BeaconBlock,
+    "ssz_beacon_block_body" => BeaconBlockBody,
+    "ssz_beacon_state" => BeaconState,
+    "ssz_casper_slashing" => CasperSlashing,
+    "ssz_crosslink" => Crosslink,
+    "ssz_deposit" => Deposit,
+    "ssz_deposit_data" => DepositData,
+    "ssz_deposit_input" => DepositInput,
+    "ssz_eth1_data" => Eth1Data,
+    "ssz_eth1_data_vote" => Eth1DataVote,
+    "ssz_exit" => Exit,
+    "ssz_fork" => Fork,
+    "ssz_free_attestation" => FreeAttestation,
+    "ssz_pending_attestation" => PendingAttestation,
+    "ssz_proposal_signed_data" => ProposalSignedData,
+    "ssz_proposer_slashing" => ProposerSlashing,
+    "ssz_shard_committee" => ShardCommittee,
+    "ssz_shard_reassignment_record" => ShardReassignmentRecord,
+    "ssz_slashable_vote_data" => SlashableVoteData,
+    "ssz_validator" => Validator,
+    "ssz_validator_registry_delta_block" => ValidatorRegistryDeltaBlock,
+}
+
+/// Runs the target named `target` on `data`, panicking if the target fails.
+///
+/// Returns `false` if there is no such target.
+pub fn run_target(target: &str, data: &[u8]) -> bool {
+    match target {
+        "ssz_special_record" => ssz_round_trip::<SpecialRecord>(data),
+        "bitfield_from_bytes" => bitfield_from_bytes(data),
+        "process_block" => process_block(data),
+        _ => return run_ssz_target(target, data),
+    }
+    true
+}
+
+/// Returns the names of all targets.
+pub fn targets() -> Vec<&'static str> {
+    let mut targets = SSZ_TARGETS.to_vec();
+    targets.extend_from_slice(&["ssz_special_record", "bitfield_from_bytes", "process_block"]);
+    targets
+}
+
+/// Returns inputs for `target` built from `TestRandom` values, or `None` if there is no such
+/// target.
+pub fn seed_corpus(target: &str) -> Option<Vec<Vec<u8>>> {
+    let mut rng = XorShiftRng::from_seed([42; 16]);
+
+    match target {
+        // `SpecialRecord` is not `TestRandom`.
+        "ssz_special_record" => Some(vec![
+            ssz_encode(&SpecialRecord::logout(&[])),
+            ssz_encode(&SpecialRecord::casper_slashing(&[1, 2, 3])),
+            ssz_encode(&SpecialRecord::randao_change(&[42; 32])),
+        ]),
+        "bitfield_from_bytes" => Some(
+            random_values::<BooleanBitfield>(&mut rng)
+                .iter()
+                .map(BooleanBitfield::to_bytes)
+                .collect(),
+        ),
+        "process_block" => Some(random_encodings::<BeaconBlock>(&mut rng)),
+        _ => ssz_seeds(target, &mut rng),
+    }
+}
+
+/// Decodes `data` as a `T`, checking that any value which decodes is encoded as `data`.
+pub fn ssz_round_trip<T>(data: &[u8])
+where
+    T: Decodable + Encodable,
+{
+    if let Ok(value) = decode_ssz_exact::<T>(data) {
+        assert_eq!(
+            ssz_encode(&value),
+            data,
+            "Decoded value encodes differently"
+        );
+    }
+}
+
+/// Builds a `BooleanBitfield` from `data`, checking that it represents the bits of `data`.
+pub fn bitfield_from_bytes(data: &[u8]) {
+    let field = BooleanBitfield::from_bytes(data);
+
+    assert_eq!(field.len(), data.len() * 8);
+    assert_eq!(field.to_bytes(), data);
+
+    let set_bits: usize = data.iter().map(|byte| byte.count_ones() as usize).sum();
+    assert_eq!(field.num_set_bits(), set_bits);
+    assert_eq!(field.highest_set_bit().is_some(), set_bits > 0);
+}
+
+/// Decodes `data` as a `BeaconBlock` which extends the genesis block, and processes it.
+///
+/// The slot clock is set to the slot of the block, so that it is not rejected as a future block.
+/// Errors are expected; only panics are failures.
+pub fn process_block(data: &[u8]) {
+    let mut block: BeaconBlock = match decode_ssz_exact(data) {
+        Ok(block) => block,
+        Err(_) => return,
+    };
+
+    let mut chain = genesis_chain();
+    block.parent_root = chain.canonical_leaf_block;
+    chain.slot_clock.set_slot(block.slot);
+
+    let _ = chain.process_block(&block);
+}
+
+/// Returns a chain with the validators of the foundation spec, all active at genesis.
+fn genesis_chain() -> BeaconChain<MemoryDB, TestingSlotClock> {
+    let mut spec = ChainSpec::foundation();
+    for validator in &mut spec.initial_validators {
+        validator.activation_slot = spec.genesis_slot;
+    }
+
+    let db = Arc::new(MemoryDB::open());
+    let block_store = Arc::new(BeaconBlockStore::new(db.clone()));
+    let state_store = Arc::new(BeaconStateStore::new(db.clone()));
+    let slot_clock = TestingSlotClock::new(spec.genesis_slot);
+
+    BeaconChain::genesis(state_store, block_store, slot_clock, spec)
+        .expect("The foundation spec has a valid genesis")
+}
+
+fn random_values<T>(rng: &mut XorShiftRng) -> Vec<T>
+where
+    T: TestRandom<XorShiftRng>,
+{
+    (0..SEEDS_PER_TARGET)
+        .map(|_| T::random_for_test(rng))
+        .collect()
+}
+
+fn random_encodings<T>(rng: &mut XorShiftRng) -> Vec<Vec<u8>>
+where
+    T: TestRandom<XorShiftRng> + Encodable,
+{
+    random_values::<T>(rng).iter().map(ssz_encode).collect()
+}
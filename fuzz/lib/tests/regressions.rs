@@ -0,0 +1,42 @@
+//! Runs each input saved in `regressions/<target>/` through its target, such that inputs found by
+//! the fuzzer remain fixed.
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn regressions() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("regressions");
+    let mut cases = 0;
+
+    for target_dir in fs::read_dir(dir).unwrap() {
+        let target_dir = target_dir.unwrap().path();
+        let target = target_dir
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+
+        for input in fs::read_dir(&target_dir).unwrap() {
+            let input = input.unwrap().path();
+            println!("Running {} on {:?}", target, input);
+            let data = fs::read(&input).unwrap();
+            assert!(
+                lighthouse_fuzz::run_target(&target, &data),
+                "Unknown target {}",
+                target
+            );
+            cases += 1;
+        }
+    }
+
+    assert!(cases > 0, "No regression cases were found");
+}
+
+#[test]
+fn seed_corpus() {
+    for target in lighthouse_fuzz::targets() {
+        for data in lighthouse_fuzz::seed_corpus(target).unwrap() {
+            lighthouse_fuzz::run_target(target, &data);
+        }
+    }
+}
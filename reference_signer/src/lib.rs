@@ -0,0 +1,192 @@
+use bls::{PublicKey, SecretKey, Signature};
+use futures::Future;
+use grpcio::{
+    Environment, RpcContext, RpcStatus, RpcStatusCode, Server, ServerBuilder,
+    ServerCredentialsBuilder, UnarySink,
+};
+use protos::services::{Empty, PublicKeysResponse, SignRequest, SignResponse};
+use protos::services_grpc::{create_signer_service, SignerService};
+use slog::{debug, error, info, warn, Logger};
+use ssz::ssz_encode;
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Signs any message with any of its keys, upon request.
+///
+/// This is a reference implementation for testing; it applies no policy (e.g., slashing
+/// protection) of its own.
+#[derive(Clone)]
+pub struct SignerServiceInstance {
+    /// Secret keys, keyed by the SSZ encoding of their public key.
+    keys: Arc<HashMap<Vec<u8>, SecretKey>>,
+    log: Logger,
+}
+
+impl SignerServiceInstance {
+    pub fn new(secret_keys: Vec<SecretKey>, log: Logger) -> Self {
+        let keys = secret_keys
+            .into_iter()
+            .map(|sk| (ssz_encode(&PublicKey::from_secret_key(&sk)), sk))
+            .collect();
+        Self {
+            keys: Arc::new(keys),
+            log,
+        }
+    }
+}
+
+impl SignerService for SignerServiceInstance {
+    fn list_public_keys(
+        &mut self,
+        ctx: RpcContext,
+        _req: Empty,
+        sink: UnarySink<PublicKeysResponse>,
+    ) {
+        debug!(self.log, "RPC request"; "endpoint" => "ListPublicKeys");
+
+        let mut resp = PublicKeysResponse::new();
+        resp.set_public_keys(self.keys.keys().cloned().collect());
+
+        let log = self.log.clone();
+        let f = sink
+            .success(resp)
+            .map_err(move |e| error!(log, "Failed to reply"; "error" => format!("{:?}", e)));
+        ctx.spawn(f)
+    }
+
+    fn sign(&mut self, ctx: RpcContext, req: SignRequest, sink: UnarySink<SignResponse>) {
+        debug!(self.log, "RPC request"; "endpoint" => "Sign", "slot" => req.get_slot(), "domain" => req.get_domain());
+
+        let log = self.log.clone();
+        let f = match self.keys.get(req.get_public_key()) {
+            Some(sk) => {
+                let signature =
+                    Signature::new_with_domain(req.get_message(), req.get_domain(), sk);
+                let mut resp = SignResponse::new();
+                resp.set_signature(ssz_encode(&signature));
+                info!(self.log, "Signed message"; "slot" => req.get_slot(), "domain" => req.get_domain());
+                sink.success(resp)
+            }
+            None => {
+                warn!(self.log, "Refused to sign for unknown key"; "public_key" => hex::encode(req.get_public_key()));
+                sink.fail(RpcStatus::new(
+                    RpcStatusCode::NotFound,
+                    Some("Unknown public key".to_string()),
+                ))
+            }
+        }
+        .map_err(move |e| error!(log, "Failed to reply"; "error" => format!("{:?}", e)));
+        ctx.spawn(f)
+    }
+}
+
+/// Start a gRPC server which signs with the given keys.
+///
+/// If `tls` holds the paths of a PEM server certificate and private key, the server is served
+/// over TLS. If `port == 0` a free port is chosen; see `Server::bind_addrs`.
+pub fn start_server(
+    secret_keys: Vec<SecretKey>,
+    listen_address: &str,
+    port: u16,
+    tls: Option<(&Path, &Path)>,
+    log: Logger,
+) -> Result<Server, String> {
+    let env = Arc::new(Environment::new(1));
+    let service = create_signer_service(SignerServiceInstance::new(secret_keys, log));
+
+    let builder = ServerBuilder::new(env).register_service(service);
+    let builder = match tls {
+        None => builder.bind(listen_address, port),
+        Some((cert, key)) => {
+            let credentials = ServerCredentialsBuilder::new()
+                .add_cert(read_pem(cert)?, read_pem(key)?)
+                .build();
+            builder.bind_secure(listen_address, port, credentials)
+        }
+    };
+
+    let mut server = builder
+        .build()
+        .map_err(|e| format!("Unable to bind {}:{}: {:?}", listen_address, port, e))?;
+    server.start();
+    Ok(server)
+}
+
+/// Read a PEM-encoded file into memory.
+fn read_pem(path: &Path) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|e| format!("Unable to read {:?}: {}", path, e))
+}
+
+/// Read secret keys from `path`, which contains the hex encoding of one secret key per line.
+pub fn read_keys(path: &Path) -> Result<Vec<SecretKey>, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| format!("Unable to read {:?}: {}", path, e))?;
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let bytes = hex::decode(line).map_err(|_| format!("Invalid hex: {}", line))?;
+            SecretKey::from_bytes(&bytes).map_err(|_| "Invalid secret key".to_string())
+        })
+        .collect()
+}
+
+/// Write `secret_keys` to `path`, in the format read by `read_keys`.
+///
+/// A new file is only readable and writable by its owner.
+pub fn write_keys(path: &Path, secret_keys: &[SecretKey]) -> Result<(), String> {
+    let contents: String = secret_keys
+        .iter()
+        .map(|sk| format!("{}\n", hex::encode(sk.as_raw().as_bytes())))
+        .collect();
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .map_err(|e| format!("Unable to write {:?}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+    use std::{env, process};
+
+    #[test]
+    pub fn key_file_round_trip() {
+        let path = env::temp_dir().join(format!("reference_signer_keys_{}", process::id()));
+        let keys = vec![SecretKey::random(), SecretKey::random()];
+
+        let _ = fs::remove_file(&path);
+        write_keys(&path, &keys).unwrap();
+        assert_eq!(read_keys(&path), Ok(keys));
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        fs::write(&path, "not hex\n").unwrap();
+        assert!(read_keys(&path).is_err());
+    }
+
+    #[test]
+    pub fn missing_tls_files_are_reported() {
+        let log = Logger::root(slog::Discard, slog::o!());
+        let missing = env::temp_dir().join(format!("reference_signer_missing_{}", process::id()));
+
+        let result = start_server(
+            vec![],
+            "127.0.0.1",
+            0,
+            Some((missing.as_path(), missing.as_path())),
+            log,
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,107 @@
+use bls::{PublicKey, SecretKey};
+use clap::{App, Arg};
+use reference_signer::{read_keys, start_server, write_keys};
+use slog::{error, info, o, Drain};
+use std::path::Path;
+
+fn main() {
+    let decorator = slog_term::TermDecorator::new().build();
+    let drain = slog_term::CompactFormat::new(decorator).build().fuse();
+    let drain = slog_async::Async::new(drain).build().fuse();
+    let log = slog::Logger::root(drain, o!());
+
+    let matches = App::new("Lighthouse Reference Signer")
+        .version("0.0.1")
+        .author("Sigma Prime <contact@sigmaprime.io>")
+        .about("Signs messages for a validator client. For testing only; applies no slashing protection.")
+        .arg(
+            Arg::with_name("keys")
+                .long("keys")
+                .value_name("FILE")
+                .help("File of hex-encoded secret keys, one per line. Created with a new key if absent.")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("listen-address")
+                .long("listen-address")
+                .value_name("ADDRESS")
+                .help("Address to listen on.")
+                .default_value("127.0.0.1")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("port")
+                .long("port")
+                .value_name("PORT")
+                .help("Port to listen on.")
+                .default_value("50053")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("tls-cert")
+                .long("tls-cert")
+                .value_name("FILE")
+                .help("PEM certificate for serving gRPC over TLS.")
+                .requires("tls-key")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("tls-key")
+                .long("tls-key")
+                .value_name("FILE")
+                .help("PEM private key for serving gRPC over TLS.")
+                .requires("tls-cert")
+                .takes_value(true),
+        )
+        .get_matches();
+
+    let path = Path::new(matches.value_of("keys").expect("keys is required"));
+    if !path.exists() {
+        if let Err(e) = write_keys(path, &[SecretKey::random()]) {
+            error!(log, "Unable to generate key"; "error" => e);
+            return;
+        }
+        info!(log, "Generated new key"; "path" => format!("{:?}", path));
+    }
+
+    let secret_keys = match read_keys(path) {
+        Ok(keys) => keys,
+        Err(e) => {
+            error!(log, "Unable to read keys"; "error" => e);
+            return;
+        }
+    };
+    for sk in &secret_keys {
+        info!(log, "Loaded key"; "public_key" => PublicKey::from_secret_key(sk).as_hex_string());
+    }
+
+    let listen_address = matches
+        .value_of("listen-address")
+        .expect("listen-address has a default");
+    let port = match matches.value_of("port").map(str::parse::<u16>) {
+        Some(Ok(port)) => port,
+        _ => {
+            error!(log, "Invalid port");
+            return;
+        }
+    };
+
+    let tls = match (matches.value_of("tls-cert"), matches.value_of("tls-key")) {
+        (Some(cert), Some(key)) => Some((Path::new(cert), Path::new(key))),
+        _ => None,
+    };
+
+    let _server = match start_server(secret_keys, listen_address, port, tls, log.clone()) {
+        Ok(server) => server,
+        Err(e) => {
+            error!(log, "Unable to start gRPC server"; "error" => e);
+            return;
+        }
+    };
+    info!(log, "Signer listening"; "address" => listen_address, "port" => port, "tls" => tls.is_some());
+
+    loop {
+        std::thread::sleep(std::time::Duration::from_secs(1));
+    }
+}
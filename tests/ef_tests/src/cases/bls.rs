@@ -0,0 +1,76 @@
+use super::{compare, hex_bytes, parse};
+use crate::error::Error;
+use bls::{AggregateSignature, PublicKey, SecretKey, Signature};
+use serde_derive::Deserialize;
+use serde_yaml::Value;
+
+/// The length of a secret key accepted by the bls crate.
+const SECRET_KEY_BYTES: usize = 48;
+
+#[derive(Debug, Deserialize)]
+struct PrivToPub {
+    input: String,
+    output: PublicKey,
+}
+
+#[derive(Debug, Deserialize)]
+struct SignMsgInput {
+    privkey: String,
+    message: String,
+    domain: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct SignMsg {
+    input: SignMsgInput,
+    output: Signature,
+}
+
+#[derive(Debug, Deserialize)]
+struct AggregateSigs {
+    input: Vec<Signature>,
+    output: AggregateSignature,
+}
+
+/// Parses a secret key, which the tests give as a 32 byte integer and is left-padded to the
+/// length accepted by the bls crate.
+fn secret_key(string: &str) -> Result<SecretKey, Error> {
+    let bytes = hex_bytes(string)?;
+    let mut padded = vec![0; SECRET_KEY_BYTES.saturating_sub(bytes.len())];
+    padded.extend_from_slice(&bytes);
+
+    SecretKey::from_bytes(&padded)
+        .map_err(|e| Error::FailedToParseTest(format!("invalid secret key: {:?}", e)))
+}
+
+pub fn priv_to_pub(case: &Value) -> Result<(), Error> {
+    let case: PrivToPub = parse(case)?;
+    let secret_key = secret_key(&case.input)?;
+
+    compare(
+        "public key",
+        &PublicKey::from_secret_key(&secret_key),
+        &case.output,
+    )
+}
+
+pub fn sign_msg(case: &Value) -> Result<(), Error> {
+    let case: SignMsg = parse(case)?;
+    let secret_key = secret_key(&case.input.privkey)?;
+    let message = hex_bytes(&case.input.message)?;
+
+    let signature = Signature::new_with_domain(&message, case.input.domain, &secret_key);
+
+    compare("signature", &signature, &case.output)
+}
+
+pub fn aggregate_sigs(case: &Value) -> Result<(), Error> {
+    let case: AggregateSigs = parse(case)?;
+
+    let mut aggregate = AggregateSignature::new();
+    for signature in &case.input {
+        aggregate.add(signature);
+    }
+
+    compare("aggregate signature", &aggregate, &case.output)
+}
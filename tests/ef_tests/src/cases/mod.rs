@@ -0,0 +1,86 @@
+//! The implementation of each runner and handler.
+use crate::error::Error;
+use serde::de::DeserializeOwned;
+use serde_yaml::Value;
+use std::fmt::Debug;
+
+mod bls;
+mod shuffling;
+mod ssz_static;
+
+/// Runs a single `case` of the given runner and handler, where `config` names the constants with
+/// which the case was generated, if known.
+///
+/// Cases of categories which are not implemented by this crate are skipped.
+pub fn run_case(
+    runner: &str,
+    handler: &str,
+    case: &Value,
+    config: Option<&str>,
+) -> Result<(), Error> {
+    match (runner, handler) {
+        ("ssz_static", _) => ssz_static::run(handler, case, config),
+        ("shuffling", _) => shuffling::run(case),
+        ("bls", "priv_to_pub") => bls::priv_to_pub(case),
+        ("bls", "sign_msg") => bls::sign_msg(case),
+        ("bls", "aggregate_sigs") => bls::aggregate_sigs(case),
+        ("bls", _) => Err(Error::Skipped(format!(
+            "bls/{} is not supported by the bls crate",
+            handler
+        ))),
+        // TODO: run these once the per-block and per-epoch state transitions are implemented.
+        ("operations", _) | ("epoch_processing", _) | ("sanity", "blocks") => {
+            Err(Error::Skipped(format!(
+                "{}/{} requires a state transition, which is not implemented",
+                runner, handler
+            )))
+        }
+        _ => Err(Error::Skipped(format!(
+            "unknown handler {}/{}",
+            runner, handler
+        ))),
+    }
+}
+
+/// Returns `true` if the cases of the given runner and handler are run, rather than skipped.
+pub fn is_implemented(runner: &str, handler: &str) -> bool {
+    match (runner, handler) {
+        ("ssz_static", _) | ("shuffling", _) => true,
+        ("bls", "priv_to_pub") | ("bls", "sign_msg") | ("bls", "aggregate_sigs") => true,
+        _ => false,
+    }
+}
+
+/// Parses `value` as a `T`.
+fn parse<T>(value: &Value) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    serde_yaml::from_value(value.clone()).map_err(|e| Error::FailedToParseTest(e.to_string()))
+}
+
+/// Decodes a `0x`-prefixed hex string.
+fn hex_bytes(string: &str) -> Result<Vec<u8>, Error> {
+    if !string.starts_with("0x") {
+        return Err(Error::FailedToParseTest(format!(
+            "hex string must start with 0x: {}",
+            string
+        )));
+    }
+    hex::decode(&string[2..]).map_err(|e| Error::FailedToParseTest(format!("invalid hex: {:?}", e)))
+}
+
+/// Returns an error describing both values if `result` differs from `expected`.
+fn compare<T>(name: &str, result: &T, expected: &T) -> Result<(), Error>
+where
+    T: PartialEq + Debug,
+{
+    if result == expected {
+        Ok(())
+    } else {
+        Err(Error::NotEqual(format!(
+            "{}: expected {:?}, got {:?}",
+            name, expected, result
+        )))
+    }
+}
@@ -0,0 +1,23 @@
+use super::{compare, hex_bytes, parse};
+use crate::error::Error;
+use serde_derive::Deserialize;
+use serde_yaml::Value;
+use vec_shuffle::shuffle;
+
+/// The expected order of the indices `0..count` after shuffling with `seed`.
+#[derive(Debug, Deserialize)]
+struct Shuffling {
+    seed: String,
+    count: usize,
+    shuffled: Vec<usize>,
+}
+
+pub fn run(case: &Value) -> Result<(), Error> {
+    let case: Shuffling = parse(case)?;
+    let seed = hex_bytes(&case.seed)?;
+
+    let shuffled = shuffle(&seed, (0..case.count).collect())
+        .map_err(|e| Error::NotEqual(format!("failed to shuffle: {:?}", e)))?;
+
+    compare("shuffled", &shuffled, &case.shuffled)
+}
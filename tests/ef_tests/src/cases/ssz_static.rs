@@ -0,0 +1,112 @@
+use super::{compare, hex_bytes, parse};
+use crate::error::Error;
+use serde::de::DeserializeOwned;
+use serde_derive::Deserialize;
+use serde_yaml::Value;
+use ssz::{decode_ssz_exact, ssz_encode, Decodable, Encodable, TreeHash};
+use std::fmt::Debug;
+use types::{
+    Attestation, AttestationData, AttestationDataAndCustodyBit, BeaconBlock, BeaconBlockBody,
+    BeaconState, CasperSlashing, Crosslink, Deposit, DepositData, DepositInput, Eth1Data,
+    Eth1DataVote, Exit, Fork, PendingAttestation, ProposalSignedData, ProposerSlashing,
+    SlashableVoteData, Validator,
+};
+
+/// The only config supported by types whose encoding depends on the config, as the lengths of
+/// their vectors are constants (see `types::ssz_limits`).
+const SUPPORTED_CONFIG: &str = "mainnet";
+
+/// A value, along with its expected serialization and tree hash root.
+///
+/// The type is named by `type_name` in YAML test files, or by the handler for case directories.
+/// Likewise, the root is given by `root` or by the `root` of `roots.yaml`.
+#[derive(Debug, Deserialize)]
+struct SszStatic {
+    type_name: Option<String>,
+    value: Value,
+    serialized: String,
+    root: Option<String>,
+    roots: Option<Roots>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Roots {
+    root: String,
+}
+
+impl SszStatic {
+    fn root(&self) -> Result<Vec<u8>, Error> {
+        match (&self.root, &self.roots) {
+            (Some(root), _) | (None, Some(Roots { root })) => hex_bytes(root),
+            (None, None) => Err(Error::FailedToParseTest("missing root".to_string())),
+        }
+    }
+}
+
+macro_rules! check_types {
+    ($type_name: expr, $case: expr, $($type: ident),*) => {
+        match $type_name {
+            $(stringify!($type) => check::<$type>($case),)*
+            _ => Err(Error::Skipped(format!("unknown type {}", $type_name))),
+        }
+    };
+}
+
+/// Checks that the value of `case` is serialized and hashed as expected, and that the
+/// serialization decodes to the value.
+pub fn run(handler: &str, case: &Value, config: Option<&str>) -> Result<(), Error> {
+    let case: SszStatic = parse(case)?;
+    let type_name = case.type_name.as_ref().map_or(handler, String::as_str);
+
+    match config {
+        Some(config) if type_name == "BeaconState" && config != SUPPORTED_CONFIG => {
+            return Err(Error::Skipped(format!(
+                "BeaconState is only supported with the {} config",
+                SUPPORTED_CONFIG
+            )));
+        }
+        _ => {}
+    }
+
+    check_types!(
+        type_name,
+        &case,
+        Attestation,
+        AttestationData,
+        AttestationDataAndCustodyBit,
+        BeaconBlock,
+        BeaconBlockBody,
+        BeaconState,
+        CasperSlashing,
+        Crosslink,
+        Deposit,
+        DepositData,
+        DepositInput,
+        Eth1Data,
+        Eth1DataVote,
+        Exit,
+        Fork,
+        PendingAttestation,
+        ProposalSignedData,
+        ProposerSlashing,
+        SlashableVoteData,
+        Validator
+    )
+}
+
+fn check<T>(case: &SszStatic) -> Result<(), Error>
+where
+    T: DeserializeOwned + Encodable + Decodable + TreeHash + PartialEq + Debug,
+{
+    let value: T = parse(&case.value)?;
+    let serialized = hex_bytes(&case.serialized)?;
+    let root = case.root()?;
+
+    compare("serialized", &ssz_encode(&value), &serialized)?;
+
+    let decoded: T = decode_ssz_exact(&serialized)
+        .map_err(|e| Error::NotEqual(format!("failed to decode: {}", e)))?;
+    compare("decoded", &decoded, &value)?;
+
+    compare("root", &value.hash_tree_root(), &root)
+}
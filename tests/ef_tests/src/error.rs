@@ -0,0 +1,31 @@
+use std::fmt;
+
+/// The reason a test case did not pass.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    /// The test file or case could not be read or parsed.
+    FailedToParseTest(String),
+    /// The case was run, but its result differed from the expected result.
+    NotEqual(String),
+    /// The case was not run (e.g., it tests behaviour which is not implemented).
+    Skipped(String),
+}
+
+impl Error {
+    pub fn is_skipped(&self) -> bool {
+        match self {
+            Error::Skipped(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::FailedToParseTest(e) => write!(f, "failed to parse test: {}", e),
+            Error::NotEqual(e) => write!(f, "not equal: {}", e),
+            Error::Skipped(e) => write!(f, "skipped: {}", e),
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! Runs the Ethereum Foundation consensus test vectors (`eth2.0-spec-tests`) against the `types`,
+//! `ssz`, `bls` and `vec_shuffle` crates.
+//!
+//! The tests are read from a local copy of the `tests` directory of the test suite, which is laid
+//! out as `<runner>/<handler>/...`. Within each handler directory, cases are read from:
+//!
+//! - YAML files holding a `test_cases` list, and
+//! - `case_*` directories, in which each `.yaml` file is read into the field named by its file
+//!   stem and each `.ssz` file is read as a `0x`-prefixed hex string.
+//!
+//! Each case is dispatched by its runner and handler (see `cases::run_case`), and the number of
+//! cases which passed, failed or were skipped is reported for each handler.
+//!
+//! The version of the test suite is pinned by `TESTS_TAG` in the `Makefile` of this crate, which
+//! fetches it into `SPEC_TESTS_DIR`.
+use serde_derive::Deserialize;
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub mod cases;
+mod error;
+mod results;
+
+pub use crate::error::Error;
+pub use crate::results::HandlerResults;
+
+/// The location of the `tests` directory of the test suite, relative to this crate.
+pub const SPEC_TESTS_DIR: &str = "eth2.0-spec-tests/tests";
+
+/// A YAML test file, holding many cases.
+#[derive(Debug, Deserialize)]
+struct TestDoc {
+    /// The name of the constants with which the cases were generated (e.g., `mainnet`).
+    config: Option<String>,
+    test_cases: Vec<Value>,
+}
+
+/// Runs every case in `tests_dir`, returning the results of each handler ordered by runner and
+/// then handler.
+///
+/// Cases which cannot be parsed are recorded as failures, whilst an error is only returned if a
+/// directory cannot be read.
+pub fn run_tests(tests_dir: &Path) -> Result<Vec<HandlerResults>, Error> {
+    let mut all_results = vec![];
+
+    for runner_dir in sorted_dir_entries(tests_dir)? {
+        if !runner_dir.is_dir() {
+            continue;
+        }
+        let runner = file_name(&runner_dir);
+
+        for handler_dir in sorted_dir_entries(&runner_dir)? {
+            if !handler_dir.is_dir() {
+                continue;
+            }
+            let handler = file_name(&handler_dir);
+
+            let mut results = HandlerResults::new(&runner, &handler);
+            run_dir(&handler_dir, &mut results)?;
+            all_results.push(results);
+        }
+    }
+
+    Ok(all_results)
+}
+
+/// Runs the cases of all test files and case directories within `dir`, recursively.
+fn run_dir(dir: &Path, results: &mut HandlerResults) -> Result<(), Error> {
+    for path in sorted_dir_entries(dir)? {
+        if path.is_dir() && file_name(&path).starts_with("case_") {
+            let result = load_case_dir(&path)
+                .and_then(|case| cases::run_case(&results.runner, &results.handler, &case, None));
+            results.record(&path.display().to_string(), result);
+        } else if path.is_dir() {
+            run_dir(&path, results)?;
+        } else if path.extension().map_or(false, |ext| ext == "yaml") {
+            run_test_file(&path, results);
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs each case of the YAML test file at `path`.
+///
+/// If the file cannot be parsed, a single failure is recorded.
+fn run_test_file(path: &Path, results: &mut HandlerResults) {
+    let doc: TestDoc = match fs::read_to_string(path)
+        .map_err(|e| Error::FailedToParseTest(e.to_string()))
+        .and_then(|yaml| {
+            serde_yaml::from_str(&yaml).map_err(|e| Error::FailedToParseTest(e.to_string()))
+        }) {
+        Ok(doc) => doc,
+        Err(e) => {
+            results.record(&path.display().to_string(), Err(e));
+            return;
+        }
+    };
+
+    for (i, case) in doc.test_cases.iter().enumerate() {
+        let result = cases::run_case(
+            &results.runner,
+            &results.handler,
+            case,
+            doc.config.as_ref().map(String::as_str),
+        );
+        results.record(&format!("{} case {}", path.display(), i), result);
+    }
+}
+
+/// Reads the files of a case directory into a single YAML mapping.
+fn load_case_dir(dir: &Path) -> Result<Value, Error> {
+    let mut case = Mapping::new();
+
+    for path in sorted_dir_entries(dir)? {
+        let stem = match path.file_stem() {
+            Some(stem) => Value::String(stem.to_string_lossy().into_owned()),
+            None => continue,
+        };
+        let value = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml") => {
+                let yaml = fs::read_to_string(&path)
+                    .map_err(|e| Error::FailedToParseTest(e.to_string()))?;
+                serde_yaml::from_str(&yaml).map_err(|e| Error::FailedToParseTest(e.to_string()))?
+            }
+            Some("ssz") => {
+                let bytes = fs::read(&path).map_err(|e| Error::FailedToParseTest(e.to_string()))?;
+                Value::String(format!("0x{}", hex::encode(bytes)))
+            }
+            _ => continue,
+        };
+        case.insert(stem, value);
+    }
+
+    Ok(Value::Mapping(case))
+}
+
+/// Returns the paths within `dir`, sorted such that results are reported in a stable order.
+fn sorted_dir_entries(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let entries = fs::read_dir(dir).map_err(|e| {
+        Error::FailedToParseTest(format!("unable to read {}: {}", dir.display(), e))
+    })?;
+
+    let mut paths = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| Error::FailedToParseTest(e.to_string()))?;
+    paths.sort();
+
+    Ok(paths)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
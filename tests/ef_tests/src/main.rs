@@ -0,0 +1,33 @@
+use ef_tests::{run_tests, SPEC_TESTS_DIR};
+use std::env;
+use std::path::PathBuf;
+use std::process;
+
+/// Runs the tests in the directory given as the first argument (or `SPEC_TESTS_DIR`), printing
+/// the results of each handler. Exits with a non-zero status if any case failed.
+fn main() {
+    let tests_dir = env::args().nth(1).map(PathBuf::from).unwrap_or_else(|| {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push(SPEC_TESTS_DIR);
+        path
+    });
+
+    let results = match run_tests(&tests_dir) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("Unable to run tests in {}: {}", tests_dir.display(), e);
+            process::exit(1);
+        }
+    };
+
+    for handler in &results {
+        println!("{}", handler);
+        for failure in &handler.failures {
+            println!("    {}", failure);
+        }
+    }
+
+    if results.iter().any(|handler| handler.failed > 0) {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,46 @@
+use crate::error::Error;
+use std::fmt;
+
+/// The outcomes of all cases run by a single handler (e.g., `ssz_static/core`).
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct HandlerResults {
+    pub runner: String,
+    pub handler: String,
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    /// A description of each failure, including the file and index of the case.
+    pub failures: Vec<String>,
+}
+
+impl HandlerResults {
+    pub fn new(runner: &str, handler: &str) -> Self {
+        Self {
+            runner: runner.to_string(),
+            handler: handler.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Records the result of the case described by `case`.
+    pub fn record(&mut self, case: &str, result: Result<(), Error>) {
+        match result {
+            Ok(()) => self.passed += 1,
+            Err(ref e) if e.is_skipped() => self.skipped += 1,
+            Err(e) => {
+                self.failed += 1;
+                self.failures.push(format!("{}: {}", case, e));
+            }
+        }
+    }
+}
+
+impl fmt::Display for HandlerResults {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}: {} passed, {} failed, {} skipped",
+            self.runner, self.handler, self.passed, self.failed, self.skipped
+        )
+    }
+}
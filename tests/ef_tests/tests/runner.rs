@@ -0,0 +1,62 @@
+//! Checks the runner itself (discovery, dispatch, counting and failure reporting) with
+//! hand-written cases in `tests/runner_fixtures`, some of which fail deliberately.
+//!
+//! These are not spec test vectors; see `tests/spec_tests.rs` for those.
+use ef_tests::{run_tests, HandlerResults};
+use std::path::PathBuf;
+
+fn fixture_results() -> Vec<HandlerResults> {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/runner_fixtures");
+
+    run_tests(&path).unwrap()
+}
+
+/// Returns the `(passed, failed, skipped)` counts of each handler.
+fn counts(results: &[HandlerResults]) -> Vec<(String, (usize, usize, usize))> {
+    results
+        .iter()
+        .map(|r| {
+            (
+                format!("{}/{}", r.runner, r.handler),
+                (r.passed, r.failed, r.skipped),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn handlers_are_dispatched() {
+    let results = fixture_results();
+
+    let expected: Vec<(String, (usize, usize, usize))> = vec![
+        ("bls/aggregate_pubkeys", (0, 0, 1)),
+        ("epoch_processing/crosslinks", (0, 0, 2)),
+        ("operations/deposit", (0, 0, 2)),
+        ("sanity/blocks", (0, 0, 2)),
+        ("sanity/slots", (0, 1, 0)),
+        ("shuffling/core", (2, 1, 0)),
+        ("ssz_static/Eth1Data", (1, 0, 0)),
+        ("ssz_static/core", (1, 1, 1)),
+    ]
+    .into_iter()
+    .map(|(handler, counts)| (handler.to_string(), counts))
+    .collect();
+
+    assert_eq!(counts(&results), expected);
+}
+
+#[test]
+fn failures_are_described() {
+    let results = fixture_results();
+
+    let ssz_static = results
+        .iter()
+        .find(|r| r.runner == "ssz_static" && r.handler == "core")
+        .unwrap();
+    assert_eq!(ssz_static.failures.len(), 1);
+    assert!(ssz_static.failures[0].contains("ssz_mainnet_random.yaml case 1: not equal"));
+
+    let broken = results.iter().find(|r| r.handler == "slots").unwrap();
+    assert!(broken.failures[0].contains("failed to parse test"));
+}
@@ -0,0 +1,47 @@
+//! Runs test vectors against the implemented handlers, requiring that no case fails:
+//!
+//! - The vectors vendored in `tests/vendored`, which this tree passes.
+//! - The Ethereum Foundation test vectors fetched by `make` (see the `Makefile` of this crate for
+//!   the pinned version).
+use ef_tests::{cases, run_tests, SPEC_TESTS_DIR};
+use std::path::{Path, PathBuf};
+
+/// Runs the tests in `path`, requiring that each implemented handler ran some cases and that
+/// none of them failed.
+fn check_tests(path: &Path) {
+    let results = run_tests(path).unwrap();
+
+    for handler in results
+        .iter()
+        .filter(|r| cases::is_implemented(&r.runner, &r.handler))
+    {
+        assert!(handler.passed > 0, "{}: no cases were run", handler);
+        assert_eq!(handler.failed, 0, "{}: {:?}", handler, handler.failures);
+    }
+}
+
+#[test]
+fn vendored_tests() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/vendored");
+
+    check_tests(&path);
+}
+
+// The fetched vectors are generated from a later spec than this tree implements, which shuffles
+// with swap-or-not rather than Fisher-Yates and has newer `types`. This test is ignored until the
+// tree is updated to that spec.
+#[test]
+#[ignore]
+fn spec_tests() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push(SPEC_TESTS_DIR);
+    assert!(
+        path.is_dir(),
+        "{} does not exist, run `make` in {} to fetch the test vectors",
+        path.display(),
+        env!("CARGO_MANIFEST_DIR")
+    );
+
+    check_tests(&path);
+}
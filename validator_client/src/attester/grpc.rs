@@ -0,0 +1,84 @@
+use super::traits::{BeaconNode, BeaconNodeError};
+use protos::services::{
+    AttestationData as GrpcAttestationData, FreeAttestation as GrpcFreeAttestation,
+    ProduceAttestationDataRequest, PublishAttestationRequest,
+};
+use protos::services_grpc::AttestationServiceClient;
+use ssz::ssz_encode;
+use types::{AttestationData, FreeAttestation, Hash256};
+
+impl BeaconNode for AttestationServiceClient {
+    /// Request a Beacon Node (BN) to produce the `AttestationData` for the supplied slot and shard.
+    ///
+    /// Returns `None` if it is not possible to produce at the supplied slot.
+    fn produce_attestation_data(
+        &self,
+        slot: u64,
+        shard: u64,
+    ) -> Result<Option<AttestationData>, BeaconNodeError> {
+        let mut req = ProduceAttestationDataRequest::new();
+        req.set_slot(slot);
+        req.set_shard(shard);
+
+        let reply = self
+            .produce_attestation_data(&req)
+            .map_err(|err| BeaconNodeError::RemoteFailure(format!("{:?}", err)))?;
+
+        if reply.has_attestation_data() {
+            let data = reply.get_attestation_data();
+
+            Ok(Some(AttestationData {
+                slot: data.get_slot(),
+                shard: data.get_shard(),
+                beacon_block_root: decode_hash(data.get_beacon_block_root())?,
+                epoch_boundary_root: decode_hash(data.get_epoch_boundary_root())?,
+                shard_block_root: decode_hash(data.get_shard_block_root())?,
+                latest_crosslink_root: decode_hash(data.get_latest_crosslink_root())?,
+                justified_slot: data.get_justified_slot(),
+                justified_block_root: decode_hash(data.get_justified_block_root())?,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Request a Beacon Node (BN) to publish an attestation.
+    fn publish_attestation(
+        &self,
+        free_attestation: FreeAttestation,
+    ) -> Result<bool, BeaconNodeError> {
+        let data = &free_attestation.data;
+        let mut grpc_data = GrpcAttestationData::new();
+        grpc_data.set_slot(data.slot);
+        grpc_data.set_shard(data.shard);
+        grpc_data.set_beacon_block_root(data.beacon_block_root.to_vec());
+        grpc_data.set_epoch_boundary_root(data.epoch_boundary_root.to_vec());
+        grpc_data.set_shard_block_root(data.shard_block_root.to_vec());
+        grpc_data.set_latest_crosslink_root(data.latest_crosslink_root.to_vec());
+        grpc_data.set_justified_slot(data.justified_slot);
+        grpc_data.set_justified_block_root(data.justified_block_root.to_vec());
+
+        let mut grpc_free_attestation = GrpcFreeAttestation::new();
+        grpc_free_attestation.set_data(grpc_data);
+        grpc_free_attestation.set_signature(ssz_encode(&free_attestation.signature));
+        grpc_free_attestation.set_validator_index(free_attestation.validator_index);
+
+        let mut req = PublishAttestationRequest::new();
+        req.set_free_attestation(grpc_free_attestation);
+
+        let reply = self
+            .publish_attestation(&req)
+            .map_err(|err| BeaconNodeError::RemoteFailure(format!("{:?}", err)))?;
+
+        Ok(reply.get_success())
+    }
+}
+
+/// Decode a 32-byte root, failing on any other length.
+fn decode_hash(bytes: &[u8]) -> Result<Hash256, BeaconNodeError> {
+    if bytes.len() == 32 {
+        Ok(Hash256::from(bytes))
+    } else {
+        Err(BeaconNodeError::DecodeFailure)
+    }
+}
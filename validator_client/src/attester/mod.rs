@@ -0,0 +1,445 @@
+mod grpc;
+mod service;
+#[cfg(test)]
+mod test_node;
+mod traits;
+
+use super::EpochDutiesMap;
+use crate::node_status::{VerifiedFork, VerifiedSlot};
+use crate::signer::{Error as SignerError, Signer};
+use crate::slashing_protection::{Error as SlashingProtectionError, SlashingProtection};
+use bls::PublicKey;
+use slot_clock::SlotClock;
+use spec::ChainSpec;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use types::{AttestationData, FreeAttestation, Signature};
+
+pub use self::service::AttesterService;
+pub use self::traits::{BeaconNode, BeaconNodeError};
+
+/// The custody bit signed by attesters.
+///
+/// Proofs of custody are not yet implemented, so all attestations are signed with a custody bit
+/// of `false`.
+const CUSTODY_BIT: bool = false;
+
+#[derive(Debug, PartialEq)]
+pub enum PollOutcome {
+    /// A new attestation was produced.
+    AttestationProduced(u64),
+    /// An attestation was signed, but the Beacon Node did not accept it for publishing.
+    AttestationNotPublished(u64),
+    /// An attestation was not produced as it would have been slashable.
+    SlashableAttestationNotProduced(u64),
+    /// The validator duties did not require an attestation to be produced.
+    AttestationNotRequired(u64),
+    /// The duties for the present epoch were not found.
+    AttesterDutiesUnknown(u64),
+    /// The slot has already been processed, execution was skipped.
+    SlotAlreadyProcessed(u64),
+    /// An attestation is required, but it is too early in the slot to attest.
+    TooEarlyInSlot(u64),
+    /// The Beacon Node was unable to produce attestation data at that slot.
+    BeaconNodeUnableToProduceAttestation(u64),
+    /// The Beacon Node has not been verified as safe to sign upon (e.g., it is syncing).
+    BeaconNodeNotVerified(u64),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    SlotClockError,
+    SlotUnknowable,
+    EpochMapPoisoned,
+    SlotClockPoisoned,
+    VerifiedSlotPoisoned,
+    VerifiedForkPoisoned,
+    /// The fork of the Beacon Node is not known, so no signature domain can be computed.
+    ForkUnknown,
+    EpochLengthIsZero,
+    BeaconNodeError(BeaconNodeError),
+    SlashingProtectionError(SlashingProtectionError),
+    SignerError(SignerError),
+}
+
+/// A polling state machine which performs attestation duties, based upon some epoch duties
+/// (`EpochDutiesMap`) and a concept of time (`SlotClock`).
+///
+/// Attestations are produced half-way through the slot, giving the proposer time to publish the
+/// block for that slot. Ensures that messages are not slashable by recording each attestation in
+/// a `SlashingProtection` database before it is signed.
+///
+/// Relies upon external services to keep the `EpochDutiesMap`, `VerifiedSlot` and `VerifiedFork`
+/// updated.
+pub struct Attester<T: SlotClock, U: BeaconNode, V: Signer> {
+    pub last_processed_slot: u64,
+    spec: Arc<ChainSpec>,
+    epoch_map: Arc<RwLock<EpochDutiesMap>>,
+    verified_slot: Arc<RwLock<VerifiedSlot>>,
+    verified_fork: Arc<RwLock<VerifiedFork>>,
+    slot_clock: Arc<RwLock<T>>,
+    beacon_node: Arc<U>,
+    signer: Arc<V>,
+    slashing_protection: Arc<SlashingProtection>,
+}
+
+impl<T: SlotClock, U: BeaconNode, V: Signer> Attester<T, U, V> {
+    /// Returns a new instance where `last_processed_slot == 0`.
+    pub fn new(
+        spec: Arc<ChainSpec>,
+        epoch_map: Arc<RwLock<EpochDutiesMap>>,
+        verified_slot: Arc<RwLock<VerifiedSlot>>,
+        verified_fork: Arc<RwLock<VerifiedFork>>,
+        slot_clock: Arc<RwLock<T>>,
+        beacon_node: Arc<U>,
+        signer: Arc<V>,
+        slashing_protection: Arc<SlashingProtection>,
+    ) -> Self {
+        Self {
+            last_processed_slot: 0,
+            spec,
+            epoch_map,
+            verified_slot,
+            verified_fork,
+            slot_clock,
+            beacon_node,
+            signer,
+            slashing_protection,
+        }
+    }
+
+    /// The public key of the attesting validator.
+    pub fn public_key(&self) -> &PublicKey {
+        self.signer.public_key()
+    }
+}
+
+impl<T: SlotClock, U: BeaconNode, V: Signer> Attester<T, U, V> {
+    /// "Poll" to see if the validator is required to take any action.
+    ///
+    /// The slot clock will be read and any new actions undertaken.
+    pub fn poll(&mut self) -> Result<PollOutcome, Error> {
+        let (slot, slot_offset) = {
+            let slot_clock = self
+                .slot_clock
+                .read()
+                .map_err(|_| Error::SlotClockPoisoned)?;
+            let slot = slot_clock
+                .present_slot()
+                .map_err(|_| Error::SlotClockError)?
+                .ok_or(Error::SlotUnknowable)?;
+            let slot_offset = slot_clock
+                .present_slot_offset()
+                .map_err(|_| Error::SlotClockError)?
+                .ok_or(Error::SlotUnknowable)?;
+            (slot, slot_offset)
+        };
+
+        let epoch = slot
+            .checked_div(self.spec.epoch_length)
+            .ok_or(Error::EpochLengthIsZero)?;
+
+        // If this is a new slot.
+        if slot > self.last_processed_slot {
+            let (validator_index, duty) = {
+                let epoch_map = self.epoch_map.read().map_err(|_| Error::EpochMapPoisoned)?;
+                match epoch_map.get(&epoch) {
+                    None => return Ok(PollOutcome::AttesterDutiesUnknown(slot)),
+                    Some(duties) => (duties.validator_index, duties.attestation_duty_at(slot)),
+                }
+            };
+
+            if let Some(duty) = duty {
+                if slot_offset < self.attestation_offset() {
+                    return Ok(PollOutcome::TooEarlyInSlot(slot));
+                }
+
+                // The slot is not marked as processed so production may be re-attempted once
+                // the Beacon Node is verified.
+                if !self.beacon_node_verified(slot)? {
+                    return Ok(PollOutcome::BeaconNodeNotVerified(slot));
+                }
+
+                self.last_processed_slot = slot;
+
+                self.produce_attestation(slot, duty.shard, validator_index)
+            } else {
+                Ok(PollOutcome::AttestationNotRequired(slot))
+            }
+        } else {
+            Ok(PollOutcome::SlotAlreadyProcessed(slot))
+        }
+    }
+
+    /// Returns the time into a slot at which attestations should be produced.
+    fn attestation_offset(&self) -> Duration {
+        Duration::from_secs(self.spec.slot_duration) / 2
+    }
+
+    /// Returns `true` if the Beacon Node has been verified as safe to sign upon at `slot`.
+    fn beacon_node_verified(&self, slot: u64) -> Result<bool, Error> {
+        let verified_slot = self
+            .verified_slot
+            .read()
+            .map_err(|_| Error::VerifiedSlotPoisoned)?;
+        Ok(*verified_slot == Some(slot))
+    }
+
+    /// Produce an attestation at some slot and shard.
+    ///
+    /// Assumes that an attestation is required at this slot (does not check the duties).
+    ///
+    /// Ensures the message is not slashable.
+    fn produce_attestation(
+        &mut self,
+        slot: u64,
+        shard: u64,
+        validator_index: u64,
+    ) -> Result<PollOutcome, Error> {
+        if let Some(data) = self.beacon_node.produce_attestation_data(slot, shard)? {
+            if self.safe_to_attest(&data)? {
+                let signature = self.sign_attestation_data(&data)?;
+                let free_attestation = FreeAttestation {
+                    data,
+                    signature,
+                    validator_index,
+                };
+                if self.beacon_node.publish_attestation(free_attestation)? {
+                    Ok(PollOutcome::AttestationProduced(slot))
+                } else {
+                    Ok(PollOutcome::AttestationNotPublished(slot))
+                }
+            } else {
+                Ok(PollOutcome::SlashableAttestationNotProduced(slot))
+            }
+        } else {
+            Ok(PollOutcome::BeaconNodeUnableToProduceAttestation(slot))
+        }
+    }
+
+    /// Returns the validators signature upon `data`, with the custody bit.
+    ///
+    /// Important: this function will not check to ensure the attestation is not slashable. This
+    /// must be done upstream.
+    fn sign_attestation_data(&self, data: &AttestationData) -> Result<Signature, Error> {
+        let domain = self.domain(data.slot, self.spec.domain_attestation)?;
+        Ok(self
+            .signer
+            .sign(&data.signing_root(CUSTODY_BIT), domain, data.slot)?)
+    }
+
+    /// Returns the signature domain for `domain_type` at the given `slot`, using the fork of the
+    /// verified Beacon Node.
+    fn domain(&self, slot: u64, domain_type: u64) -> Result<u64, Error> {
+        let verified_fork = self
+            .verified_fork
+            .read()
+            .map_err(|_| Error::VerifiedForkPoisoned)?;
+        let fork = verified_fork.as_ref().ok_or(Error::ForkUnknown)?;
+        Ok(fork.get_domain(slot, domain_type))
+    }
+
+    /// Returns `true` if signing some attestation data is safe (non-slashable).
+    ///
+    /// If safe, the attestation is recorded in the slashing-protection database so that
+    /// conflicting attestations will not be signed in the future.
+    fn safe_to_attest(&self, data: &AttestationData) -> Result<bool, Error> {
+        let epoch_length = self.spec.epoch_length;
+        let source_epoch = data
+            .justified_slot
+            .checked_div(epoch_length)
+            .ok_or(Error::EpochLengthIsZero)?;
+        let target_epoch = data
+            .slot
+            .checked_div(epoch_length)
+            .ok_or(Error::EpochLengthIsZero)?;
+
+        match self.slashing_protection.check_and_insert_attestation(
+            self.signer.public_key(),
+            source_epoch,
+            target_epoch,
+            &data.signing_root(CUSTODY_BIT),
+        ) {
+            Ok(()) => Ok(true),
+            Err(SlashingProtectionError::NotSafe(_)) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl From<BeaconNodeError> for Error {
+    fn from(e: BeaconNodeError) -> Error {
+        Error::BeaconNodeError(e)
+    }
+}
+
+impl From<SlashingProtectionError> for Error {
+    fn from(e: SlashingProtectionError) -> Error {
+        Error::SlashingProtectionError(e)
+    }
+}
+
+impl From<SignerError> for Error {
+    fn from(e: SignerError) -> Error {
+        Error::SignerError(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_node::TestBeaconNode;
+    use super::*;
+    use crate::duties::{AttestationDuty, EpochDuties};
+    use crate::signer::LocalSigner;
+    use crate::slashing_protection::SLASHING_PROTECTION_FILE;
+    use bls::Keypair;
+    use slot_clock::TestingSlotClock;
+    use std::{env, fs, process};
+    use types::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use types::Fork;
+
+    #[test]
+    pub fn polling() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+
+        let spec = Arc::new(ChainSpec::foundation());
+        let epoch_map = Arc::new(RwLock::new(EpochDutiesMap::new()));
+        let verified_slot = Arc::new(RwLock::new(None));
+        let verified_fork = Arc::new(RwLock::new(None));
+        let slot_clock = Arc::new(RwLock::new(TestingSlotClock::new(0)));
+        let beacon_node = Arc::new(TestBeaconNode::default());
+        let keypair = Keypair::random();
+        let signer = Arc::new(LocalSigner::new(keypair.clone()));
+        let slashing_protection = {
+            let dir = env::temp_dir().join(format!("attester_polling_{}", process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            let path = dir.join(SLASHING_PROTECTION_FILE);
+            Arc::new(SlashingProtection::open(&path, spec.genesis_time).unwrap())
+        };
+
+        let mut attester = Attester::new(
+            spec.clone(),
+            epoch_map.clone(),
+            verified_slot.clone(),
+            verified_fork.clone(),
+            slot_clock.clone(),
+            beacon_node.clone(),
+            signer,
+            slashing_protection,
+        );
+
+        // Setup some valid duties for the validator
+        let attest_slot = 100;
+        let attest_shard = 12;
+        let attest_epoch = attest_slot / spec.epoch_length;
+        let validator_index = 7;
+
+        // Configure responses from the BeaconNode.
+        let mut data = AttestationData::random_for_test(&mut rng);
+        data.slot = attest_slot;
+        data.shard = attest_shard;
+        data.justified_slot = attest_slot - spec.epoch_length;
+        beacon_node.set_next_produce_result(Ok(Some(data.clone())));
+        beacon_node.set_next_publish_result(Ok(true));
+
+        let duties = EpochDuties {
+            validator_index,
+            attestation_duty: Some(AttestationDuty {
+                slot: attest_slot,
+                shard: attest_shard,
+            }),
+            ..std::default::Default::default()
+        };
+        epoch_map.write().unwrap().insert(attest_epoch, duties);
+
+        // One slot before the attestation slot...
+        slot_clock.write().unwrap().set_slot(attest_slot - 1);
+        assert_eq!(
+            attester.poll(),
+            Ok(PollOutcome::AttestationNotRequired(attest_slot - 1))
+        );
+
+        // At the start of the attestation slot...
+        slot_clock.write().unwrap().set_slot(attest_slot);
+        assert_eq!(
+            attester.poll(),
+            Ok(PollOutcome::TooEarlyInSlot(attest_slot))
+        );
+
+        // Half-way through the attestation slot, without a verified beacon node...
+        let half_slot = Duration::from_secs(spec.slot_duration) / 2;
+        slot_clock.write().unwrap().set_slot_offset(half_slot);
+        assert_eq!(
+            attester.poll(),
+            Ok(PollOutcome::BeaconNodeNotVerified(attest_slot))
+        );
+
+        // Half-way through the attestation slot, after a fork...
+        let fork = Fork {
+            pre_fork_version: spec.genesis_fork_version,
+            post_fork_version: spec.genesis_fork_version + 1,
+            fork_slot: attest_slot - 1,
+        };
+        *verified_slot.write().unwrap() = Some(attest_slot);
+        *verified_fork.write().unwrap() = Some(fork.clone());
+        assert_eq!(
+            attester.poll(),
+            Ok(PollOutcome::AttestationProduced(attest_slot))
+        );
+
+        // The published attestation should be signed with the custody bit.
+        assert_eq!(
+            *beacon_node.produce_input.read().unwrap(),
+            Some((attest_slot, attest_shard))
+        );
+        let published = beacon_node.publish_input.read().unwrap().clone().unwrap();
+        assert_eq!(published.data, data);
+        assert_eq!(published.validator_index, validator_index);
+        assert!(published.signature.verify_with_domain(
+            &data.signing_root(CUSTODY_BIT),
+            fork.get_domain(attest_slot, spec.domain_attestation),
+            &keypair.pk
+        ));
+
+        // An attestation the Beacon Node refuses to publish is not reported as produced...
+        beacon_node.set_next_publish_result(Ok(false));
+        attester.last_processed_slot = attest_slot - 1;
+        assert_eq!(
+            attester.poll(),
+            Ok(PollOutcome::AttestationNotPublished(attest_slot))
+        );
+
+        // Attesting to different data in the same slot (e.g., after a restart)...
+        let mut conflicting_data = data.clone();
+        conflicting_data.beacon_block_root =
+            AttestationData::random_for_test(&mut rng).beacon_block_root;
+        beacon_node.set_next_produce_result(Ok(Some(conflicting_data)));
+        attester.last_processed_slot = attest_slot - 1;
+        assert_eq!(
+            attester.poll(),
+            Ok(PollOutcome::SlashableAttestationNotProduced(attest_slot))
+        );
+
+        // Trying the same attestation slot again...
+        assert_eq!(
+            attester.poll(),
+            Ok(PollOutcome::SlotAlreadyProcessed(attest_slot))
+        );
+
+        // One slot after the attestation slot...
+        slot_clock.write().unwrap().set_slot(attest_slot + 1);
+        assert_eq!(
+            attester.poll(),
+            Ok(PollOutcome::AttestationNotRequired(attest_slot + 1))
+        );
+
+        // In an epoch without known duties...
+        let slot = (attest_epoch + 1) * spec.epoch_length;
+        slot_clock.write().unwrap().set_slot(slot);
+        assert_eq!(
+            attester.poll(),
+            Ok(PollOutcome::AttesterDutiesUnknown(slot))
+        );
+    }
+}
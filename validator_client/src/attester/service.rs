@@ -0,0 +1,123 @@
+use super::traits::BeaconNode;
+use super::{Attester, Error, PollOutcome as AttesterPollOutcome, SlotClock};
+use crate::doppelganger::SigningGate;
+use crate::metrics::Metrics;
+use crate::signer::Signer;
+use slog::{debug, error, info, warn, Logger};
+use std::sync::Arc;
+
+pub struct AttesterService<T: SlotClock, U: BeaconNode, V: Signer> {
+    pub attester: Attester<T, U, V>,
+    /// Attestations are not produced until the validator is enabled.
+    pub gate: Arc<SigningGate>,
+    pub metrics: Arc<Metrics>,
+    /// The last slot counted as `duties_unknown`, which is the outcome of every poll of a slot
+    /// until its duties are known.
+    pub duties_unknown_slot: Option<u64>,
+    pub log: Logger,
+}
+
+impl<T: SlotClock, U: BeaconNode, V: Signer> AttesterService<T, U, V> {
+    /// Poll the attester once, logging the outcome and counting it if it is final for its slot.
+    pub fn poll(&mut self) {
+        if !self.gate.is_enabled(self.attester.public_key()) {
+            debug!(self.log, "Validator not yet enabled, attestation skipped");
+            return;
+        }
+
+        let result = self.attester.poll();
+        if let Some(outcome) = counted_outcome(&result, &mut self.duties_unknown_slot) {
+            let validator = self.attester.public_key().as_hex_string();
+            self.metrics.inc_attester_outcome(&validator, outcome);
+        }
+
+        match result {
+            Err(error) => {
+                error!(self.log, "Attester poll error"; "error" => format!("{:?}", error))
+            }
+            Ok(AttesterPollOutcome::AttestationProduced(slot)) => {
+                info!(self.log, "Produced attestation"; "slot" => slot)
+            }
+            Ok(AttesterPollOutcome::AttestationNotPublished(slot)) => {
+                error!(self.log, "Beacon node did not publish attestation"; "slot" => slot)
+            }
+            Ok(AttesterPollOutcome::SlashableAttestationNotProduced(slot)) => {
+                warn!(self.log, "Slashable attestation was not signed"; "slot" => slot)
+            }
+            Ok(AttesterPollOutcome::AttestationNotRequired(slot)) => {
+                debug!(self.log, "Attestation not required"; "slot" => slot)
+            }
+            Ok(AttesterPollOutcome::AttesterDutiesUnknown(slot)) => {
+                error!(self.log, "Attestation duties unknown"; "slot" => slot)
+            }
+            Ok(AttesterPollOutcome::SlotAlreadyProcessed(slot)) => {
+                debug!(self.log, "Attempted to re-process slot"; "slot" => slot)
+            }
+            Ok(AttesterPollOutcome::TooEarlyInSlot(slot)) => {
+                debug!(self.log, "Waiting to attest"; "slot" => slot)
+            }
+            Ok(AttesterPollOutcome::BeaconNodeUnableToProduceAttestation(slot)) => {
+                error!(self.log, "Beacon node unable to produce attestation"; "slot" => slot)
+            }
+            Ok(AttesterPollOutcome::BeaconNodeNotVerified(slot)) => {
+                warn!(self.log, "Beacon node not verified, attestation not produced"; "slot" => slot)
+            }
+        };
+    }
+}
+
+/// Returns the name by which `result` is counted, if it is the final outcome of its slot.
+///
+/// Outcomes after which the slot is polled again (e.g., `TooEarlyInSlot`) are not counted, and
+/// `duties_unknown` is only counted once per slot, using `duties_unknown_slot`.
+fn counted_outcome(
+    result: &Result<AttesterPollOutcome, Error>,
+    duties_unknown_slot: &mut Option<u64>,
+) -> Option<&'static str> {
+    match result {
+        Ok(AttesterPollOutcome::AttestationProduced(_)) => Some("attestation_produced"),
+        Ok(AttesterPollOutcome::AttestationNotPublished(_)) => Some("attestation_not_published"),
+        Ok(AttesterPollOutcome::SlashableAttestationNotProduced(_)) => {
+            Some("slashable_not_produced")
+        }
+        Ok(AttesterPollOutcome::BeaconNodeUnableToProduceAttestation(_)) => {
+            Some("beacon_node_unable_to_produce")
+        }
+        Ok(AttesterPollOutcome::AttesterDutiesUnknown(slot)) => {
+            if *duties_unknown_slot == Some(*slot) {
+                None
+            } else {
+                *duties_unknown_slot = Some(*slot);
+                Some("duties_unknown")
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn only_final_outcomes_are_counted() {
+        let mut duties_unknown_slot = None;
+        let mut count = |outcome| counted_outcome(&Ok(outcome), &mut duties_unknown_slot);
+
+        assert_eq!(
+            count(AttesterPollOutcome::AttesterDutiesUnknown(1)),
+            Some("duties_unknown")
+        );
+        assert_eq!(count(AttesterPollOutcome::AttesterDutiesUnknown(1)), None);
+        assert_eq!(count(AttesterPollOutcome::TooEarlyInSlot(2)), None);
+        assert_eq!(
+            count(AttesterPollOutcome::AttestationProduced(2)),
+            Some("attestation_produced")
+        );
+        assert_eq!(count(AttesterPollOutcome::SlotAlreadyProcessed(2)), None);
+        assert_eq!(
+            count(AttesterPollOutcome::AttesterDutiesUnknown(3)),
+            Some("duties_unknown")
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use super::traits::{BeaconNode, BeaconNodeError};
+use std::sync::RwLock;
+use types::{AttestationData, FreeAttestation};
+
+type ProduceResult = Result<Option<AttestationData>, BeaconNodeError>;
+type PublishResult = Result<bool, BeaconNodeError>;
+
+/// A test-only struct used to simulate a Beacon Node.
+#[derive(Default)]
+pub struct TestBeaconNode {
+    pub produce_input: RwLock<Option<(u64, u64)>>,
+    pub produce_result: RwLock<Option<ProduceResult>>,
+    pub publish_input: RwLock<Option<FreeAttestation>>,
+    pub publish_result: RwLock<Option<PublishResult>>,
+}
+
+impl TestBeaconNode {
+    /// Set the result to be returned when `produce_attestation_data` is called.
+    pub fn set_next_produce_result(&self, result: ProduceResult) {
+        *self.produce_result.write().unwrap() = Some(result);
+    }
+
+    /// Set the result to be returned when `publish_attestation` is called.
+    pub fn set_next_publish_result(&self, result: PublishResult) {
+        *self.publish_result.write().unwrap() = Some(result);
+    }
+}
+
+impl BeaconNode for TestBeaconNode {
+    /// Returns the value specified by the `set_next_produce_result`.
+    fn produce_attestation_data(&self, slot: u64, shard: u64) -> ProduceResult {
+        *self.produce_input.write().unwrap() = Some((slot, shard));
+        match *self.produce_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: produce_result == None"),
+        }
+    }
+
+    /// Returns the value specified by the `set_next_publish_result`.
+    fn publish_attestation(&self, free_attestation: FreeAttestation) -> PublishResult {
+        *self.publish_input.write().unwrap() = Some(free_attestation);
+        match *self.publish_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: publish_result == None"),
+        }
+    }
+}
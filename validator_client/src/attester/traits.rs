@@ -0,0 +1,26 @@
+use types::{AttestationData, FreeAttestation};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum BeaconNodeError {
+    RemoteFailure(String),
+    DecodeFailure,
+}
+
+/// Defines the methods required to produce and publish attestations on a Beacon Node.
+pub trait BeaconNode: Send + Sync {
+    /// Request that the node produces the `AttestationData` for the given `slot` and `shard`.
+    ///
+    /// Returns Ok(None) if the Beacon Node is unable to produce at the given slot.
+    fn produce_attestation_data(
+        &self,
+        slot: u64,
+        shard: u64,
+    ) -> Result<Option<AttestationData>, BeaconNodeError>;
+    /// Request that the node publishes an attestation.
+    ///
+    /// Returns `true` if the publish was sucessful.
+    fn publish_attestation(
+        &self,
+        free_attestation: FreeAttestation,
+    ) -> Result<bool, BeaconNodeError>;
+}
@@ -0,0 +1,100 @@
+use crate::attester::{BeaconNode as AttesterBeaconNode, BeaconNodeError as AttesterError};
+use crate::block_producer::{
+    BeaconNode as BlockProducerBeaconNode, BeaconNodeError as BlockProducerError,
+};
+use crate::doppelganger::{
+    BeaconNode as DoppelgangerBeaconNode, BeaconNodeError as DoppelgangerError,
+};
+use crate::duties::{BeaconNode as DutiesBeaconNode, BeaconNodeError as DutiesError, EpochDuties};
+use crate::node_status::{
+    BeaconNode as NodeStatusBeaconNode, BeaconNodeError as NodeStatusError, NodeStatus,
+};
+use bls::PublicKey;
+use grpcio::Channel;
+use protos::services_grpc::{
+    AttestationServiceClient, BeaconBlockServiceClient, NodeServiceClient, ValidatorServiceClient,
+};
+use types::{AttestationData, BeaconBlock, FreeAttestation, Hash256, Signature};
+
+/// The gRPC clients for all services of a single Beacon Node, sharing one channel.
+pub struct GrpcBeaconNode {
+    beacon_block: BeaconBlockServiceClient,
+    attestation: AttestationServiceClient,
+    validator: ValidatorServiceClient,
+    node: NodeServiceClient,
+}
+
+impl GrpcBeaconNode {
+    pub fn new(channel: Channel) -> Self {
+        Self {
+            beacon_block: BeaconBlockServiceClient::new(channel.clone()),
+            attestation: AttestationServiceClient::new(channel.clone()),
+            validator: ValidatorServiceClient::new(channel.clone()),
+            node: NodeServiceClient::new(channel),
+        }
+    }
+}
+
+// Note: fully-qualified syntax is required as the gRPC clients have inherent methods with the same
+// names as the trait methods.
+
+impl DutiesBeaconNode for GrpcBeaconNode {
+    fn request_shuffling(
+        &self,
+        epoch: u64,
+        public_key: &PublicKey,
+    ) -> Result<Option<EpochDuties>, DutiesError> {
+        DutiesBeaconNode::request_shuffling(&self.validator, epoch, public_key)
+    }
+}
+
+impl BlockProducerBeaconNode for GrpcBeaconNode {
+    fn produce_beacon_block(
+        &self,
+        slot: u64,
+        randao_reveal: &Signature,
+    ) -> Result<Option<BeaconBlock>, BlockProducerError> {
+        BlockProducerBeaconNode::produce_beacon_block(&self.beacon_block, slot, randao_reveal)
+    }
+
+    fn publish_beacon_block(&self, block: BeaconBlock) -> Result<bool, BlockProducerError> {
+        BlockProducerBeaconNode::publish_beacon_block(&self.beacon_block, block)
+    }
+
+    fn canonical_block_root(&self, slot: u64) -> Result<Option<Hash256>, BlockProducerError> {
+        BlockProducerBeaconNode::canonical_block_root(&self.beacon_block, slot)
+    }
+}
+
+impl AttesterBeaconNode for GrpcBeaconNode {
+    fn produce_attestation_data(
+        &self,
+        slot: u64,
+        shard: u64,
+    ) -> Result<Option<AttestationData>, AttesterError> {
+        AttesterBeaconNode::produce_attestation_data(&self.attestation, slot, shard)
+    }
+
+    fn publish_attestation(
+        &self,
+        free_attestation: FreeAttestation,
+    ) -> Result<bool, AttesterError> {
+        AttesterBeaconNode::publish_attestation(&self.attestation, free_attestation)
+    }
+}
+
+impl DoppelgangerBeaconNode for GrpcBeaconNode {
+    fn validator_liveness(
+        &self,
+        epoch: u64,
+        validator_indices: &[u64],
+    ) -> Result<Vec<u64>, DoppelgangerError> {
+        DoppelgangerBeaconNode::validator_liveness(&self.validator, epoch, validator_indices)
+    }
+}
+
+impl NodeStatusBeaconNode for GrpcBeaconNode {
+    fn node_status(&self) -> Result<NodeStatus, NodeStatusError> {
+        NodeStatusBeaconNode::node_status(&self.node)
+    }
+}
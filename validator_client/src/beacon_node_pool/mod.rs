@@ -0,0 +1,524 @@
+mod grpc;
+mod service;
+#[cfg(test)]
+mod test_node;
+
+use crate::attester::{BeaconNode as AttesterBeaconNode, BeaconNodeError as AttesterError};
+use crate::block_producer::{
+    BeaconNode as BlockProducerBeaconNode, BeaconNodeError as BlockProducerError,
+};
+use crate::doppelganger::{
+    BeaconNode as DoppelgangerBeaconNode, BeaconNodeError as DoppelgangerError,
+};
+use crate::duties::{BeaconNode as DutiesBeaconNode, BeaconNodeError as DutiesError, EpochDuties};
+use crate::node_status::{
+    BeaconNode as NodeStatusBeaconNode, Error as StatusPollError, NodeStatusManager,
+    PollOutcome as NodeStatusPollOutcome, VerifiedFork, VerifiedSlot,
+};
+use bls::PublicKey;
+use slog::{debug, info, warn, Logger};
+use slot_clock::SlotClock;
+use spec::ChainSpec;
+use std::fmt::Debug;
+use std::sync::{Arc, RwLock};
+use types::{AttestationData, BeaconBlock, FreeAttestation, Hash256, Signature};
+
+pub use self::grpc::GrpcBeaconNode;
+pub use self::service::BeaconNodePoolService;
+
+/// A Beacon Node which serves all of the services required by the validator client.
+pub trait BeaconNode:
+    DutiesBeaconNode
+    + BlockProducerBeaconNode
+    + AttesterBeaconNode
+    + DoppelgangerBeaconNode
+    + NodeStatusBeaconNode
+{
+}
+
+impl<T> BeaconNode for T where
+    T: DutiesBeaconNode
+        + BlockProducerBeaconNode
+        + AttesterBeaconNode
+        + DoppelgangerBeaconNode
+        + NodeStatusBeaconNode
+{
+}
+
+/// The number of slots after the slot at which a node was last verified that it is still treated as
+/// verified, allowing for the status poll to run after the start of a slot.
+const VERIFIED_SLOT_TOLERANCE: u64 = 1;
+
+/// The outcome of polling the status of each node in a `BeaconNodePool`, keyed by address.
+pub type PollOutcomes = Vec<(String, Result<NodeStatusPollOutcome, StatusPollError>)>;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    VerifiedSlotPoisoned,
+    VerifiedForkPoisoned,
+}
+
+/// A single Beacon Node in a `BeaconNodePool`.
+struct Endpoint<S: SlotClock, T: BeaconNode> {
+    address: String,
+    node: Arc<T>,
+    status: NodeStatusManager<S, T>,
+}
+
+impl<S: SlotClock, T: BeaconNode> Endpoint<S, T> {
+    /// Returns `true` if the node was last found to be safe to sign upon no more than
+    /// `VERIFIED_SLOT_TOLERANCE` slots before `present_slot`.
+    fn is_verified(&self, present_slot: u64) -> bool {
+        self.status
+            .verified_slot
+            .read()
+            .map(|slot| match *slot {
+                Some(slot) => slot + VERIFIED_SLOT_TOLERANCE >= present_slot,
+                None => false,
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// A set of Beacon Nodes which implements each of the `BeaconNode` traits, failing over to the
+/// next node if a node cannot be reached.
+///
+/// Nodes which have been verified as safe to sign upon (see `NodeStatusManager`) are preferred
+/// over those which have not; otherwise, nodes are preferred in the order they were supplied.
+/// Blocks and attestations are only ever produced and published by verified nodes, and only
+/// verified nodes are trusted to report validator liveness.
+///
+/// Relies upon an external service to `poll` the status of the nodes.
+pub struct BeaconNodePool<S: SlotClock, T: BeaconNode> {
+    endpoints: Vec<Endpoint<S, T>>,
+    slot_clock: Arc<RwLock<S>>,
+    verified_slot: Arc<RwLock<VerifiedSlot>>,
+    verified_fork: Arc<RwLock<VerifiedFork>>,
+    log: Logger,
+}
+
+impl<S: SlotClock, T: BeaconNode> BeaconNodePool<S, T> {
+    /// Returns a new pool containing the given `(address, node)` pairs, in order of preference.
+    pub fn new(
+        nodes: Vec<(String, T)>,
+        spec: Arc<ChainSpec>,
+        slot_clock: Arc<RwLock<S>>,
+        max_head_distance: u64,
+        log: Logger,
+    ) -> Self {
+        let endpoints = nodes
+            .into_iter()
+            .map(|(address, node)| {
+                let node = Arc::new(node);
+                let status = NodeStatusManager {
+                    verified_slot: Arc::new(RwLock::new(None)),
+                    verified_fork: Arc::new(RwLock::new(None)),
+                    spec: spec.clone(),
+                    slot_clock: slot_clock.clone(),
+                    beacon_node: node.clone(),
+                    max_head_distance,
+                };
+                Endpoint {
+                    address,
+                    node,
+                    status,
+                }
+            })
+            .collect();
+
+        Self {
+            endpoints,
+            slot_clock,
+            verified_slot: Arc::new(RwLock::new(None)),
+            verified_fork: Arc::new(RwLock::new(None)),
+            log,
+        }
+    }
+
+    /// Returns the `VerifiedSlot` for the pool as a whole, which is the latest slot at which any
+    /// node in the pool was verified.
+    pub fn verified_slot(&self) -> Arc<RwLock<VerifiedSlot>> {
+        self.verified_slot.clone()
+    }
+
+    /// Returns the `VerifiedFork` for the pool as a whole, which is the fork reported by the node
+    /// which set the `VerifiedSlot`.
+    pub fn verified_fork(&self) -> Arc<RwLock<VerifiedFork>> {
+        self.verified_fork.clone()
+    }
+
+    /// Poll the status of each node, updating the `VerifiedSlot` and `VerifiedFork` of the pool.
+    pub fn poll(&self) -> Result<PollOutcomes, Error> {
+        let outcomes: PollOutcomes = self
+            .endpoints
+            .iter()
+            .map(|endpoint| (endpoint.address.clone(), endpoint.status.poll()))
+            .collect();
+
+        let latest_verified = self
+            .endpoints
+            .iter()
+            .zip(outcomes.iter())
+            .filter_map(|(endpoint, (_, outcome))| match outcome {
+                Ok(NodeStatusPollOutcome::NodeVerified(slot)) => Some((*slot, endpoint)),
+                _ => None,
+            })
+            .max_by_key(|(slot, _)| *slot);
+
+        let (latest_verified_slot, latest_verified_fork) = match latest_verified {
+            Some((slot, endpoint)) => {
+                let fork = endpoint
+                    .status
+                    .verified_fork
+                    .read()
+                    .map_err(|_| Error::VerifiedForkPoisoned)?
+                    .clone();
+                (Some(slot), fork)
+            }
+            None => (None, None),
+        };
+
+        *self
+            .verified_slot
+            .write()
+            .map_err(|_| Error::VerifiedSlotPoisoned)? = latest_verified_slot;
+        *self
+            .verified_fork
+            .write()
+            .map_err(|_| Error::VerifiedForkPoisoned)? = latest_verified_fork;
+
+        Ok(outcomes)
+    }
+
+    /// Returns the endpoints in order of preference, optionally excluding unverified nodes.
+    ///
+    /// If the present slot cannot be read, no node is considered verified.
+    fn preferred_endpoints(&self, verified_only: bool) -> Vec<&Endpoint<S, T>> {
+        let present_slot = self
+            .slot_clock
+            .read()
+            .ok()
+            .and_then(|slot_clock| slot_clock.present_slot().ok())
+            .and_then(|slot| slot);
+        let (verified, unverified): (Vec<_>, Vec<_>) = self
+            .endpoints
+            .iter()
+            .partition(|e| present_slot.map_or(false, |slot| e.is_verified(slot)));
+
+        if verified_only {
+            verified
+        } else {
+            verified.into_iter().chain(unverified).collect()
+        }
+    }
+
+    /// Call `f` on each node, in order of preference, until a node returns something other than a
+    /// remote failure. Returns the result along with the address of the node which served it.
+    fn call<F, R, E>(&self, duty: &str, verified_only: bool, f: F) -> Result<(R, &str), E>
+    where
+        F: Fn(&T) -> Result<R, E>,
+        E: FailoverError,
+    {
+        for endpoint in self.preferred_endpoints(verified_only) {
+            match f(&*endpoint.node) {
+                Err(ref e) if e.is_remote_failure() => {
+                    warn!(self.log, "Beacon node failed, trying next";
+                          "duty" => duty,
+                          "server" => &endpoint.address,
+                          "error" => format!("{:?}", e));
+                }
+                result => return result.map(|r| (r, endpoint.address.as_str())),
+            }
+        }
+
+        Err(E::unavailable(duty))
+    }
+}
+
+impl<S: SlotClock, T: BeaconNode> DutiesBeaconNode for BeaconNodePool<S, T> {
+    fn request_shuffling(
+        &self,
+        epoch: u64,
+        public_key: &PublicKey,
+    ) -> Result<Option<EpochDuties>, DutiesError> {
+        let (duties, server) = self.call("request_shuffling", false, |node| {
+            node.request_shuffling(epoch, public_key)
+        })?;
+        debug!(self.log, "Duties served"; "epoch" => epoch, "server" => server);
+        Ok(duties)
+    }
+}
+
+impl<S: SlotClock, T: BeaconNode> BlockProducerBeaconNode for BeaconNodePool<S, T> {
+    fn produce_beacon_block(
+        &self,
+        slot: u64,
+        randao_reveal: &Signature,
+    ) -> Result<Option<BeaconBlock>, BlockProducerError> {
+        let (block, server) = self.call("produce_beacon_block", true, |node| {
+            node.produce_beacon_block(slot, randao_reveal)
+        })?;
+        info!(self.log, "Block production served"; "slot" => slot, "server" => server);
+        Ok(block)
+    }
+
+    /// Note: the same order of preference is used as `produce_beacon_block`, so the block will
+    /// usually be published to the node which produced it.
+    fn publish_beacon_block(&self, block: BeaconBlock) -> Result<bool, BlockProducerError> {
+        let slot = block.slot;
+        let (success, server) = self.call("publish_beacon_block", true, |node| {
+            node.publish_beacon_block(block.clone())
+        })?;
+        info!(self.log, "Block publication served"; "slot" => slot, "server" => server);
+        Ok(success)
+    }
+
+    fn canonical_block_root(&self, slot: u64) -> Result<Option<Hash256>, BlockProducerError> {
+        let (root, server) = self.call("canonical_block_root", true, |node| {
+            node.canonical_block_root(slot)
+        })?;
+        debug!(self.log, "Canonical block root served"; "slot" => slot, "server" => server);
+        Ok(root)
+    }
+}
+
+impl<S: SlotClock, T: BeaconNode> AttesterBeaconNode for BeaconNodePool<S, T> {
+    fn produce_attestation_data(
+        &self,
+        slot: u64,
+        shard: u64,
+    ) -> Result<Option<AttestationData>, AttesterError> {
+        let (data, server) = self.call("produce_attestation_data", true, |node| {
+            node.produce_attestation_data(slot, shard)
+        })?;
+        info!(self.log, "Attestation production served"; "slot" => slot, "shard" => shard, "server" => server);
+        Ok(data)
+    }
+
+    fn publish_attestation(
+        &self,
+        free_attestation: FreeAttestation,
+    ) -> Result<bool, AttesterError> {
+        let slot = free_attestation.data.slot;
+        let (success, server) = self.call("publish_attestation", true, |node| {
+            node.publish_attestation(free_attestation.clone())
+        })?;
+        info!(self.log, "Attestation publication served"; "slot" => slot, "server" => server);
+        Ok(success)
+    }
+}
+
+impl<S: SlotClock, T: BeaconNode> DoppelgangerBeaconNode for BeaconNodePool<S, T> {
+    /// Note: only verified nodes are used, as a syncing node may not yet have seen the messages
+    /// of a doppelganger.
+    fn validator_liveness(
+        &self,
+        epoch: u64,
+        validator_indices: &[u64],
+    ) -> Result<Vec<u64>, DoppelgangerError> {
+        let (live_indices, server) = self.call("validator_liveness", true, |node| {
+            node.validator_liveness(epoch, validator_indices)
+        })?;
+        debug!(self.log, "Validator liveness served"; "epoch" => epoch, "server" => server);
+        Ok(live_indices)
+    }
+}
+
+/// An error which may indicate that a different node should be tried.
+trait FailoverError: Debug {
+    /// Returns `true` if the node could not be reached or did not respond correctly.
+    fn is_remote_failure(&self) -> bool;
+    /// Returns an error indicating that no node was able to perform the `duty`.
+    fn unavailable(duty: &str) -> Self;
+}
+
+impl FailoverError for DutiesError {
+    fn is_remote_failure(&self) -> bool {
+        match self {
+            DutiesError::RemoteFailure(_) => true,
+            DutiesError::DecodeFailure => false,
+        }
+    }
+
+    fn unavailable(duty: &str) -> Self {
+        DutiesError::RemoteFailure(format!("No beacon node available for {}", duty))
+    }
+}
+
+impl FailoverError for BlockProducerError {
+    fn is_remote_failure(&self) -> bool {
+        match self {
+            BlockProducerError::RemoteFailure(_) => true,
+            BlockProducerError::DecodeFailure => false,
+        }
+    }
+
+    fn unavailable(duty: &str) -> Self {
+        BlockProducerError::RemoteFailure(format!("No beacon node available for {}", duty))
+    }
+}
+
+impl FailoverError for AttesterError {
+    fn is_remote_failure(&self) -> bool {
+        match self {
+            AttesterError::RemoteFailure(_) => true,
+            AttesterError::DecodeFailure => false,
+        }
+    }
+
+    fn unavailable(duty: &str) -> Self {
+        AttesterError::RemoteFailure(format!("No beacon node available for {}", duty))
+    }
+}
+
+impl FailoverError for DoppelgangerError {
+    fn is_remote_failure(&self) -> bool {
+        match self {
+            DoppelgangerError::RemoteFailure(_) => true,
+            DoppelgangerError::Unimplemented => false,
+        }
+    }
+
+    fn unavailable(duty: &str) -> Self {
+        DoppelgangerError::RemoteFailure(format!("No beacon node available for {}", duty))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_node::TestBeaconNode;
+    use super::*;
+    use crate::node_status::NodeStatus;
+    use slog::o;
+    use slot_clock::TestingSlotClock;
+    use types::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use types::Fork;
+
+    #[test]
+    pub fn failover() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let spec = Arc::new(ChainSpec::foundation());
+        let slot_clock = Arc::new(RwLock::new(TestingSlotClock::new(10)));
+        let log = Logger::root(slog::Discard, o!());
+        let public_key = PublicKey::random_for_test(&mut rng);
+
+        let fork = Fork::random_for_test(&mut rng);
+        let synced = NodeStatus {
+            genesis_time: spec.genesis_time,
+            head_slot: 10,
+            fork: fork.clone(),
+            ..NodeStatus::default()
+        };
+        let syncing = NodeStatus {
+            is_syncing: true,
+            ..synced.clone()
+        };
+
+        let pool = BeaconNodePool::new(
+            vec![
+                ("a:50051".to_string(), TestBeaconNode::default()),
+                ("b:50051".to_string(), TestBeaconNode::default()),
+            ],
+            spec.clone(),
+            slot_clock.clone(),
+            4,
+            log,
+        );
+        let a = pool.endpoints[0].node.clone();
+        let b = pool.endpoints[1].node.clone();
+
+        let a_duties = EpochDuties {
+            validator_index: 1,
+            ..EpochDuties::default()
+        };
+        let b_duties = EpochDuties {
+            validator_index: 2,
+            ..EpochDuties::default()
+        };
+        a.set_next_shuffling_result(Ok(Some(a_duties)));
+        b.set_next_shuffling_result(Ok(Some(b_duties)));
+
+        // The first node is syncing, the second is synced.
+        a.set_next_status_result(Ok(syncing.clone()));
+        b.set_next_status_result(Ok(synced));
+        let outcomes = pool.poll().unwrap();
+        assert_eq!(
+            outcomes[0],
+            (
+                "a:50051".to_string(),
+                Ok(NodeStatusPollOutcome::NodeSyncing(10))
+            )
+        );
+        assert_eq!(*pool.verified_slot().read().unwrap(), Some(10));
+        assert_eq!(*pool.verified_fork().read().unwrap(), Some(fork));
+
+        // The synced node is preferred...
+        assert_eq!(pool.request_shuffling(0, &public_key), Ok(Some(b_duties)));
+
+        // ...unless it cannot be reached.
+        let failure = DutiesError::RemoteFailure("unreachable".to_string());
+        b.set_next_shuffling_result(Err(failure));
+        assert_eq!(pool.request_shuffling(0, &public_key), Ok(Some(a_duties)));
+
+        // Blocks are never produced by unverified nodes.
+        let block = BeaconBlock::random_for_test(&mut rng);
+        let randao_reveal = Signature::random_for_test(&mut rng);
+        a.set_next_produce_result(Ok(Some(block.clone())));
+        b.set_next_produce_result(Err(BlockProducerError::RemoteFailure(
+            "unreachable".to_string(),
+        )));
+        assert_eq!(
+            pool.produce_beacon_block(10, &randao_reveal),
+            Err(BlockProducerError::unavailable("produce_beacon_block"))
+        );
+        b.set_next_produce_result(Ok(Some(block.clone())));
+        assert_eq!(
+            pool.produce_beacon_block(10, &randao_reveal),
+            Ok(Some(block))
+        );
+
+        // Decode failures are not remote failures and are not retried.
+        b.set_next_produce_result(Err(BlockProducerError::DecodeFailure));
+        assert_eq!(
+            pool.produce_beacon_block(10, &randao_reveal),
+            Err(BlockProducerError::DecodeFailure)
+        );
+
+        // Nor is attestation data.
+        let data = AttestationData::random_for_test(&mut rng);
+        a.set_next_attestation_data_result(Ok(Some(data.clone())));
+        b.set_next_attestation_data_result(Err(AttesterError::RemoteFailure(
+            "unreachable".to_string(),
+        )));
+        assert_eq!(
+            pool.produce_attestation_data(10, 0),
+            Err(AttesterError::unavailable("produce_attestation_data"))
+        );
+
+        // A verification is only trusted until the tolerance has passed.
+        slot_clock
+            .write()
+            .unwrap()
+            .set_slot(10 + VERIFIED_SLOT_TOLERANCE);
+        a.set_next_attestation_data_result(Err(AttesterError::RemoteFailure(
+            "unreachable".to_string(),
+        )));
+        b.set_next_attestation_data_result(Ok(Some(data.clone())));
+        assert_eq!(pool.produce_attestation_data(10, 0), Ok(Some(data)));
+        slot_clock
+            .write()
+            .unwrap()
+            .set_slot(10 + VERIFIED_SLOT_TOLERANCE + 1);
+        assert_eq!(
+            pool.produce_attestation_data(10, 0),
+            Err(AttesterError::unavailable("produce_attestation_data"))
+        );
+        slot_clock.write().unwrap().set_slot(10);
+
+        // With neither node verified, the pool is not verified.
+        b.set_next_status_result(Ok(syncing));
+        pool.poll().unwrap();
+        assert_eq!(*pool.verified_slot().read().unwrap(), None);
+        assert_eq!(*pool.verified_fork().read().unwrap(), None);
+    }
+}
@@ -0,0 +1,46 @@
+use super::{BeaconNode, BeaconNodePool};
+use crate::node_status::PollOutcome;
+use slog::{debug, error, warn, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+
+pub struct BeaconNodePoolService<S: SlotClock, T: BeaconNode> {
+    pub pool: Arc<BeaconNodePool<S, T>>,
+    pub log: Logger,
+}
+
+impl<S: SlotClock, T: BeaconNode> BeaconNodePoolService<S, T> {
+    /// Poll the status of each node once, logging the outcomes.
+    pub fn poll(&mut self) {
+        match self.pool.poll() {
+            Err(error) => {
+                error!(self.log, "Beacon node pool poll error"; "error" => format!("{:?}", error))
+            }
+            Ok(outcomes) => {
+                for (server, outcome) in outcomes {
+                    self.log_outcome(&server, outcome)
+                }
+            }
+        };
+    }
+
+    fn log_outcome<E: std::fmt::Debug>(&self, server: &str, outcome: Result<PollOutcome, E>) {
+        match outcome {
+            Err(error) => {
+                error!(self.log, "Node status poll error"; "server" => server, "error" => format!("{:?}", error))
+            }
+            Ok(PollOutcome::NodeVerified(slot)) => {
+                debug!(self.log, "Beacon node verified"; "server" => server, "slot" => slot)
+            }
+            Ok(PollOutcome::NodeSyncing(slot)) => {
+                warn!(self.log, "Beacon node is syncing"; "server" => server, "slot" => slot)
+            }
+            Ok(PollOutcome::GenesisTimeMismatch(genesis_time)) => {
+                error!(self.log, "Beacon node has a different genesis time"; "server" => server, "node_genesis_time" => genesis_time)
+            }
+            Ok(PollOutcome::HeadTooFarBehind(head_slot, slot)) => {
+                warn!(self.log, "Beacon node head is too far behind"; "server" => server, "head_slot" => head_slot, "slot" => slot)
+            }
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use crate::attester::{BeaconNode as AttesterBeaconNode, BeaconNodeError as AttesterError};
+use crate::block_producer::{
+    BeaconNode as BlockProducerBeaconNode, BeaconNodeError as BlockProducerError,
+};
+use crate::doppelganger::{
+    BeaconNode as DoppelgangerBeaconNode, BeaconNodeError as DoppelgangerError,
+};
+use crate::duties::{BeaconNode as DutiesBeaconNode, BeaconNodeError as DutiesError, EpochDuties};
+use crate::node_status::{
+    BeaconNode as NodeStatusBeaconNode, BeaconNodeError as NodeStatusError, NodeStatus,
+};
+use bls::PublicKey;
+use std::sync::RwLock;
+use types::{AttestationData, BeaconBlock, FreeAttestation, Hash256, Signature};
+
+type ShufflingResult = Result<Option<EpochDuties>, DutiesError>;
+type ProduceResult = Result<Option<BeaconBlock>, BlockProducerError>;
+type PublishResult = Result<bool, BlockProducerError>;
+type CanonicalRootResult = Result<Option<Hash256>, BlockProducerError>;
+type AttestationDataResult = Result<Option<AttestationData>, AttesterError>;
+type PublishAttestationResult = Result<bool, AttesterError>;
+type LivenessResult = Result<Vec<u64>, DoppelgangerError>;
+type StatusResult = Result<NodeStatus, NodeStatusError>;
+
+/// A test-only struct used to simulate a Beacon Node serving all services.
+#[derive(Default)]
+pub struct TestBeaconNode {
+    pub shuffling_result: RwLock<Option<ShufflingResult>>,
+    pub produce_result: RwLock<Option<ProduceResult>>,
+    pub publish_result: RwLock<Option<PublishResult>>,
+    pub canonical_root_result: RwLock<Option<CanonicalRootResult>>,
+    pub attestation_data_result: RwLock<Option<AttestationDataResult>>,
+    pub publish_attestation_result: RwLock<Option<PublishAttestationResult>>,
+    pub liveness_result: RwLock<Option<LivenessResult>>,
+    pub status_result: RwLock<Option<StatusResult>>,
+}
+
+impl TestBeaconNode {
+    /// Set the result to be returned when `request_shuffling` is called.
+    pub fn set_next_shuffling_result(&self, result: ShufflingResult) {
+        *self.shuffling_result.write().unwrap() = Some(result);
+    }
+
+    /// Set the result to be returned when `produce_beacon_block` is called.
+    pub fn set_next_produce_result(&self, result: ProduceResult) {
+        *self.produce_result.write().unwrap() = Some(result);
+    }
+
+    /// Set the result to be returned when `publish_beacon_block` is called.
+    pub fn set_next_publish_result(&self, result: PublishResult) {
+        *self.publish_result.write().unwrap() = Some(result);
+    }
+
+    /// Set the result to be returned when `canonical_block_root` is called.
+    pub fn set_next_canonical_root_result(&self, result: CanonicalRootResult) {
+        *self.canonical_root_result.write().unwrap() = Some(result);
+    }
+
+    /// Set the result to be returned when `produce_attestation_data` is called.
+    pub fn set_next_attestation_data_result(&self, result: AttestationDataResult) {
+        *self.attestation_data_result.write().unwrap() = Some(result);
+    }
+
+    /// Set the result to be returned when `publish_attestation` is called.
+    pub fn set_next_publish_attestation_result(&self, result: PublishAttestationResult) {
+        *self.publish_attestation_result.write().unwrap() = Some(result);
+    }
+
+    /// Set the result to be returned when `validator_liveness` is called.
+    pub fn set_next_liveness_result(&self, result: LivenessResult) {
+        *self.liveness_result.write().unwrap() = Some(result);
+    }
+
+    /// Set the result to be returned when `node_status` is called.
+    pub fn set_next_status_result(&self, result: StatusResult) {
+        *self.status_result.write().unwrap() = Some(result);
+    }
+}
+
+impl DutiesBeaconNode for TestBeaconNode {
+    /// Returns the value specified by the `set_next_shuffling_result`.
+    fn request_shuffling(&self, _epoch: u64, _public_key: &PublicKey) -> ShufflingResult {
+        match *self.shuffling_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: shuffling_result == None"),
+        }
+    }
+}
+
+impl BlockProducerBeaconNode for TestBeaconNode {
+    /// Returns the value specified by the `set_next_produce_result`.
+    fn produce_beacon_block(&self, _slot: u64, _randao_reveal: &Signature) -> ProduceResult {
+        match *self.produce_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: produce_result == None"),
+        }
+    }
+
+    /// Returns the value specified by the `set_next_publish_result`.
+    fn publish_beacon_block(&self, _block: BeaconBlock) -> PublishResult {
+        match *self.publish_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: publish_result == None"),
+        }
+    }
+
+    /// Returns the value specified by the `set_next_canonical_root_result`.
+    fn canonical_block_root(&self, _slot: u64) -> CanonicalRootResult {
+        match *self.canonical_root_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: canonical_root_result == None"),
+        }
+    }
+}
+
+impl AttesterBeaconNode for TestBeaconNode {
+    /// Returns the value specified by the `set_next_attestation_data_result`.
+    fn produce_attestation_data(&self, _slot: u64, _shard: u64) -> AttestationDataResult {
+        match *self.attestation_data_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: attestation_data_result == None"),
+        }
+    }
+
+    /// Returns the value specified by the `set_next_publish_attestation_result`.
+    fn publish_attestation(&self, _free_attestation: FreeAttestation) -> PublishAttestationResult {
+        match *self.publish_attestation_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: publish_attestation_result == None"),
+        }
+    }
+}
+
+impl DoppelgangerBeaconNode for TestBeaconNode {
+    /// Returns the value specified by the `set_next_liveness_result`.
+    fn validator_liveness(&self, _epoch: u64, _validator_indices: &[u64]) -> LivenessResult {
+        match *self.liveness_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: liveness_result == None"),
+        }
+    }
+}
+
+impl NodeStatusBeaconNode for TestBeaconNode {
+    /// Returns the value specified by the `set_next_status_result`.
+    fn node_status(&self) -> StatusResult {
+        match *self.status_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: status_result == None"),
+        }
+    }
+}
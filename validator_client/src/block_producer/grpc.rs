@@ -1,6 +1,7 @@
 use super::traits::{BeaconNode, BeaconNodeError};
 use protos::services::{
-    BeaconBlock as GrpcBeaconBlock, ProduceBeaconBlockRequest, PublishBeaconBlockRequest,
+    BeaconBlock as GrpcBeaconBlock, CanonicalBlockRootRequest, ProduceBeaconBlockRequest,
+    PublishBeaconBlockRequest,
 };
 use protos::services_grpc::BeaconBlockServiceClient;
 use ssz::{ssz_encode, Decodable};
@@ -11,9 +12,14 @@ impl BeaconNode for BeaconBlockServiceClient {
     ///
     /// Returns `None` if it is not possible to produce at the supplied slot. For example, if the
     /// BN is unable to find a parent block.
-    fn produce_beacon_block(&self, slot: u64) -> Result<Option<BeaconBlock>, BeaconNodeError> {
+    fn produce_beacon_block(
+        &self,
+        slot: u64,
+        randao_reveal: &Signature,
+    ) -> Result<Option<BeaconBlock>, BeaconNodeError> {
         let mut req = ProduceBeaconBlockRequest::new();
         req.set_slot(slot);
+        req.set_randao_reveal(ssz_encode(randao_reveal));
 
         let reply = self
             .produce_beacon_block(&req)
@@ -77,4 +83,24 @@ impl BeaconNode for BeaconBlockServiceClient {
 
         Ok(reply.get_success())
     }
+
+    /// Request the root of the canonical block at `slot` from a Beacon Node (BN).
+    fn canonical_block_root(&self, slot: u64) -> Result<Option<Hash256>, BeaconNodeError> {
+        let mut req = CanonicalBlockRootRequest::new();
+        req.set_slot(slot);
+
+        let reply = self
+            .canonical_block_root(&req)
+            .map_err(|err| BeaconNodeError::RemoteFailure(format!("{:?}", err)))?;
+
+        if reply.has_block_root() {
+            let root = reply.get_block_root();
+            if root.len() != 32 {
+                return Err(BeaconNodeError::DecodeFailure);
+            }
+            Ok(Some(Hash256::from(root)))
+        } else {
+            Ok(None)
+        }
+    }
 }
@@ -4,14 +4,19 @@ mod service;
 mod test_node;
 mod traits;
 
-use self::traits::{BeaconNode, BeaconNodeError};
 use super::EpochDutiesMap;
+use crate::node_status::{VerifiedFork, VerifiedSlot};
+use crate::signer::{Error as SignerError, Signer};
+use crate::slashing_protection::{Error as SlashingProtectionError, SlashingProtection};
+use bls::PublicKey;
 use slot_clock::SlotClock;
 use spec::ChainSpec;
+use ssz::ssz_encode;
 use std::sync::{Arc, RwLock};
-use types::BeaconBlock;
+use types::{BeaconBlock, Hash256, Signature};
 
 pub use self::service::BlockProducerService;
+pub use self::traits::{BeaconNode, BeaconNodeError};
 
 #[derive(Debug, PartialEq)]
 pub enum PollOutcome {
@@ -27,6 +32,8 @@ pub enum PollOutcome {
     SlotAlreadyProcessed(u64),
     /// The Beacon Node was unable to produce a block at that slot.
     BeaconNodeUnableToProduceBlock(u64),
+    /// The Beacon Node has not been verified as safe to sign upon (e.g., it is syncing).
+    BeaconNodeNotVerified(u64),
 }
 
 #[derive(Debug, PartialEq)]
@@ -35,43 +42,72 @@ pub enum Error {
     SlotUnknowable,
     EpochMapPoisoned,
     SlotClockPoisoned,
+    VerifiedSlotPoisoned,
+    VerifiedForkPoisoned,
+    /// The fork of the Beacon Node is not known, so no signature domain can be computed.
+    ForkUnknown,
     EpochLengthIsZero,
     BeaconNodeError(BeaconNodeError),
+    SlashingProtectionError(SlashingProtectionError),
+    SignerError(SignerError),
 }
 
 /// A polling state machine which performs block production duties, based upon some epoch duties
 /// (`EpochDutiesMap`) and a concept of time (`SlotClock`).
 ///
-/// Ensures that messages are not slashable.
+/// Ensures that messages are not slashable by recording each proposal in a `SlashingProtection`
+/// database before it is signed.
 ///
-/// Relies upon an external service to keep the `EpochDutiesMap` updated.
-pub struct BlockProducer<T: SlotClock, U: BeaconNode> {
+/// Relies upon external services to keep the `EpochDutiesMap`, `VerifiedSlot` and `VerifiedFork`
+/// updated.
+pub struct BlockProducer<T: SlotClock, U: BeaconNode, V: Signer> {
     pub last_processed_slot: u64,
     spec: Arc<ChainSpec>,
     epoch_map: Arc<RwLock<EpochDutiesMap>>,
+    verified_slot: Arc<RwLock<VerifiedSlot>>,
+    verified_fork: Arc<RwLock<VerifiedFork>>,
     slot_clock: Arc<RwLock<T>>,
     beacon_node: Arc<U>,
+    signer: Arc<V>,
+    slashing_protection: Arc<SlashingProtection>,
+    /// The slot and root of each published block which has not yet been checked for inclusion in
+    /// the canonical chain.
+    published_blocks: Vec<(u64, Hash256)>,
 }
 
-impl<T: SlotClock, U: BeaconNode> BlockProducer<T, U> {
+impl<T: SlotClock, U: BeaconNode, V: Signer> BlockProducer<T, U, V> {
     /// Returns a new instance where `last_processed_slot == 0`.
     pub fn new(
         spec: Arc<ChainSpec>,
         epoch_map: Arc<RwLock<EpochDutiesMap>>,
+        verified_slot: Arc<RwLock<VerifiedSlot>>,
+        verified_fork: Arc<RwLock<VerifiedFork>>,
         slot_clock: Arc<RwLock<T>>,
         beacon_node: Arc<U>,
+        signer: Arc<V>,
+        slashing_protection: Arc<SlashingProtection>,
     ) -> Self {
         Self {
             last_processed_slot: 0,
             spec,
             epoch_map,
+            verified_slot,
+            verified_fork,
             slot_clock,
             beacon_node,
+            signer,
+            slashing_protection,
+            published_blocks: vec![],
         }
     }
+
+    /// The public key of the validator producing blocks.
+    pub fn public_key(&self) -> &PublicKey {
+        self.signer.public_key()
+    }
 }
 
-impl<T: SlotClock, U: BeaconNode> BlockProducer<T, U> {
+impl<T: SlotClock, U: BeaconNode, V: Signer> BlockProducer<T, U, V> {
     /// "Poll" to see if the validator is required to take any action.
     ///
     /// The slot clock will be read and any new actions undertaken.
@@ -99,9 +135,15 @@ impl<T: SlotClock, U: BeaconNode> BlockProducer<T, U> {
             };
 
             if is_block_production_slot {
+                // The slot is not marked as processed so production may be re-attempted once
+                // the Beacon Node is verified.
+                if !self.beacon_node_verified(slot)? {
+                    return Ok(PollOutcome::BeaconNodeNotVerified(slot));
+                }
+
                 self.last_processed_slot = slot;
 
-                self.produce_block(slot)
+                self.produce_block(slot, epoch)
             } else {
                 Ok(PollOutcome::BlockProductionNotRequired(slot))
             }
@@ -110,21 +152,63 @@ impl<T: SlotClock, U: BeaconNode> BlockProducer<T, U> {
         }
     }
 
+    /// Check whether the blocks published at least an epoch ago were included in the canonical
+    /// chain of the Beacon Node.
+    ///
+    /// Returns the slot of each block checked and `true` if it is canonical. Each block is only
+    /// checked once; if the Beacon Node fails to respond, all are checked again on the next call.
+    pub fn check_published_blocks(&mut self) -> Result<Vec<(u64, bool)>, Error> {
+        let present_slot = self
+            .slot_clock
+            .read()
+            .map_err(|_| Error::SlotClockPoisoned)?
+            .present_slot()
+            .map_err(|_| Error::SlotClockError)?
+            .ok_or(Error::SlotUnknowable)?;
+
+        let mut checked = vec![];
+        for &(slot, block_root) in &self.published_blocks {
+            if slot + self.spec.epoch_length <= present_slot {
+                let canonical_root = self.beacon_node.canonical_block_root(slot)?;
+                checked.push((slot, canonical_root == Some(block_root)));
+            }
+        }
+        self.published_blocks
+            .retain(|(slot, _)| !checked.iter().any(|(checked_slot, _)| checked_slot == slot));
+
+        Ok(checked)
+    }
+
+    /// Returns `true` if the Beacon Node has been verified as safe to sign upon at `slot`.
+    fn beacon_node_verified(&self, slot: u64) -> Result<bool, Error> {
+        let verified_slot = self
+            .verified_slot
+            .read()
+            .map_err(|_| Error::VerifiedSlotPoisoned)?;
+        Ok(*verified_slot == Some(slot))
+    }
+
     /// Produce a block at some slot.
     ///
     /// Assumes that a block is required at this slot (does not check the duties).
     ///
     /// Ensures the message is not slashable.
-    ///
-    /// !!! UNSAFE !!!
-    ///
-    /// The slash-protection code is not yet implemented. There is zero protection against
-    /// slashing.
-    fn produce_block(&mut self, slot: u64) -> Result<PollOutcome, Error> {
-        if let Some(block) = self.beacon_node.produce_beacon_block(slot)? {
-            if self.safe_to_produce(&block) {
-                let block = self.sign_block(block);
+    fn produce_block(&mut self, slot: u64, epoch: u64) -> Result<PollOutcome, Error> {
+        let randao_reveal = self.randao_reveal(slot, epoch)?;
+
+        if let Some(mut block) = self
+            .beacon_node
+            .produce_beacon_block(slot, &randao_reveal)?
+        {
+            // The RANDAO reveal is set prior to the safety check as it forms part of the signed
+            // proposal, in case the Beacon Node did not include it.
+            block.randao_reveal = randao_reveal;
+
+            if self.safe_to_produce(&block)? {
+                let block = self.sign_block(block)?;
+                let block_root = block.canonical_root();
                 self.beacon_node.publish_beacon_block(block)?;
+                self.published_blocks.push((slot, block_root));
                 Ok(PollOutcome::BlockProduced(slot))
             } else {
                 Ok(PollOutcome::SlashableBlockNotProduced(slot))
@@ -134,36 +218,49 @@ impl<T: SlotClock, U: BeaconNode> BlockProducer<T, U> {
         }
     }
 
-    /// Consumes a block, returning that block signed by the validators private key.
+    /// Returns the RANDAO reveal for the given `epoch`: the validators signature upon the epoch.
+    fn randao_reveal(&self, slot: u64, epoch: u64) -> Result<Signature, Error> {
+        let domain = self.domain(slot, self.spec.domain_randao)?;
+        Ok(self.signer.sign(&ssz_encode(&epoch), domain, slot)?)
+    }
+
+    /// Consumes a block, returning that block signed by the validators `Signer`.
     ///
     /// Important: this function will not check to ensure the block is not slashable. This must be
     /// done upstream.
-    fn sign_block(&mut self, block: BeaconBlock) -> BeaconBlock {
-        // TODO: sign the block
-        // https://github.com/sigp/lighthouse/issues/160
-        self.store_produce(&block);
-        block
+    fn sign_block(&mut self, mut block: BeaconBlock) -> Result<BeaconBlock, Error> {
+        let proposal_root = block.proposal_root(self.spec.beacon_chain_shard_number);
+        let domain = self.domain(block.slot, self.spec.domain_proposal)?;
+        block.signature = self.signer.sign(&proposal_root, domain, block.slot)?;
+        Ok(block)
     }
 
-    /// Returns `true` if signing a block is safe (non-slashable).
-    ///
-    /// !!! UNSAFE !!!
-    ///
-    /// Important: this function is presently stubbed-out. It provides ZERO SAFETY.
-    fn safe_to_produce(&self, _block: &BeaconBlock) -> bool {
-        // TODO: ensure the producer doesn't produce slashable blocks.
-        // https://github.com/sigp/lighthouse/issues/160
-        true
+    /// Returns the signature domain for `domain_type` at the given `slot`, using the fork of the
+    /// verified Beacon Node.
+    fn domain(&self, slot: u64, domain_type: u64) -> Result<u64, Error> {
+        let verified_fork = self
+            .verified_fork
+            .read()
+            .map_err(|_| Error::VerifiedForkPoisoned)?;
+        let fork = verified_fork.as_ref().ok_or(Error::ForkUnknown)?;
+        Ok(fork.get_domain(slot, domain_type))
     }
 
-    /// Record that a block was produced so that slashable votes may not be made in the future.
-    ///
-    /// !!! UNSAFE !!!
+    /// Returns `true` if signing a block is safe (non-slashable).
     ///
-    /// Important: this function is presently stubbed-out. It provides ZERO SAFETY.
-    fn store_produce(&mut self, _block: &BeaconBlock) {
-        // TODO: record this block production to prevent future slashings.
-        // https://github.com/sigp/lighthouse/issues/160
+    /// If safe, the proposal is recorded in the slashing-protection database so that conflicting
+    /// blocks will not be signed in the future.
+    fn safe_to_produce(&self, block: &BeaconBlock) -> Result<bool, Error> {
+        let proposal_root = block.proposal_root(self.spec.beacon_chain_shard_number);
+        match self.slashing_protection.check_and_insert_proposal(
+            self.signer.public_key(),
+            block.slot,
+            &proposal_root,
+        ) {
+            Ok(()) => Ok(true),
+            Err(SlashingProtectionError::NotSafe(_)) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
     }
 }
 
@@ -173,13 +270,30 @@ impl From<BeaconNodeError> for Error {
     }
 }
 
+impl From<SlashingProtectionError> for Error {
+    fn from(e: SlashingProtectionError) -> Error {
+        Error::SlashingProtectionError(e)
+    }
+}
+
+impl From<SignerError> for Error {
+    fn from(e: SignerError) -> Error {
+        Error::SignerError(e)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::test_node::TestBeaconNode;
     use super::*;
     use crate::duties::EpochDuties;
+    use crate::signer::LocalSigner;
+    use crate::slashing_protection::SLASHING_PROTECTION_FILE;
+    use bls::Keypair;
     use slot_clock::TestingSlotClock;
+    use std::{env, fs, process};
     use types::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use types::Fork;
 
     // TODO: implement more thorough testing.
     // https://github.com/sigp/lighthouse/issues/160
@@ -192,27 +306,45 @@ mod tests {
 
         let spec = Arc::new(ChainSpec::foundation());
         let epoch_map = Arc::new(RwLock::new(EpochDutiesMap::new()));
+        let verified_slot = Arc::new(RwLock::new(None));
+        let verified_fork = Arc::new(RwLock::new(None));
         let slot_clock = Arc::new(RwLock::new(TestingSlotClock::new(0)));
         let beacon_node = Arc::new(TestBeaconNode::default());
+        let keypair = Keypair::random();
+        let signer = Arc::new(LocalSigner::new(keypair.clone()));
+        let slashing_protection = {
+            let dir = env::temp_dir().join(format!("block_producer_polling_{}", process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            let path = dir.join(SLASHING_PROTECTION_FILE);
+            Arc::new(SlashingProtection::open(&path, spec.genesis_time).unwrap())
+        };
 
         let mut block_producer = BlockProducer::new(
             spec.clone(),
             epoch_map.clone(),
+            verified_slot.clone(),
+            verified_fork.clone(),
             slot_clock.clone(),
             beacon_node.clone(),
+            signer,
+            slashing_protection,
         );
 
+        // Setup some valid duties for the validator
+        let produce_slot = 100;
+        let produce_epoch = produce_slot / spec.epoch_length;
+
         // Configure responses from the BeaconNode.
-        beacon_node.set_next_produce_result(Ok(Some(BeaconBlock::random_for_test(&mut rng))));
+        let mut block = BeaconBlock::random_for_test(&mut rng);
+        block.slot = produce_slot;
+        beacon_node.set_next_produce_result(Ok(Some(block)));
         beacon_node.set_next_publish_result(Ok(true));
 
-        // Setup some valid duties for the validator
-        let produce_slot = 100;
         let duties = EpochDuties {
             block_production_slot: Some(produce_slot),
             ..std::default::Default::default()
         };
-        let produce_epoch = produce_slot / spec.epoch_length;
         epoch_map.write().unwrap().insert(produce_epoch, duties);
 
         // One slot before production slot...
@@ -222,13 +354,70 @@ mod tests {
             Ok(PollOutcome::BlockProductionNotRequired(produce_slot - 1))
         );
 
-        // On the produce slot...
+        // On the produce slot, without a verified beacon node...
         slot_clock.write().unwrap().set_slot(produce_slot);
+        assert_eq!(
+            block_producer.poll(),
+            Ok(PollOutcome::BeaconNodeNotVerified(produce_slot))
+        );
+
+        // On the produce slot, after a fork...
+        let fork = Fork {
+            pre_fork_version: spec.genesis_fork_version,
+            post_fork_version: spec.genesis_fork_version + 1,
+            fork_slot: produce_slot - 1,
+        };
+        *verified_slot.write().unwrap() = Some(produce_slot);
+        *verified_fork.write().unwrap() = Some(fork.clone());
         assert_eq!(
             block_producer.poll(),
             Ok(PollOutcome::BlockProduced(produce_slot))
         );
 
+        // The published block should carry a valid RANDAO reveal and proposer signature.
+        let (_, randao_reveal) = beacon_node.produce_input.read().unwrap().clone().unwrap();
+        let published = beacon_node.publish_input.read().unwrap().clone().unwrap();
+        assert_eq!(published.randao_reveal, randao_reveal);
+        assert!(randao_reveal.verify_with_domain(
+            &ssz_encode(&produce_epoch),
+            fork.get_domain(produce_slot, spec.domain_randao),
+            &keypair.pk
+        ));
+        assert!(published.signature.verify_with_domain(
+            &published.proposal_root(spec.beacon_chain_shard_number),
+            fork.get_domain(produce_slot, spec.domain_proposal),
+            &keypair.pk
+        ));
+
+        // The block is only checked for inclusion in the canonical chain an epoch later...
+        assert_eq!(block_producer.check_published_blocks(), Ok(vec![]));
+        slot_clock
+            .write()
+            .unwrap()
+            .set_slot(produce_slot + spec.epoch_length);
+        beacon_node.set_next_canonical_root_result(Ok(Some(published.canonical_root())));
+        assert_eq!(
+            block_producer.check_published_blocks(),
+            Ok(vec![(produce_slot, true)])
+        );
+        assert_eq!(
+            *beacon_node.canonical_root_input.read().unwrap(),
+            Some(produce_slot)
+        );
+        // ...and only once.
+        assert_eq!(block_producer.check_published_blocks(), Ok(vec![]));
+        slot_clock.write().unwrap().set_slot(produce_slot);
+
+        // Producing a different block in the same slot (e.g., after a restart)...
+        let mut conflicting_block = BeaconBlock::random_for_test(&mut rng);
+        conflicting_block.slot = produce_slot;
+        beacon_node.set_next_produce_result(Ok(Some(conflicting_block)));
+        block_producer.last_processed_slot = produce_slot - 1;
+        assert_eq!(
+            block_producer.poll(),
+            Ok(PollOutcome::SlashableBlockNotProduced(produce_slot))
+        );
+
         // Trying the same produce slot again...
         slot_clock.write().unwrap().set_slot(produce_slot);
         assert_eq!(
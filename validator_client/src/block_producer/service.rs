@@ -1,45 +1,147 @@
 use super::traits::BeaconNode;
-use super::{BlockProducer, PollOutcome as BlockProducerPollOutcome, SlotClock};
-use slog::{error, info, warn, Logger};
-use std::time::Duration;
+use super::{BlockProducer, Error, PollOutcome as BlockProducerPollOutcome, SlotClock};
+use crate::doppelganger::SigningGate;
+use crate::metrics::Metrics;
+use crate::signer::Signer;
+use slog::{debug, error, info, warn, Logger};
+use std::sync::Arc;
 
-pub struct BlockProducerService<T: SlotClock, U: BeaconNode> {
-    pub block_producer: BlockProducer<T, U>,
-    pub poll_interval_millis: u64,
+pub struct BlockProducerService<T: SlotClock, U: BeaconNode, V: Signer> {
+    pub block_producer: BlockProducer<T, U, V>,
+    /// Blocks are not produced until the validator is enabled.
+    pub gate: Arc<SigningGate>,
+    pub metrics: Arc<Metrics>,
+    /// The last slot counted as `duties_unknown`, which is the outcome of every poll of a slot
+    /// until its duties are known.
+    pub duties_unknown_slot: Option<u64>,
     pub log: Logger,
 }
 
-impl<T: SlotClock, U: BeaconNode> BlockProducerService<T, U> {
-    /// Run a loop which polls the block producer each `poll_interval_millis` millseconds.
+impl<T: SlotClock, U: BeaconNode, V: Signer> BlockProducerService<T, U, V> {
+    /// Poll the block producer once, logging the outcome and counting it if it is final for its
+    /// slot.
     ///
-    /// Logs the results of the polls.
-    pub fn run(&mut self) {
-        loop {
-            match self.block_producer.poll() {
-                Err(error) => {
-                    error!(self.log, "Block producer poll error"; "error" => format!("{:?}", error))
-                }
-                Ok(BlockProducerPollOutcome::BlockProduced(slot)) => {
-                    info!(self.log, "Produced block"; "slot" => slot)
-                }
-                Ok(BlockProducerPollOutcome::SlashableBlockNotProduced(slot)) => {
-                    warn!(self.log, "Slashable block was not signed"; "slot" => slot)
-                }
-                Ok(BlockProducerPollOutcome::BlockProductionNotRequired(slot)) => {
-                    info!(self.log, "Block production not required"; "slot" => slot)
-                }
-                Ok(BlockProducerPollOutcome::ProducerDutiesUnknown(slot)) => {
-                    error!(self.log, "Block production duties unknown"; "slot" => slot)
-                }
-                Ok(BlockProducerPollOutcome::SlotAlreadyProcessed(slot)) => {
-                    warn!(self.log, "Attempted to re-process slot"; "slot" => slot)
-                }
-                Ok(BlockProducerPollOutcome::BeaconNodeUnableToProduceBlock(slot)) => {
-                    error!(self.log, "Beacon node unable to produce block"; "slot" => slot)
+    /// Previously published blocks are then checked for inclusion in the canonical chain.
+    pub fn poll(&mut self) {
+        if !self.gate.is_enabled(self.block_producer.public_key()) {
+            debug!(
+                self.log,
+                "Validator not yet enabled, block production skipped"
+            );
+            return;
+        }
+
+        let result = self.block_producer.poll();
+        let validator = self.block_producer.public_key().as_hex_string();
+        if let Some(outcome) = counted_outcome(&result, &mut self.duties_unknown_slot) {
+            self.metrics.inc_block_producer_outcome(&validator, outcome);
+        }
+
+        match result {
+            Err(error) => {
+                error!(self.log, "Block producer poll error"; "error" => format!("{:?}", error))
+            }
+            Ok(BlockProducerPollOutcome::BlockProduced(slot)) => {
+                info!(self.log, "Produced block"; "slot" => slot)
+            }
+            Ok(BlockProducerPollOutcome::SlashableBlockNotProduced(slot)) => {
+                warn!(self.log, "Slashable block was not signed"; "slot" => slot)
+            }
+            Ok(BlockProducerPollOutcome::BlockProductionNotRequired(slot)) => {
+                info!(self.log, "Block production not required"; "slot" => slot)
+            }
+            Ok(BlockProducerPollOutcome::ProducerDutiesUnknown(slot)) => {
+                error!(self.log, "Block production duties unknown"; "slot" => slot)
+            }
+            Ok(BlockProducerPollOutcome::SlotAlreadyProcessed(slot)) => {
+                warn!(self.log, "Attempted to re-process slot"; "slot" => slot)
+            }
+            Ok(BlockProducerPollOutcome::BeaconNodeUnableToProduceBlock(slot)) => {
+                error!(self.log, "Beacon node unable to produce block"; "slot" => slot)
+            }
+            Ok(BlockProducerPollOutcome::BeaconNodeNotVerified(slot)) => {
+                warn!(self.log, "Beacon node not verified, block not produced"; "slot" => slot)
+            }
+        };
+
+        match self.block_producer.check_published_blocks() {
+            Err(error) => {
+                error!(self.log, "Canonical block check error"; "error" => format!("{:?}", error))
+            }
+            Ok(checked) => {
+                for (slot, canonical) in checked {
+                    if canonical {
+                        info!(self.log, "Published block is canonical"; "slot" => slot)
+                    } else {
+                        warn!(self.log, "Published block is not canonical"; "slot" => slot)
+                    }
+                    self.metrics.inc_proposed_block(&validator, canonical);
                 }
-            };
+            }
+        }
+    }
+}
 
-            std::thread::sleep(Duration::from_millis(self.poll_interval_millis));
+/// Returns the name by which `result` is counted, if it is the final outcome of its slot.
+///
+/// Outcomes after which the slot is polled again (e.g., `BeaconNodeNotVerified`) are not counted,
+/// and `duties_unknown` is only counted once per slot, using `duties_unknown_slot`.
+fn counted_outcome(
+    result: &Result<BlockProducerPollOutcome, Error>,
+    duties_unknown_slot: &mut Option<u64>,
+) -> Option<&'static str> {
+    match result {
+        Ok(BlockProducerPollOutcome::BlockProduced(_)) => Some("block_produced"),
+        Ok(BlockProducerPollOutcome::SlashableBlockNotProduced(_)) => {
+            Some("slashable_not_produced")
+        }
+        Ok(BlockProducerPollOutcome::BeaconNodeUnableToProduceBlock(_)) => {
+            Some("beacon_node_unable_to_produce")
+        }
+        Ok(BlockProducerPollOutcome::ProducerDutiesUnknown(slot)) => {
+            if *duties_unknown_slot == Some(*slot) {
+                None
+            } else {
+                *duties_unknown_slot = Some(*slot);
+                Some("duties_unknown")
+            }
         }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn only_final_outcomes_are_counted() {
+        let mut duties_unknown_slot = None;
+        let mut count = |outcome| counted_outcome(&Ok(outcome), &mut duties_unknown_slot);
+
+        assert_eq!(
+            count(BlockProducerPollOutcome::ProducerDutiesUnknown(1)),
+            Some("duties_unknown")
+        );
+        assert_eq!(
+            count(BlockProducerPollOutcome::ProducerDutiesUnknown(1)),
+            None
+        );
+        assert_eq!(
+            count(BlockProducerPollOutcome::BeaconNodeNotVerified(2)),
+            None
+        );
+        assert_eq!(
+            count(BlockProducerPollOutcome::BlockProduced(2)),
+            Some("block_produced")
+        );
+        assert_eq!(
+            count(BlockProducerPollOutcome::SlotAlreadyProcessed(2)),
+            None
+        );
+        assert_eq!(
+            count(BlockProducerPollOutcome::BlockProductionNotRequired(3)),
+            None
+        );
     }
 }
@@ -1,17 +1,20 @@
 use super::traits::{BeaconNode, BeaconNodeError};
 use std::sync::RwLock;
-use types::BeaconBlock;
+use types::{BeaconBlock, Hash256, Signature};
 
 type ProduceResult = Result<Option<BeaconBlock>, BeaconNodeError>;
 type PublishResult = Result<bool, BeaconNodeError>;
+type CanonicalRootResult = Result<Option<Hash256>, BeaconNodeError>;
 
 /// A test-only struct used to simulate a Beacon Node.
 #[derive(Default)]
 pub struct TestBeaconNode {
-    pub produce_input: RwLock<Option<u64>>,
+    pub produce_input: RwLock<Option<(u64, Signature)>>,
     pub produce_result: RwLock<Option<ProduceResult>>,
     pub publish_input: RwLock<Option<BeaconBlock>>,
     pub publish_result: RwLock<Option<PublishResult>>,
+    pub canonical_root_input: RwLock<Option<u64>>,
+    pub canonical_root_result: RwLock<Option<CanonicalRootResult>>,
 }
 
 impl TestBeaconNode {
@@ -24,12 +27,17 @@ impl TestBeaconNode {
     pub fn set_next_publish_result(&self, result: PublishResult) {
         *self.publish_result.write().unwrap() = Some(result);
     }
+
+    /// Set the result to be returned when `canonical_block_root` is called.
+    pub fn set_next_canonical_root_result(&self, result: CanonicalRootResult) {
+        *self.canonical_root_result.write().unwrap() = Some(result);
+    }
 }
 
 impl BeaconNode for TestBeaconNode {
     /// Returns the value specified by the `set_next_produce_result`.
-    fn produce_beacon_block(&self, slot: u64) -> ProduceResult {
-        *self.produce_input.write().unwrap() = Some(slot);
+    fn produce_beacon_block(&self, slot: u64, randao_reveal: &Signature) -> ProduceResult {
+        *self.produce_input.write().unwrap() = Some((slot, randao_reveal.clone()));
         match *self.produce_result.read().unwrap() {
             Some(ref r) => r.clone(),
             None => panic!("TestBeaconNode: produce_result == None"),
@@ -44,4 +52,13 @@ impl BeaconNode for TestBeaconNode {
             None => panic!("TestBeaconNode: publish_result == None"),
         }
     }
+
+    /// Returns the value specified by the `set_next_canonical_root_result`.
+    fn canonical_block_root(&self, slot: u64) -> CanonicalRootResult {
+        *self.canonical_root_input.write().unwrap() = Some(slot);
+        match *self.canonical_root_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: canonical_root_result == None"),
+        }
+    }
 }
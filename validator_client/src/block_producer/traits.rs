@@ -1,4 +1,4 @@
-use types::BeaconBlock;
+use types::{BeaconBlock, Hash256, Signature};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum BeaconNodeError {
@@ -8,12 +8,22 @@ pub enum BeaconNodeError {
 
 /// Defines the methods required to produce and publish blocks on a Beacon Node.
 pub trait BeaconNode: Send + Sync {
-    /// Request that the node produces a block.
+    /// Request that the node produces a block, including the given `randao_reveal`.
     ///
     /// Returns Ok(None) if the Beacon Node is unable to produce at the given slot.
-    fn produce_beacon_block(&self, slot: u64) -> Result<Option<BeaconBlock>, BeaconNodeError>;
+    fn produce_beacon_block(
+        &self,
+        slot: u64,
+        randao_reveal: &Signature,
+    ) -> Result<Option<BeaconBlock>, BeaconNodeError>;
     /// Request that the node publishes a block.
     ///
     /// Returns `true` if the publish was sucessful.
     fn publish_beacon_block(&self, block: BeaconBlock) -> Result<bool, BeaconNodeError>;
+    /// Request the root of the block in the canonical chain of the node at `slot`.
+    ///
+    /// Returns Ok(None) if there is no block at that slot in the canonical chain. Returns an error
+    /// if this is not yet known (e.g., the slot is later than the head of the node), so that the
+    /// block is not mistaken for an orphan.
+    fn canonical_block_root(&self, slot: u64) -> Result<Option<Hash256>, BeaconNodeError>;
 }
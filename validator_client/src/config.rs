@@ -1,3 +1,4 @@
+use crate::metrics::MetricsConfig;
 use std::fs;
 use std::path::PathBuf;
 
@@ -5,10 +6,33 @@ use std::path::PathBuf;
 #[derive(Clone)]
 pub struct ClientConfig {
     pub data_dir: PathBuf,
-    pub server: String,
+    /// Beacon Node addresses (`host:port`), in order of preference.
+    pub servers: Vec<String>,
+    pub server_tls: Option<ClientTlsConfig>,
+    /// The address (`host:port`) of a remote signing service. If `None`, the local keystores are
+    /// used.
+    pub remote_signer: Option<String>,
+    pub remote_signer_tls: Option<ClientTlsConfig>,
+    /// The maximum number of slots the head of the Beacon Node may be behind the present slot
+    /// before the validator refuses to sign.
+    pub max_head_distance: u64,
+    /// The number of epochs to watch for the validators being live elsewhere before signing. If
+    /// `0`, detection is disabled.
+    pub doppelganger_epochs: u64,
+    pub metrics: MetricsConfig,
+}
+
+/// Paths to the PEM-encoded files required to connect to a Beacon Node or remote signer over TLS.
+#[derive(Clone)]
+pub struct ClientTlsConfig {
+    /// The CA used to verify the server's certificate.
+    pub server_ca: PathBuf,
+    /// A certificate and private key to present to the server (i.e., mutual TLS).
+    pub client_cert_and_key: Option<(PathBuf, PathBuf)>,
 }
 
 const DEFAULT_LIGHTHOUSE_DIR: &str = ".lighthouse-validators";
+const DEFAULT_MAX_HEAD_DISTANCE: u64 = 4;
 
 impl ClientConfig {
     /// Build a new configuration from defaults.
@@ -19,7 +43,42 @@ impl ClientConfig {
         };
         fs::create_dir_all(&data_dir)
             .unwrap_or_else(|_| panic!("Unable to create {:?}", &data_dir));
-        let server = "localhost:50051".to_string();
-        Self { data_dir, server }
+        let servers = vec!["localhost:50051".to_string()];
+        Self {
+            data_dir,
+            servers,
+            server_tls: None,
+            remote_signer: None,
+            remote_signer_tls: None,
+            max_head_distance: DEFAULT_MAX_HEAD_DISTANCE,
+            doppelganger_epochs: 0,
+            metrics: MetricsConfig::default(),
+        }
+    }
+}
+
+/// Returns `true` if `address` is of the form `host:port`.
+pub fn is_valid_server_address(address: &str) -> bool {
+    let mut split = address.rsplitn(2, ':');
+    match (split.next(), split.next()) {
+        (Some(port), Some(host)) => !host.is_empty() && port.parse::<u16>().is_ok(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_server_address() {
+        assert!(is_valid_server_address("localhost:50051"));
+        assert!(is_valid_server_address("10.0.0.1:5052"));
+        assert!(is_valid_server_address("[::1]:50051"));
+
+        assert!(!is_valid_server_address("50051"));
+        assert!(!is_valid_server_address(":50051"));
+        assert!(!is_valid_server_address("localhost:"));
+        assert!(!is_valid_server_address("localhost:99999"));
     }
 }
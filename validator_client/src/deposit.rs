@@ -0,0 +1,69 @@
+use bls::{create_proof_of_possession, Keypair, PublicKey};
+use hashing::canonical_hash;
+use spec::ChainSpec;
+use ssz::ssz_encode;
+use types::{DepositData, DepositInput, Hash256};
+
+/// Returns the `DepositData` with which the validator of `keypair` may join the registry,
+/// withdrawable by the holder of `withdrawal_pubkey`.
+///
+/// The `timestamp` is set by the deposit contract, so is only a local estimate here.
+pub fn deposit_data(
+    keypair: &Keypair,
+    withdrawal_pubkey: &PublicKey,
+    amount: u64,
+    timestamp: u64,
+    spec: &ChainSpec,
+) -> DepositData {
+    DepositData {
+        amount,
+        timestamp,
+        deposit_input: DepositInput {
+            pubkey: keypair.pk.clone(),
+            withdrawal_credentials: bls_withdrawal_credentials(withdrawal_pubkey, spec),
+            proof_of_possession: create_proof_of_possession(keypair),
+        },
+    }
+}
+
+/// Returns the withdrawal credentials for a BLS withdrawal key: the `bls_withdrawal_prefix_byte`
+/// followed by the last 31 bytes of the hash of the key.
+pub fn bls_withdrawal_credentials(withdrawal_pubkey: &PublicKey, spec: &ChainSpec) -> Hash256 {
+    let mut credentials = canonical_hash(&ssz_encode(withdrawal_pubkey));
+    credentials[0] = spec.bls_withdrawal_prefix_byte;
+    Hash256::from(&credentials[..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls::verify_proof_of_possession;
+
+    #[test]
+    pub fn deposit_data_is_valid() {
+        let spec = ChainSpec::foundation();
+        let keypair = Keypair::random();
+        let withdrawal_keypair = Keypair::random();
+
+        let data = deposit_data(
+            &keypair,
+            &withdrawal_keypair.pk,
+            spec.max_deposit,
+            42,
+            &spec,
+        );
+        let input = &data.deposit_input;
+
+        assert_eq!(data.amount, spec.max_deposit);
+        assert_eq!(input.pubkey, keypair.pk);
+        assert!(verify_proof_of_possession(
+            &input.proof_of_possession,
+            &input.pubkey
+        ));
+
+        let credentials = input.withdrawal_credentials.to_vec();
+        let hash = canonical_hash(&ssz_encode(&withdrawal_keypair.pk));
+        assert_eq!(credentials[0], spec.bls_withdrawal_prefix_byte);
+        assert_eq!(credentials[1..], hash[1..]);
+    }
+}
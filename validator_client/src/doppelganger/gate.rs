@@ -0,0 +1,47 @@
+use bls::PublicKey;
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+/// The set of validators which are permitted to sign, shared between the services which sign and
+/// the doppelganger detection which enables them.
+///
+/// Validators are disabled until enabled; if the set is poisoned, all validators are disabled.
+#[derive(Default)]
+pub struct SigningGate {
+    enabled: RwLock<HashSet<PublicKey>>,
+}
+
+impl SigningGate {
+    /// Permit the validator with `pubkey` to sign.
+    pub fn enable(&self, pubkey: PublicKey) {
+        if let Ok(mut enabled) = self.enabled.write() {
+            enabled.insert(pubkey);
+        }
+    }
+
+    /// Returns `true` if the validator with `pubkey` may sign.
+    pub fn is_enabled(&self, pubkey: &PublicKey) -> bool {
+        self.enabled
+            .read()
+            .map(|enabled| enabled.contains(pubkey))
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls::Keypair;
+
+    #[test]
+    pub fn validators_are_disabled_until_enabled() {
+        let gate = SigningGate::default();
+        let a = Keypair::random().pk;
+        let b = Keypair::random().pk;
+
+        assert!(!gate.is_enabled(&a));
+        gate.enable(a.clone());
+        assert!(gate.is_enabled(&a));
+        assert!(!gate.is_enabled(&b));
+    }
+}
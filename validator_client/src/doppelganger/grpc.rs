@@ -0,0 +1,26 @@
+use super::traits::{BeaconNode, BeaconNodeError};
+use grpcio::{Error as GrpcError, RpcStatusCode};
+use protos::services::ValidatorLivenessRequest;
+use protos::services_grpc::ValidatorServiceClient;
+
+impl BeaconNode for ValidatorServiceClient {
+    /// Request the liveness of some validators during `epoch` from a Beacon Node (BN).
+    fn validator_liveness(
+        &self,
+        epoch: u64,
+        validator_indices: &[u64],
+    ) -> Result<Vec<u64>, BeaconNodeError> {
+        let mut req = ValidatorLivenessRequest::new();
+        req.set_epoch(epoch);
+        req.set_validator_indices(validator_indices.to_vec());
+
+        let reply = self.validator_liveness(&req).map_err(|err| match err {
+            GrpcError::RpcFailure(ref status) if status.status == RpcStatusCode::Unimplemented => {
+                BeaconNodeError::Unimplemented
+            }
+            err => BeaconNodeError::RemoteFailure(format!("{:?}", err)),
+        })?;
+
+        Ok(reply.get_live_indices().to_vec())
+    }
+}
@@ -0,0 +1,269 @@
+mod gate;
+mod grpc;
+mod service;
+#[cfg(test)]
+mod test_node;
+mod traits;
+
+use super::EpochDutiesMap;
+use bls::PublicKey;
+use slot_clock::SlotClock;
+use spec::ChainSpec;
+use std::sync::{Arc, RwLock};
+
+pub use self::gate::SigningGate;
+pub use self::service::DoppelgangerService;
+pub use self::traits::{BeaconNode, BeaconNodeError};
+
+#[derive(Debug, PartialEq)]
+pub enum PollOutcome {
+    /// The next epoch to be checked has not yet ended.
+    AwaitingEpoch(u64),
+    /// The next epoch has ended, but it cannot be checked until the validator index of each of
+    /// the given validators is known.
+    AwaitingIndices(u64, Vec<PublicKey>),
+    /// The epoch was checked, the given validators were found to be live during it.
+    EpochChecked(u64, Vec<PublicKey>),
+    /// All epochs have been checked.
+    Complete,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    SlotClockError,
+    SlotUnknowable,
+    EpochMapPoisoned,
+    SlotClockPoisoned,
+    EpochLengthIsZero,
+    BeaconNodeError(BeaconNodeError),
+}
+
+/// A validator being watched for signs of another instance.
+struct Validator {
+    pubkey: PublicKey,
+    duties_map: Arc<RwLock<EpochDutiesMap>>,
+    /// The first epoch in which the validator was seen live, if any.
+    live_epoch: Option<u64>,
+}
+
+/// A polling state machine which detects whether the validators are already being run elsewhere
+/// (a "doppelganger"), prior to them signing any messages.
+///
+/// The `detection_epochs` epochs following the epoch in which the detector was created are
+/// checked once each has ended. The epoch of creation is not checked, as it may contain messages
+/// signed by this client prior to a restart.
+///
+/// Validator indices are read from the `EpochDutiesMap` of each validator, relying upon an
+/// external service to keep it updated. No epoch is checked until the index of every validator
+/// is known, so detection does not complete while any validator's duties are yet to be loaded.
+pub struct DoppelgangerDetector<T: SlotClock, U: BeaconNode> {
+    spec: Arc<ChainSpec>,
+    slot_clock: Arc<RwLock<T>>,
+    beacon_node: Arc<U>,
+    validators: Vec<Validator>,
+    /// The next epoch to be checked.
+    next_epoch: u64,
+    /// The last epoch to be checked.
+    last_epoch: u64,
+}
+
+impl<T: SlotClock, U: BeaconNode> DoppelgangerDetector<T, U> {
+    /// Returns a new detector for the given `(pubkey, duties_map)` pairs, which checks the
+    /// `detection_epochs` epochs following the present epoch.
+    pub fn new(
+        spec: Arc<ChainSpec>,
+        slot_clock: Arc<RwLock<T>>,
+        beacon_node: Arc<U>,
+        validators: Vec<(PublicKey, Arc<RwLock<EpochDutiesMap>>)>,
+        detection_epochs: u64,
+    ) -> Result<Self, Error> {
+        let epoch = present_epoch(&spec, &slot_clock)?;
+        let validators = validators
+            .into_iter()
+            .map(|(pubkey, duties_map)| Validator {
+                pubkey,
+                duties_map,
+                live_epoch: None,
+            })
+            .collect();
+
+        Ok(Self {
+            spec,
+            slot_clock,
+            beacon_node,
+            validators,
+            next_epoch: epoch + 1,
+            last_epoch: epoch + detection_epochs,
+        })
+    }
+
+    /// "Poll" to see if another epoch may be checked.
+    ///
+    /// The slot clock will be read and, if the next epoch has ended, the Beacon Node is asked
+    /// which of the validators were live during it.
+    pub fn poll(&mut self) -> Result<PollOutcome, Error> {
+        if self.is_complete() {
+            return Ok(PollOutcome::Complete);
+        }
+
+        let epoch = present_epoch(&self.spec, &self.slot_clock)?;
+        if epoch <= self.next_epoch {
+            return Ok(PollOutcome::AwaitingEpoch(self.next_epoch));
+        }
+
+        let indices = self.validator_indices()?;
+        let indices = match indices.iter().cloned().collect::<Option<Vec<u64>>>() {
+            Some(indices) => indices,
+            None => {
+                let unresolved = self
+                    .validators
+                    .iter()
+                    .zip(&indices)
+                    .filter(|(_, index)| index.is_none())
+                    .map(|(validator, _)| validator.pubkey.clone())
+                    .collect();
+                return Ok(PollOutcome::AwaitingIndices(self.next_epoch, unresolved));
+            }
+        };
+        let live_indices = self
+            .beacon_node
+            .validator_liveness(self.next_epoch, &indices)?;
+
+        let checked_epoch = self.next_epoch;
+        let mut newly_live = vec![];
+        for (validator, index) in self.validators.iter_mut().zip(indices) {
+            if live_indices.contains(&index) && validator.live_epoch.is_none() {
+                validator.live_epoch = Some(checked_epoch);
+                newly_live.push(validator.pubkey.clone());
+            }
+        }
+        self.next_epoch += 1;
+
+        Ok(PollOutcome::EpochChecked(checked_epoch, newly_live))
+    }
+
+    /// Returns `true` if all epochs have been checked.
+    pub fn is_complete(&self) -> bool {
+        self.next_epoch > self.last_epoch
+    }
+
+    /// Returns the validators which have not been seen live elsewhere, and so may be started.
+    pub fn not_live(&self) -> Vec<PublicKey> {
+        self.validators
+            .iter()
+            .filter(|v| v.live_epoch.is_none())
+            .map(|v| v.pubkey.clone())
+            .collect()
+    }
+
+    /// Returns the validator index of each validator, if known.
+    fn validator_indices(&self) -> Result<Vec<Option<u64>>, Error> {
+        let mut indices = vec![];
+        for validator in &self.validators {
+            let duties_map = validator
+                .duties_map
+                .read()
+                .map_err(|_| Error::EpochMapPoisoned)?;
+            indices.push(
+                duties_map
+                    .values()
+                    .next()
+                    .map(|duties| duties.validator_index),
+            );
+        }
+        Ok(indices)
+    }
+}
+
+fn present_epoch<T: SlotClock>(spec: &ChainSpec, slot_clock: &RwLock<T>) -> Result<u64, Error> {
+    let slot = slot_clock
+        .read()
+        .map_err(|_| Error::SlotClockPoisoned)?
+        .present_slot()
+        .map_err(|_| Error::SlotClockError)?
+        .ok_or(Error::SlotUnknowable)?;
+
+    slot.checked_div(spec.epoch_length)
+        .ok_or(Error::EpochLengthIsZero)
+}
+
+impl From<BeaconNodeError> for Error {
+    fn from(e: BeaconNodeError) -> Error {
+        Error::BeaconNodeError(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_node::TestBeaconNode;
+    use super::*;
+    use crate::duties::EpochDuties;
+    use bls::Keypair;
+    use slot_clock::TestingSlotClock;
+
+    #[test]
+    pub fn polling() {
+        let spec = Arc::new(ChainSpec::foundation());
+        let start_slot = 2 * spec.epoch_length + 1;
+        let slot_clock = Arc::new(RwLock::new(TestingSlotClock::new(start_slot)));
+        let beacon_node = Arc::new(TestBeaconNode::default());
+
+        // One validator with a known index, one without.
+        let a = Keypair::random().pk;
+        let b = Keypair::random().pk;
+        let a_duties = Arc::new(RwLock::new(EpochDutiesMap::new()));
+        let duties = EpochDuties {
+            validator_index: 7,
+            ..std::default::Default::default()
+        };
+        a_duties.write().unwrap().insert(2, duties);
+        let b_duties = Arc::new(RwLock::new(EpochDutiesMap::new()));
+
+        let mut detector = DoppelgangerDetector::new(
+            spec.clone(),
+            slot_clock.clone(),
+            beacon_node.clone(),
+            vec![(a.clone(), a_duties), (b.clone(), b_duties.clone())],
+            2,
+        )
+        .unwrap();
+
+        // The epoch of creation is not checked, nor the next epoch until it has ended.
+        assert_eq!(detector.poll(), Ok(PollOutcome::AwaitingEpoch(3)));
+        slot_clock.write().unwrap().set_slot(3 * spec.epoch_length);
+        assert_eq!(detector.poll(), Ok(PollOutcome::AwaitingEpoch(3)));
+
+        // Once ended, the epoch is not checked until the index of each validator is known...
+        beacon_node.set_next_liveness_result(Ok(vec![]));
+        slot_clock.write().unwrap().set_slot(4 * spec.epoch_length);
+        assert_eq!(
+            detector.poll(),
+            Ok(PollOutcome::AwaitingIndices(3, vec![b.clone()]))
+        );
+        assert_eq!(*beacon_node.liveness_input.read().unwrap(), None);
+        assert!(!detector.is_complete());
+
+        // ...then an epoch without activity is checked...
+        let duties = EpochDuties {
+            validator_index: 8,
+            ..std::default::Default::default()
+        };
+        b_duties.write().unwrap().insert(2, duties);
+        assert_eq!(detector.poll(), Ok(PollOutcome::EpochChecked(3, vec![])));
+        assert_eq!(
+            *beacon_node.liveness_input.read().unwrap(),
+            Some((3, vec![7, 8]))
+        );
+
+        // ...and activity by a validator is detected.
+        beacon_node.set_next_liveness_result(Ok(vec![7]));
+        slot_clock.write().unwrap().set_slot(5 * spec.epoch_length);
+        assert_eq!(
+            detector.poll(),
+            Ok(PollOutcome::EpochChecked(4, vec![a.clone()]))
+        );
+
+        assert_eq!(detector.poll(), Ok(PollOutcome::Complete));
+        assert_eq!(detector.not_live(), vec![b]);
+    }
+}
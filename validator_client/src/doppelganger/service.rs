@@ -0,0 +1,79 @@
+use super::traits::{BeaconNode, BeaconNodeError};
+use super::{DoppelgangerDetector, Error, PollOutcome, SigningGate};
+use slog::{debug, error, info, warn, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+
+pub struct DoppelgangerService<T: SlotClock, U: BeaconNode> {
+    detector: DoppelgangerDetector<T, U>,
+    gate: Arc<SigningGate>,
+    log: Logger,
+    /// Set once detection has finished, whether or not any validators were enabled.
+    finished: bool,
+}
+
+impl<T: SlotClock, U: BeaconNode> DoppelgangerService<T, U> {
+    /// Returns a service which enables validators on the `gate` once the `detector` has
+    /// completed without seeing them live elsewhere.
+    pub fn new(detector: DoppelgangerDetector<T, U>, gate: Arc<SigningGate>, log: Logger) -> Self {
+        Self {
+            detector,
+            gate,
+            log,
+            finished: false,
+        }
+    }
+
+    /// Poll the detector once, logging the outcome.
+    ///
+    /// Once detection is complete, the validators which were not seen live elsewhere are enabled.
+    /// If the Beacon Node does not track liveness, detection can never complete and no validator
+    /// is enabled.
+    pub fn poll(&mut self) {
+        if self.finished {
+            return;
+        }
+
+        match self.detector.poll() {
+            Err(Error::BeaconNodeError(BeaconNodeError::Unimplemented)) => {
+                error!(
+                    self.log,
+                    "Beacon node does not track validator liveness, validators will not be started"
+                );
+                self.finished = true;
+            }
+            Err(error) => {
+                error!(self.log, "Doppelganger detection poll error"; "error" => format!("{:?}", error))
+            }
+            Ok(PollOutcome::AwaitingEpoch(epoch)) => {
+                debug!(self.log, "Awaiting the end of epoch for doppelganger detection"; "epoch" => epoch)
+            }
+            Ok(PollOutcome::AwaitingIndices(epoch, validators)) => {
+                for pubkey in validators {
+                    warn!(self.log, "Awaiting validator index for doppelganger detection"; "validator" => pubkey.concatenated_hex_id(), "epoch" => epoch)
+                }
+            }
+            Ok(PollOutcome::EpochChecked(epoch, live)) => {
+                info!(self.log, "Checked epoch for doppelgangers"; "epoch" => epoch);
+                for pubkey in live {
+                    error!(self.log, "Validator is live elsewhere, it will not be started"; "validator" => pubkey.concatenated_hex_id(), "epoch" => epoch)
+                }
+            }
+            Ok(PollOutcome::Complete) => {
+                let validators = self.detector.not_live();
+                if validators.is_empty() {
+                    error!(
+                        self.log,
+                        "Doppelganger detection complete, all validators are live elsewhere"
+                    );
+                } else {
+                    info!(self.log, "Doppelganger detection complete, starting validators"; "validators" => validators.len());
+                }
+                for pubkey in validators {
+                    self.gate.enable(pubkey);
+                }
+                self.finished = true;
+            }
+        };
+    }
+}
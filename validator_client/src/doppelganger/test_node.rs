@@ -0,0 +1,29 @@
+use super::traits::{BeaconNode, BeaconNodeError};
+use std::sync::RwLock;
+
+type LivenessResult = Result<Vec<u64>, BeaconNodeError>;
+
+/// A test-only struct used to simulate a Beacon Node.
+#[derive(Default)]
+pub struct TestBeaconNode {
+    pub liveness_input: RwLock<Option<(u64, Vec<u64>)>>,
+    pub liveness_result: RwLock<Option<LivenessResult>>,
+}
+
+impl TestBeaconNode {
+    /// Set the result to be returned when `validator_liveness` is called.
+    pub fn set_next_liveness_result(&self, result: LivenessResult) {
+        *self.liveness_result.write().unwrap() = Some(result);
+    }
+}
+
+impl BeaconNode for TestBeaconNode {
+    /// Returns the value specified by the `set_next_liveness_result`.
+    fn validator_liveness(&self, epoch: u64, validator_indices: &[u64]) -> LivenessResult {
+        *self.liveness_input.write().unwrap() = Some((epoch, validator_indices.to_vec()));
+        match *self.liveness_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: liveness_result == None"),
+        }
+    }
+}
@@ -0,0 +1,17 @@
+#[derive(Debug, PartialEq, Clone)]
+pub enum BeaconNodeError {
+    RemoteFailure(String),
+    /// The Beacon Node does not track validator liveness, so doppelgangers cannot be detected.
+    Unimplemented,
+}
+
+/// Defines the methods required to detect other instances of a validator on a Beacon Node.
+pub trait BeaconNode: Send + Sync {
+    /// Returns the subset of `validator_indices` which produced a block or attestation during
+    /// `epoch`.
+    fn validator_liveness(
+        &self,
+        epoch: u64,
+        validator_indices: &[u64],
+    ) -> Result<Vec<u64>, BeaconNodeError>;
+}
@@ -1,17 +1,17 @@
 use super::traits::{BeaconNode, BeaconNodeError};
-use super::EpochDuties;
-use protos::services::{ProposeBlockSlotRequest, PublicKey as IndexRequest};
+use super::{AttestationDuty, EpochDuties};
+use protos::services::{
+    AttestationDutyRequest, ProposeBlockSlotRequest, PublicKey as IndexRequest,
+};
 use protos::services_grpc::ValidatorServiceClient;
 use ssz::ssz_encode;
-use types::PublicKey;
+use types::{Hash256, PublicKey};
 
 impl BeaconNode for ValidatorServiceClient {
     /// Request the shuffling from the Beacon Node (BN).
     ///
     /// As this function takes a `PublicKey`, it will first attempt to resolve the public key into
     /// a validator index, then call the BN for production/attestation duties.
-    ///
-    /// Note: presently only block production information is returned.
     fn request_shuffling(
         &self,
         epoch: u64,
@@ -35,15 +35,61 @@ impl BeaconNode for ValidatorServiceClient {
             .propose_block_slot(&req)
             .map_err(|err| BeaconNodeError::RemoteFailure(format!("{:?}", err)))?;
 
+        let dependent_root = decode_hash(reply.get_dependent_root())?;
         let block_production_slot = if reply.has_slot() {
             Some(reply.get_slot())
         } else {
             None
         };
 
+        let mut req = AttestationDutyRequest::new();
+        req.set_validator_index(validator_index);
+        req.set_epoch(epoch);
+
+        let reply = self
+            .attestation_duty(&req)
+            .map_err(|err| BeaconNodeError::RemoteFailure(format!("{:?}", err)))?;
+
+        // The shuffling may have changed between requests; the duties would be inconsistent.
+        if decode_hash(reply.get_dependent_root())? != dependent_root {
+            return Err(BeaconNodeError::RemoteFailure(
+                "Dependent root changed between requests".to_string(),
+            ));
+        }
+
+        let attestation_duty = if reply.has_duty() {
+            let duty = reply.get_duty();
+            Some(AttestationDuty {
+                slot: duty.get_slot(),
+                shard: duty.get_shard(),
+            })
+        } else {
+            None
+        };
+
         Ok(Some(EpochDuties {
             validator_index,
             block_production_slot,
+            attestation_duty,
+            dependent_root,
         }))
     }
 }
+
+/// Decode a 32-byte root, failing on any other length.
+///
+/// A zero root is never the root of a block, so it is rejected; duties without a known dependent
+/// block cannot be invalidated by a re-org.
+fn decode_hash(bytes: &[u8]) -> Result<Hash256, BeaconNodeError> {
+    if bytes.len() != 32 {
+        return Err(BeaconNodeError::DecodeFailure);
+    }
+    let root = Hash256::from(bytes);
+    if root.is_zero() {
+        Err(BeaconNodeError::RemoteFailure(
+            "Beacon Node returned a zero dependent root".to_string(),
+        ))
+    } else {
+        Ok(root)
+    }
+}
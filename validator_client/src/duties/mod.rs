@@ -4,14 +4,15 @@ mod service;
 mod test_node;
 mod traits;
 
-use self::traits::{BeaconNode, BeaconNodeError};
 use bls::PublicKey;
 use slot_clock::SlotClock;
 use spec::ChainSpec;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
+use types::Hash256;
 
 pub use self::service::DutiesManagerService;
+pub use self::traits::{BeaconNode, BeaconNodeError};
 
 /// The information required for a validator to propose and attest during some epoch.
 ///
@@ -22,7 +23,17 @@ pub use self::service::DutiesManagerService;
 pub struct EpochDuties {
     pub validator_index: u64,
     pub block_production_slot: Option<u64>,
-    // Future shard info
+    pub attestation_duty: Option<AttestationDuty>,
+    /// The root of the block upon which the shuffling for the epoch depends. If it is re-orged
+    /// out, the duties are invalid.
+    pub dependent_root: Hash256,
+}
+
+/// The slot in which a validator should attest, and the shard of the committee it attests with.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct AttestationDuty {
+    pub slot: u64,
+    pub shard: u64,
 }
 
 impl EpochDuties {
@@ -34,9 +45,22 @@ impl EpochDuties {
             _ => false,
         }
     }
+
+    /// Returns the `AttestationDuty` if the supplied `slot` is a slot in which the validator should
+    /// attest.
+    pub fn attestation_duty_at(&self, slot: u64) -> Option<AttestationDuty> {
+        match self.attestation_duty {
+            Some(duty) if duty.slot == slot => Some(duty),
+            _ => None,
+        }
+    }
 }
 
 /// Maps an `epoch` to some `EpochDuties` for a single validator.
+///
+/// Entries are only valid whilst their `dependent_root` remains canonical; the `DutiesManager`
+/// evicts all entries of a root once the Beacon Node reports a different one, and any entry which
+/// its latest poll could not confirm.
 pub type EpochDutiesMap = HashMap<u64, EpochDuties>;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -66,6 +90,9 @@ pub enum Error {
 /// A polling state machine which ensures the latest `EpochDuties` are obtained from the Beacon
 /// Node.
 ///
+/// The duties of the present epoch are polled, along with those of the next epoch once the
+/// `seed_lookahead` has made them knowable. Duties of past epochs are pruned.
+///
 /// There is a single `DutiesManager` per validator instance.
 pub struct DutiesManager<T: SlotClock, U: BeaconNode> {
     pub duties_map: Arc<RwLock<EpochDutiesMap>>,
@@ -77,11 +104,14 @@ pub struct DutiesManager<T: SlotClock, U: BeaconNode> {
 }
 
 impl<T: SlotClock, U: BeaconNode> DutiesManager<T, U> {
-    /// Poll the Beacon Node for `EpochDuties`.
+    /// Poll the Beacon Node for `EpochDuties`, returning an outcome for each epoch polled.
     ///
     /// The present `epoch` will be learned from the supplied `SlotClock`. In production this will
     /// be a wall-clock (e.g., system time, remote server time, etc.).
-    pub fn poll(&self) -> Result<PollOutcome, Error> {
+    ///
+    /// The dependent root of every known epoch is re-checked each poll. If the Beacon Node cannot
+    /// be reached, all duties are forgotten rather than risk serving those of a re-orged chain.
+    pub fn poll(&self) -> Result<Vec<PollOutcome>, Error> {
         let slot = self
             .slot_clock
             .read()
@@ -94,6 +124,34 @@ impl<T: SlotClock, U: BeaconNode> DutiesManager<T, U> {
             .checked_div(self.spec.epoch_length)
             .ok_or(Error::EpochLengthIsZero)?;
 
+        self.prune(epoch)?;
+
+        let mut epochs = vec![epoch];
+        let next_epoch_start = (epoch + 1) * self.spec.epoch_length;
+        if slot + self.spec.seed_lookahead >= next_epoch_start {
+            epochs.push(epoch + 1);
+        }
+
+        let mut outcomes = Vec::with_capacity(epochs.len());
+        for epoch in epochs {
+            match self.poll_epoch(epoch) {
+                Ok(outcome) => outcomes.push(outcome),
+                Err(e) => {
+                    self.duties_map
+                        .write()
+                        .map_err(|_| Error::EpochMapPoisoned)?
+                        .clear();
+                    return Err(e);
+                }
+            }
+        }
+        Ok(outcomes)
+    }
+
+    /// Poll the Beacon Node for the `EpochDuties` of a single epoch, updating the map.
+    ///
+    /// If the Beacon Node no longer knows the duties, any previously known duties are removed.
+    fn poll_epoch(&self, epoch: u64) -> Result<PollOutcome, Error> {
         if let Some(duties) = self.beacon_node.request_shuffling(epoch, &self.pubkey)? {
             let mut map = self
                 .duties_map
@@ -101,21 +159,37 @@ impl<T: SlotClock, U: BeaconNode> DutiesManager<T, U> {
                 .map_err(|_| Error::EpochMapPoisoned)?;
 
             // If these duties were known, check to see if they're updates or identical.
-            let result = if let Some(known_duties) = map.get(&epoch) {
-                if *known_duties == duties {
-                    Ok(PollOutcome::NoChange(epoch))
-                } else {
-                    Ok(PollOutcome::DutiesChanged(epoch, duties))
+            let result = match map.get(&epoch).cloned() {
+                Some(known_duties) if known_duties == duties => PollOutcome::NoChange(epoch),
+                Some(known_duties) => {
+                    // The dependent block has been re-orged out; so have the duties of every other
+                    // epoch which depended upon it.
+                    if known_duties.dependent_root != duties.dependent_root {
+                        map.retain(|_, d| d.dependent_root != known_duties.dependent_root);
+                    }
+                    PollOutcome::DutiesChanged(epoch, duties)
                 }
-            } else {
-                Ok(PollOutcome::NewDuties(epoch, duties))
+                None => PollOutcome::NewDuties(epoch, duties),
             };
             map.insert(epoch, duties);
-            result
+            Ok(result)
         } else {
+            self.duties_map
+                .write()
+                .map_err(|_| Error::EpochMapPoisoned)?
+                .remove(&epoch);
             Ok(PollOutcome::UnknownValidatorOrEpoch(epoch))
         }
     }
+
+    /// Remove the duties of all epochs prior to `epoch`.
+    fn prune(&self, epoch: u64) -> Result<(), Error> {
+        self.duties_map
+            .write()
+            .map_err(|_| Error::EpochMapPoisoned)?
+            .retain(|e, _| *e >= epoch);
+        Ok(())
+    }
 }
 
 impl From<BeaconNodeError> for Error {
@@ -136,9 +210,16 @@ mod tests {
     //
     // These tests should serve as a good example for future tests.
 
+    /// Returns a spec in which the next epoch is only knowable in the last quarter of an epoch.
+    fn spec() -> Arc<ChainSpec> {
+        let mut spec = ChainSpec::foundation();
+        spec.seed_lookahead = spec.epoch_length / 4;
+        Arc::new(spec)
+    }
+
     #[test]
     pub fn polling() {
-        let spec = Arc::new(ChainSpec::foundation());
+        let spec = spec();
         let duties_map = Arc::new(RwLock::new(EpochDutiesMap::new()));
         let keypair = Keypair::random();
         let slot_clock = Arc::new(RwLock::new(TestingSlotClock::new(0)));
@@ -156,24 +237,126 @@ mod tests {
         let duties = EpochDuties {
             validator_index: 0,
             block_production_slot: Some(10),
+            attestation_duty: Some(AttestationDuty { slot: 12, shard: 1 }),
+            dependent_root: Hash256::zero(),
         };
         beacon_node.set_next_shuffling_result(Ok(Some(duties)));
 
         // Get the duties for the first time...
-        assert_eq!(manager.poll(), Ok(PollOutcome::NewDuties(0, duties)));
+        assert_eq!(manager.poll(), Ok(vec![PollOutcome::NewDuties(0, duties)]));
         // Get the same duties again...
-        assert_eq!(manager.poll(), Ok(PollOutcome::NoChange(0)));
+        assert_eq!(manager.poll(), Ok(vec![PollOutcome::NoChange(0)]));
 
         // Return new duties.
         let duties = EpochDuties {
             validator_index: 0,
             block_production_slot: Some(11),
+            attestation_duty: Some(AttestationDuty { slot: 12, shard: 1 }),
+            dependent_root: Hash256::zero(),
         };
         beacon_node.set_next_shuffling_result(Ok(Some(duties)));
-        assert_eq!(manager.poll(), Ok(PollOutcome::DutiesChanged(0, duties)));
+        assert_eq!(
+            manager.poll(),
+            Ok(vec![PollOutcome::DutiesChanged(0, duties)])
+        );
 
-        // Return no duties.
+        // Return no duties, forgetting those previously known.
         beacon_node.set_next_shuffling_result(Ok(None));
-        assert_eq!(manager.poll(), Ok(PollOutcome::UnknownValidatorOrEpoch(0)));
+        assert_eq!(
+            manager.poll(),
+            Ok(vec![PollOutcome::UnknownValidatorOrEpoch(0)])
+        );
+        assert!(duties_map.read().unwrap().is_empty());
+    }
+
+    #[test]
+    pub fn unconfirmed_duties_are_forgotten() {
+        let spec = spec();
+        let duties_map = Arc::new(RwLock::new(EpochDutiesMap::new()));
+        let keypair = Keypair::random();
+        let lookahead_slot = spec.epoch_length - spec.seed_lookahead;
+        let slot_clock = Arc::new(RwLock::new(TestingSlotClock::new(lookahead_slot)));
+        let beacon_node = Arc::new(TestBeaconNode::default());
+
+        let manager = DutiesManager {
+            spec: spec.clone(),
+            pubkey: keypair.pk.clone(),
+            duties_map: duties_map.clone(),
+            slot_clock: slot_clock.clone(),
+            beacon_node: beacon_node.clone(),
+        };
+
+        let duties = EpochDuties {
+            validator_index: 0,
+            dependent_root: Hash256::from(&[1; 32][..]),
+            ..EpochDuties::default()
+        };
+        beacon_node.set_next_shuffling_result(Ok(Some(duties)));
+        assert_eq!(
+            manager.poll(),
+            Ok(vec![
+                PollOutcome::NewDuties(0, duties),
+                PollOutcome::NewDuties(1, duties)
+            ])
+        );
+
+        // The dependent root cannot be re-checked, so none of the duties are served.
+        let failure = BeaconNodeError::RemoteFailure("unreachable".to_string());
+        beacon_node.set_next_shuffling_result(Err(failure.clone()));
+        assert_eq!(manager.poll(), Err(Error::BeaconNodeError(failure)));
+        assert!(duties_map.read().unwrap().is_empty());
+    }
+
+    #[test]
+    pub fn lookahead_reorgs_and_pruning() {
+        let spec = spec();
+        let duties_map = Arc::new(RwLock::new(EpochDutiesMap::new()));
+        let keypair = Keypair::random();
+        let lookahead_slot = spec.epoch_length - spec.seed_lookahead;
+        let slot_clock = Arc::new(RwLock::new(TestingSlotClock::new(lookahead_slot - 1)));
+        let beacon_node = Arc::new(TestBeaconNode::default());
+
+        let manager = DutiesManager {
+            spec: spec.clone(),
+            pubkey: keypair.pk.clone(),
+            duties_map: duties_map.clone(),
+            slot_clock: slot_clock.clone(),
+            beacon_node: beacon_node.clone(),
+        };
+
+        let a = EpochDuties {
+            validator_index: 0,
+            dependent_root: Hash256::from(&[1; 32][..]),
+            ..EpochDuties::default()
+        };
+        beacon_node.set_next_shuffling_result(Ok(Some(a)));
+
+        // The next epoch is polled once the seed lookahead permits.
+        assert_eq!(manager.poll(), Ok(vec![PollOutcome::NewDuties(0, a)]));
+        slot_clock.write().unwrap().set_slot(lookahead_slot);
+        assert_eq!(
+            manager.poll(),
+            Ok(vec![PollOutcome::NoChange(0), PollOutcome::NewDuties(1, a)])
+        );
+
+        // A re-org of the dependent block invalidates every epoch which depended upon it.
+        let b = EpochDuties {
+            dependent_root: Hash256::from(&[2; 32][..]),
+            ..a
+        };
+        beacon_node.set_next_shuffling_result(Ok(Some(b)));
+        assert_eq!(
+            manager.poll(),
+            Ok(vec![
+                PollOutcome::DutiesChanged(0, b),
+                PollOutcome::NewDuties(1, b)
+            ])
+        );
+
+        // Duties of past epochs are pruned.
+        slot_clock.write().unwrap().set_slot(spec.epoch_length);
+        assert_eq!(manager.poll(), Ok(vec![PollOutcome::NoChange(1)]));
+        let epochs: Vec<u64> = duties_map.read().unwrap().keys().cloned().collect();
+        assert_eq!(epochs, vec![1]);
     }
 }
@@ -2,39 +2,41 @@ use super::traits::BeaconNode;
 use super::{DutiesManager, PollOutcome};
 use slog::{debug, error, info, Logger};
 use slot_clock::SlotClock;
-use std::time::Duration;
 
+/// Polls the `EpochDuties` of every validator in turn, so a single task serves all keys.
 pub struct DutiesManagerService<T: SlotClock, U: BeaconNode> {
-    pub manager: DutiesManager<T, U>,
-    pub poll_interval_millis: u64,
+    pub managers: Vec<DutiesManager<T, U>>,
     pub log: Logger,
 }
 
 impl<T: SlotClock, U: BeaconNode> DutiesManagerService<T, U> {
-    /// Run a loop which polls the manager each `poll_interval_millis` milliseconds.
-    ///
-    /// Logs the results of the polls.
-    pub fn run(&mut self) {
-        loop {
-            match self.manager.poll() {
+    /// Poll each manager once, logging the outcomes.
+    pub fn poll(&mut self) {
+        for manager in &self.managers {
+            let validator = manager.pubkey.concatenated_hex_id();
+            let outcomes = match manager.poll() {
                 Err(error) => {
-                    error!(self.log, "Epoch duties poll error"; "error" => format!("{:?}", error))
-                }
-                Ok(PollOutcome::NoChange(epoch)) => {
-                    debug!(self.log, "No change in duties"; "epoch" => epoch)
-                }
-                Ok(PollOutcome::DutiesChanged(epoch, duties)) => {
-                    info!(self.log, "Duties changed (potential re-org)"; "epoch" => epoch, "duties" => format!("{:?}", duties))
-                }
-                Ok(PollOutcome::NewDuties(epoch, duties)) => {
-                    info!(self.log, "New duties obtained"; "epoch" => epoch, "duties" => format!("{:?}", duties))
-                }
-                Ok(PollOutcome::UnknownValidatorOrEpoch(epoch)) => {
-                    error!(self.log, "Epoch or validator unknown"; "epoch" => epoch)
+                    error!(self.log, "Epoch duties poll error"; "validator" => &validator, "error" => format!("{:?}", error));
+                    continue;
                 }
+                Ok(outcomes) => outcomes,
             };
-
-            std::thread::sleep(Duration::from_millis(self.poll_interval_millis));
+            for outcome in outcomes {
+                match outcome {
+                    PollOutcome::NoChange(epoch) => {
+                        debug!(self.log, "No change in duties"; "validator" => &validator, "epoch" => epoch)
+                    }
+                    PollOutcome::DutiesChanged(epoch, duties) => {
+                        info!(self.log, "Duties changed (potential re-org)"; "validator" => &validator, "epoch" => epoch, "duties" => format!("{:?}", duties))
+                    }
+                    PollOutcome::NewDuties(epoch, duties) => {
+                        info!(self.log, "New duties obtained"; "validator" => &validator, "epoch" => epoch, "duties" => format!("{:?}", duties))
+                    }
+                    PollOutcome::UnknownValidatorOrEpoch(epoch) => {
+                        error!(self.log, "Epoch or validator unknown"; "validator" => &validator, "epoch" => epoch)
+                    }
+                };
+            }
         }
     }
 }
@@ -4,6 +4,7 @@ use bls::PublicKey;
 #[derive(Debug, PartialEq, Clone)]
 pub enum BeaconNodeError {
     RemoteFailure(String),
+    DecodeFailure,
 }
 
 /// Defines the methods required to obtain a validators shuffling from a Beacon Node.
@@ -0,0 +1,40 @@
+use super::traits::{BeaconNode, BeaconNodeError};
+use grpcio::{Error as GrpcError, RpcStatusCode};
+use protos::services::{Exit as GrpcExit, PublicKey as IndexRequest, PublishExitRequest};
+use protos::services_grpc::ValidatorServiceClient;
+use ssz::ssz_encode;
+use types::{Exit, PublicKey};
+
+impl BeaconNode for ValidatorServiceClient {
+    /// Request the registry index of a validator from a Beacon Node (BN).
+    fn validator_index(&self, public_key: &PublicKey) -> Result<u64, BeaconNodeError> {
+        let mut req = IndexRequest::new();
+        req.set_public_key(ssz_encode(public_key).to_vec());
+
+        let reply = self
+            .validator_index(&req)
+            .map_err(|err| BeaconNodeError::RemoteFailure(format!("{:?}", err)))?;
+
+        Ok(reply.get_index())
+    }
+
+    /// Request a Beacon Node (BN) to publish a voluntary exit.
+    fn publish_exit(&self, exit: Exit) -> Result<bool, BeaconNodeError> {
+        let mut grpc_exit = GrpcExit::new();
+        grpc_exit.set_slot(exit.slot);
+        grpc_exit.set_validator_index(u64::from(exit.validator_index));
+        grpc_exit.set_signature(ssz_encode(&exit.signature));
+
+        let mut req = PublishExitRequest::new();
+        req.set_exit(grpc_exit);
+
+        let reply = self.publish_exit(&req).map_err(|err| match err {
+            GrpcError::RpcFailure(ref status) if status.status == RpcStatusCode::Unimplemented => {
+                BeaconNodeError::Unimplemented
+            }
+            err => BeaconNodeError::RemoteFailure(format!("{:?}", err)),
+        })?;
+
+        Ok(reply.get_success())
+    }
+}
@@ -0,0 +1,125 @@
+mod grpc;
+#[cfg(test)]
+mod test_node;
+mod traits;
+
+use crate::signer::{Error as SignerError, Signer};
+use spec::ChainSpec;
+use types::{Exit, Fork, Signature};
+
+pub use self::traits::{BeaconNode, BeaconNodeError};
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    EpochTooLarge,
+    ValidatorIndexTooLarge,
+    PublishRejected,
+    BeaconNodeError(BeaconNodeError),
+    SignerError(SignerError),
+}
+
+/// Signs a voluntary `Exit` for the validator of `signer`, effective from the first slot of
+/// `epoch`, and publishes it to the Beacon Node.
+///
+/// The signature domain is computed from `fork`, which should be the fork reported by the Beacon
+/// Node.
+///
+/// Returns the published exit.
+pub fn publish_exit<U: BeaconNode, V: Signer>(
+    spec: &ChainSpec,
+    fork: &Fork,
+    beacon_node: &U,
+    signer: &V,
+    epoch: u64,
+) -> Result<Exit, Error> {
+    let slot = epoch
+        .checked_mul(spec.epoch_length)
+        .ok_or(Error::EpochTooLarge)?;
+
+    let validator_index = beacon_node.validator_index(signer.public_key())?;
+    if validator_index > u64::from(u32::max_value()) {
+        return Err(Error::ValidatorIndexTooLarge);
+    }
+
+    let mut exit = Exit {
+        slot,
+        validator_index: validator_index as u32,
+        signature: Signature::empty_signature(),
+    };
+    let domain = fork.get_domain(slot, spec.domain_exit);
+    exit.signature = signer.sign(&exit.signing_root(), domain, slot)?;
+
+    if beacon_node.publish_exit(exit.clone())? {
+        Ok(exit)
+    } else {
+        Err(Error::PublishRejected)
+    }
+}
+
+impl From<BeaconNodeError> for Error {
+    fn from(e: BeaconNodeError) -> Error {
+        Error::BeaconNodeError(e)
+    }
+}
+
+impl From<SignerError> for Error {
+    fn from(e: SignerError) -> Error {
+        Error::SignerError(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_node::TestBeaconNode;
+    use super::*;
+    use crate::signer::LocalSigner;
+    use bls::Keypair;
+
+    #[test]
+    pub fn publishing() {
+        let spec = ChainSpec::foundation();
+        let keypair = Keypair::random();
+        let signer = LocalSigner::new(keypair.clone());
+        let beacon_node = TestBeaconNode::default();
+        let epoch = 3;
+        let fork = Fork {
+            pre_fork_version: spec.genesis_fork_version,
+            post_fork_version: spec.genesis_fork_version + 1,
+            fork_slot: epoch * spec.epoch_length,
+        };
+
+        beacon_node.set_next_index_result(Ok(7));
+        beacon_node.set_next_publish_result(Ok(true));
+        let exit = publish_exit(&spec, &fork, &beacon_node, &signer, epoch).unwrap();
+
+        assert_eq!(
+            *beacon_node.index_input.read().unwrap(),
+            Some(keypair.pk.clone())
+        );
+        assert_eq!(
+            *beacon_node.publish_input.read().unwrap(),
+            Some(exit.clone())
+        );
+        assert_eq!(exit.slot, epoch * spec.epoch_length);
+        assert_eq!(exit.validator_index, 7);
+        assert!(exit.signature.verify_with_domain(
+            &exit.signing_root(),
+            fork.get_domain(exit.slot, spec.domain_exit),
+            &keypair.pk
+        ));
+
+        // A rejected exit is an error.
+        beacon_node.set_next_publish_result(Ok(false));
+        assert_eq!(
+            publish_exit(&spec, &fork, &beacon_node, &signer, epoch),
+            Err(Error::PublishRejected)
+        );
+
+        // Indices which cannot be represented in an `Exit` are rejected before signing.
+        beacon_node.set_next_index_result(Ok(u64::from(u32::max_value()) + 1));
+        assert_eq!(
+            publish_exit(&spec, &fork, &beacon_node, &signer, epoch),
+            Err(Error::ValidatorIndexTooLarge)
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use super::traits::{BeaconNode, BeaconNodeError};
+use std::sync::RwLock;
+use types::{Exit, PublicKey};
+
+type IndexResult = Result<u64, BeaconNodeError>;
+type PublishResult = Result<bool, BeaconNodeError>;
+
+/// A test-only struct used to simulate a Beacon Node.
+#[derive(Default)]
+pub struct TestBeaconNode {
+    pub index_input: RwLock<Option<PublicKey>>,
+    pub index_result: RwLock<Option<IndexResult>>,
+    pub publish_input: RwLock<Option<Exit>>,
+    pub publish_result: RwLock<Option<PublishResult>>,
+}
+
+impl TestBeaconNode {
+    /// Set the result to be returned when `validator_index` is called.
+    pub fn set_next_index_result(&self, result: IndexResult) {
+        *self.index_result.write().unwrap() = Some(result);
+    }
+
+    /// Set the result to be returned when `publish_exit` is called.
+    pub fn set_next_publish_result(&self, result: PublishResult) {
+        *self.publish_result.write().unwrap() = Some(result);
+    }
+}
+
+impl BeaconNode for TestBeaconNode {
+    /// Returns the value specified by the `set_next_index_result`.
+    fn validator_index(&self, public_key: &PublicKey) -> IndexResult {
+        *self.index_input.write().unwrap() = Some(public_key.clone());
+        match *self.index_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: index_result == None"),
+        }
+    }
+
+    /// Returns the value specified by the `set_next_publish_result`.
+    fn publish_exit(&self, exit: Exit) -> PublishResult {
+        *self.publish_input.write().unwrap() = Some(exit);
+        match *self.publish_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: publish_result == None"),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use types::{Exit, PublicKey};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum BeaconNodeError {
+    RemoteFailure(String),
+    /// The Beacon Node does not accept voluntary exits.
+    Unimplemented,
+}
+
+/// Defines the methods required to submit a voluntary exit to a Beacon Node.
+pub trait BeaconNode: Send + Sync {
+    /// Returns the index of the validator with `public_key` in the validator registry.
+    fn validator_index(&self, public_key: &PublicKey) -> Result<u64, BeaconNodeError>;
+    /// Request that the node publishes a signed exit.
+    ///
+    /// Returns `true` if the publish was sucessful.
+    fn publish_exit(&self, exit: Exit) -> Result<bool, BeaconNodeError>;
+}
@@ -0,0 +1,272 @@
+use super::{Error as KeystoreError, Keystore};
+use bls::{Keypair, PublicKey};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+
+/// The directory (within the data directory) holding one sub-directory per validator.
+pub const VALIDATORS_DIR: &str = "validators";
+/// The keystore file within a validator directory.
+pub const KEYSTORE_FILE: &str = "keystore.json";
+/// If present within a validator directory, the key is not loaded at startup.
+pub const DISABLED_FILE: &str = "disabled";
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    IoError(String),
+    InvalidJson(String),
+    KeystoreError(KeystoreError),
+    ValidatorExists(String),
+    UnknownValidator(String),
+}
+
+/// A validator key known to the `KeyManager`.
+#[derive(Debug, PartialEq)]
+pub struct ValidatorKey {
+    pub pubkey: String,
+    pub enabled: bool,
+}
+
+/// Manages the keystores in `data_dir/validators/<pubkey>/`.
+pub struct KeyManager {
+    validators_dir: PathBuf,
+}
+
+impl KeyManager {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            validators_dir: data_dir.join(VALIDATORS_DIR),
+        }
+    }
+
+    /// Generate a new random key, stored encrypted with `password`.
+    ///
+    /// Returns the public key of the new validator.
+    pub fn generate(&self, password: &str, iterations: u32) -> Result<String, Error> {
+        let keystore = Keystore::encrypt(&Keypair::random(), password, iterations)?;
+        self.store(&keystore)?;
+        Ok(keystore.pubkey)
+    }
+
+    /// Import an existing keystore, ensuring it can be decrypted with `password` first.
+    ///
+    /// Returns the public key of the imported validator.
+    pub fn import(&self, keystore: &Keystore, password: &str) -> Result<String, Error> {
+        keystore.decrypt(password)?;
+        self.store(keystore)?;
+        Ok(keystore.pubkey.clone())
+    }
+
+    /// Returns all known validator keys, sorted by public key.
+    pub fn list(&self) -> Result<Vec<ValidatorKey>, Error> {
+        if !self.validators_dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut keys = vec![];
+        for entry in fs::read_dir(&self.validators_dir)? {
+            let path = entry?.path();
+            if !path.join(KEYSTORE_FILE).is_file() {
+                continue;
+            }
+            if let Some(pubkey) = path.file_name().and_then(|name| name.to_str()) {
+                keys.push(ValidatorKey {
+                    pubkey: pubkey.to_string(),
+                    enabled: !path.join(DISABLED_FILE).exists(),
+                });
+            }
+        }
+        keys.sort_by(|a, b| a.pubkey.cmp(&b.pubkey));
+
+        Ok(keys)
+    }
+
+    /// Permanently delete the keystore of `pubkey`.
+    pub fn delete(&self, pubkey: &str) -> Result<(), Error> {
+        let dir = self.validator_dir(pubkey)?;
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    /// Set whether or not the key of `pubkey` is loaded at startup.
+    ///
+    /// Keys without a local keystore (i.e., those held by a remote signer) may also be disabled,
+    /// so long as `pubkey` is a valid public key.
+    pub fn set_enabled(&self, pubkey: &str, enabled: bool) -> Result<(), Error> {
+        let dir = match self.validator_dir(pubkey) {
+            Ok(dir) => dir,
+            Err(_) if PublicKey::from_hex_string(pubkey).is_some() => {
+                self.validators_dir.join(pubkey)
+            }
+            Err(e) => return Err(e),
+        };
+
+        let disabled_path = dir.join(DISABLED_FILE);
+        if enabled && disabled_path.exists() {
+            fs::remove_file(disabled_path)?;
+        } else if !enabled {
+            fs::create_dir_all(&dir)?;
+            fs::write(disabled_path, &[])?;
+        }
+        Ok(())
+    }
+
+    /// Returns the public keys of all disabled validators, whether or not they have a keystore.
+    pub fn disabled(&self) -> Result<HashSet<String>, Error> {
+        if !self.validators_dir.exists() {
+            return Ok(HashSet::new());
+        }
+
+        let mut disabled = HashSet::new();
+        for entry in fs::read_dir(&self.validators_dir)? {
+            let path = entry?.path();
+            if !path.join(DISABLED_FILE).exists() {
+                continue;
+            }
+            if let Some(pubkey) = path.file_name().and_then(|name| name.to_str()) {
+                disabled.insert(pubkey.to_string());
+            }
+        }
+
+        Ok(disabled)
+    }
+
+    /// Decrypt and return the keypairs of all enabled validators.
+    pub fn load_enabled(&self, password: &str) -> Result<Vec<Keypair>, Error> {
+        let mut keypairs = vec![];
+        for key in self.list()?.into_iter().filter(|key| key.enabled) {
+            keypairs.push(self.read(&key.pubkey)?.decrypt(password)?);
+        }
+        Ok(keypairs)
+    }
+
+    /// Decrypt and return the keypair of `pubkey`, whether or not it is enabled.
+    pub fn load(&self, pubkey: &str, password: &str) -> Result<Keypair, Error> {
+        Ok(self.read(pubkey)?.decrypt(password)?)
+    }
+
+    /// Read the keystore of `pubkey` from disk.
+    fn read(&self, pubkey: &str) -> Result<Keystore, Error> {
+        let path = self.validator_dir(pubkey)?.join(KEYSTORE_FILE);
+        let bytes = fs::read(path)?;
+        serde_json::from_slice(&bytes).map_err(|e| Error::InvalidJson(e.to_string()))
+    }
+
+    /// Write `keystore` to a new validator directory, refusing to replace an existing key.
+    ///
+    /// The keystore file is only readable and writable by its owner.
+    fn store(&self, keystore: &Keystore) -> Result<(), Error> {
+        let dir = self.validators_dir.join(&keystore.pubkey);
+        if dir.exists() {
+            return Err(Error::ValidatorExists(keystore.pubkey.clone()));
+        }
+
+        let json =
+            serde_json::to_vec_pretty(keystore).map_err(|e| Error::InvalidJson(e.to_string()))?;
+        fs::create_dir_all(&dir)?;
+        OpenOptions::new()
+            .create_new(true)
+            .write(true)
+            .mode(0o600)
+            .open(dir.join(KEYSTORE_FILE))?
+            .write_all(&json)?;
+        Ok(())
+    }
+
+    /// Returns the directory of an existing validator.
+    fn validator_dir(&self, pubkey: &str) -> Result<PathBuf, Error> {
+        let dir = self.validators_dir.join(pubkey);
+        // The pubkey is user-supplied; ensure it names a directory within `validators_dir`.
+        let is_single_component = Path::new(pubkey).components().count() == 1;
+        if is_single_component && dir.join(KEYSTORE_FILE).is_file() {
+            Ok(dir)
+        } else {
+            Err(Error::UnknownValidator(pubkey.to_string()))
+        }
+    }
+}
+
+impl From<KeystoreError> for Error {
+    fn from(e: KeystoreError) -> Error {
+        Error::KeystoreError(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Error {
+        Error::IoError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::os::unix::fs::PermissionsExt;
+    use std::process;
+
+    const TEST_ITERATIONS: u32 = 16;
+
+    #[test]
+    pub fn key_management() {
+        let data_dir = env::temp_dir().join(format!("key_manager_{}", process::id()));
+        let _ = fs::remove_dir_all(&data_dir);
+        let manager = KeyManager::new(&data_dir);
+
+        assert_eq!(manager.list(), Ok(vec![]));
+
+        let a = manager.generate("cats", TEST_ITERATIONS).unwrap();
+        let keypair = Keypair::random();
+        let keystore = Keystore::encrypt(&keypair, "cats", TEST_ITERATIONS).unwrap();
+        let b = manager.import(&keystore, "cats").unwrap();
+        assert_eq!(
+            manager.import(&keystore, "cats"),
+            Err(Error::ValidatorExists(b.clone()))
+        );
+
+        assert_eq!(manager.list().unwrap().len(), 2);
+        let keystore_path = data_dir.join(VALIDATORS_DIR).join(&a).join(KEYSTORE_FILE);
+        let mode = fs::metadata(keystore_path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(manager.load_enabled("cats").unwrap().len(), 2);
+        assert!(manager.load_enabled("dogs").is_err());
+
+        manager.set_enabled(&a, false).unwrap();
+        assert_eq!(manager.load_enabled("cats"), Ok(vec![keypair]));
+        assert!(manager.load(&a, "cats").is_ok());
+
+        // Keys held by a remote signer may be disabled without a keystore.
+        let remote = Keypair::random().pk.as_hex_string();
+        manager.set_enabled(&remote, false).unwrap();
+        assert_eq!(
+            manager.disabled(),
+            Ok(vec![a.clone(), remote.clone()]
+                .into_iter()
+                .collect::<HashSet<_>>())
+        );
+        manager.set_enabled(&remote, true).unwrap();
+        assert_eq!(
+            manager.disabled(),
+            Ok(vec![a.clone()].into_iter().collect::<HashSet<_>>())
+        );
+        assert_eq!(
+            manager.set_enabled("cats", false),
+            Err(Error::UnknownValidator("cats".to_string()))
+        );
+
+        manager.delete(&b).unwrap();
+        assert_eq!(
+            manager.list(),
+            Ok(vec![ValidatorKey {
+                pubkey: a,
+                enabled: false,
+            }])
+        );
+        assert_eq!(
+            manager.delete("../validators"),
+            Err(Error::UnknownValidator("../validators".to_string()))
+        );
+    }
+}
@@ -0,0 +1,198 @@
+mod manager;
+
+use bls::{Keypair, PublicKey, SecretKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::{aead, digest, pbkdf2};
+use serde_derive::{Deserialize, Serialize};
+use ssz::{ssz_encode, Decodable};
+
+pub use self::manager::{Error as KeyManagerError, KeyManager};
+
+/// The version of the keystore format produced by this client.
+pub const KEYSTORE_VERSION: u64 = 1;
+/// The number of PBKDF2 iterations used when encrypting new keys.
+pub const DEFAULT_PBKDF2_ITERATIONS: u32 = 262_144;
+/// The largest number of PBKDF2 iterations accepted, so a malicious keystore cannot stall the
+/// client.
+pub const MAX_PBKDF2_ITERATIONS: u32 = 10_000_000;
+
+const KDF: &str = "pbkdf2-sha256";
+const CIPHER: &str = "aes-256-gcm";
+const SALT_LEN: usize = 32;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The password did not decrypt the key (or the keystore has been tampered with).
+    InvalidPassword,
+    UnsupportedVersion(u64),
+    UnsupportedKdf(String),
+    UnsupportedCipher(String),
+    /// The KDF parameters are outside of the accepted range (e.g., zero iterations).
+    InvalidKdfParams,
+    InvalidHex(String),
+    InvalidSecretKey,
+    /// The decrypted secret key does not match the public key of the keystore.
+    PublicKeyMismatch,
+    RandomnessUnavailable,
+    CryptoError,
+}
+
+/// A password-encrypted `SecretKey`, as stored on disk.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u64,
+    /// The `0x`-prefixed hex encoding of the public key, so keys may be identified without
+    /// decryption.
+    pub pubkey: String,
+    pub crypto: Crypto,
+}
+
+/// The parameters required to decrypt the secret key of a `Keystore`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Crypto {
+    pub kdf: String,
+    pub kdf_iterations: u32,
+    pub kdf_salt: String,
+    pub cipher: String,
+    pub cipher_nonce: String,
+    /// The encrypted SSZ encoding of the secret key, followed by the authentication tag.
+    pub ciphertext: String,
+}
+
+impl Keystore {
+    /// Encrypt the secret key of `keypair` with a key derived from `password`.
+    pub fn encrypt(keypair: &Keypair, password: &str, iterations: u32) -> Result<Self, Error> {
+        check_iterations(iterations)?;
+
+        let algorithm = &aead::AES_256_GCM;
+        let rng = SystemRandom::new();
+
+        let mut salt = vec![0; SALT_LEN];
+        rng.fill(&mut salt)
+            .map_err(|_| Error::RandomnessUnavailable)?;
+        let mut nonce = vec![0; algorithm.nonce_len()];
+        rng.fill(&mut nonce)
+            .map_err(|_| Error::RandomnessUnavailable)?;
+
+        let key = derive_key(password, &salt, iterations, algorithm.key_len());
+        let sealing_key = aead::SealingKey::new(algorithm, &key).map_err(|_| Error::CryptoError)?;
+
+        let mut in_out = ssz_encode(&keypair.sk);
+        in_out.extend(vec![0; algorithm.tag_len()]);
+        let len = aead::seal_in_place(&sealing_key, &nonce, &[], &mut in_out, algorithm.tag_len())
+            .map_err(|_| Error::CryptoError)?;
+        in_out.truncate(len);
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            pubkey: keypair.pk.as_hex_string(),
+            crypto: Crypto {
+                kdf: KDF.to_string(),
+                kdf_iterations: iterations,
+                kdf_salt: hex::encode(&salt),
+                cipher: CIPHER.to_string(),
+                cipher_nonce: hex::encode(&nonce),
+                ciphertext: hex::encode(&in_out),
+            },
+        })
+    }
+
+    /// Decrypt the keystore with `password`, returning the keypair.
+    pub fn decrypt(&self, password: &str) -> Result<Keypair, Error> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(Error::UnsupportedVersion(self.version));
+        }
+        if self.crypto.kdf != KDF {
+            return Err(Error::UnsupportedKdf(self.crypto.kdf.clone()));
+        }
+        check_iterations(self.crypto.kdf_iterations)?;
+        if self.crypto.cipher != CIPHER {
+            return Err(Error::UnsupportedCipher(self.crypto.cipher.clone()));
+        }
+
+        let algorithm = &aead::AES_256_GCM;
+        let salt = decode_hex(&self.crypto.kdf_salt)?;
+        let nonce = decode_hex(&self.crypto.cipher_nonce)?;
+        let mut ciphertext = decode_hex(&self.crypto.ciphertext)?;
+
+        let key = derive_key(
+            password,
+            &salt,
+            self.crypto.kdf_iterations,
+            algorithm.key_len(),
+        );
+        let opening_key = aead::OpeningKey::new(algorithm, &key).map_err(|_| Error::CryptoError)?;
+        let plaintext = aead::open_in_place(&opening_key, &nonce, &[], 0, &mut ciphertext)
+            .map_err(|_| Error::InvalidPassword)?;
+
+        let (sk, _) = SecretKey::ssz_decode(plaintext, 0).map_err(|_| Error::InvalidSecretKey)?;
+        let pk = PublicKey::from_secret_key(&sk);
+        if pk.as_hex_string() != self.pubkey {
+            return Err(Error::PublicKeyMismatch);
+        }
+
+        Ok(Keypair { sk, pk })
+    }
+}
+
+/// Returns an error if `iterations` is outside of `1..=MAX_PBKDF2_ITERATIONS`.
+fn check_iterations(iterations: u32) -> Result<(), Error> {
+    if iterations == 0 || iterations > MAX_PBKDF2_ITERATIONS {
+        return Err(Error::InvalidKdfParams);
+    }
+    Ok(())
+}
+
+fn derive_key(password: &str, salt: &[u8], iterations: u32, len: usize) -> Vec<u8> {
+    let mut key = vec![0; len];
+    pbkdf2::derive(
+        &digest::SHA256,
+        iterations,
+        salt,
+        password.as_bytes(),
+        &mut key,
+    );
+    key
+}
+
+fn decode_hex(string: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(string).map_err(|_| Error::InvalidHex(string.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Keep the tests fast; the iteration count is not under test.
+    const TEST_ITERATIONS: u32 = 16;
+
+    #[test]
+    pub fn encrypt_and_decrypt() {
+        let keypair = Keypair::random();
+        let keystore = Keystore::encrypt(&keypair, "cats", TEST_ITERATIONS).unwrap();
+
+        assert_eq!(keystore.pubkey, keypair.pk.as_hex_string());
+        assert_eq!(keystore.decrypt("cats"), Ok(keypair));
+        assert_eq!(keystore.decrypt("dogs"), Err(Error::InvalidPassword));
+    }
+
+    #[test]
+    pub fn detects_tampering() {
+        let keypair = Keypair::random();
+        let mut keystore = Keystore::encrypt(&keypair, "cats", TEST_ITERATIONS).unwrap();
+
+        keystore.pubkey = Keypair::random().pk.as_hex_string();
+        assert_eq!(keystore.decrypt("cats"), Err(Error::PublicKeyMismatch));
+
+        keystore.crypto.kdf_iterations = 0;
+        assert_eq!(keystore.decrypt("cats"), Err(Error::InvalidKdfParams));
+        keystore.crypto.kdf_iterations = MAX_PBKDF2_ITERATIONS + 1;
+        assert_eq!(keystore.decrypt("cats"), Err(Error::InvalidKdfParams));
+
+        keystore.crypto.kdf = "md5".to_string();
+        assert_eq!(
+            keystore.decrypt("cats"),
+            Err(Error::UnsupportedKdf("md5".to_string()))
+        );
+    }
+}
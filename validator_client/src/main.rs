@@ -1,20 +1,50 @@
+use self::beacon_node_pool::{BeaconNodePool, BeaconNodePoolService, GrpcBeaconNode};
 use self::duties::{DutiesManager, DutiesManagerService, EpochDutiesMap};
+use crate::attester::{Attester, AttesterService};
 use crate::block_producer::{BlockProducer, BlockProducerService};
-use crate::config::ClientConfig;
-use bls::Keypair;
-use clap::{App, Arg};
-use grpcio::{ChannelBuilder, EnvBuilder};
-use protos::services_grpc::{BeaconBlockServiceClient, ValidatorServiceClient};
-use slog::{error, info, o, Drain};
+use crate::config::{is_valid_server_address, ClientConfig, ClientTlsConfig};
+use crate::doppelganger::{
+    BeaconNode as DoppelgangerBeaconNode, BeaconNodeError as DoppelgangerError,
+    DoppelgangerDetector, DoppelgangerService, SigningGate,
+};
+use crate::keystore::{KeyManager, KeyManagerError, Keystore, DEFAULT_PBKDF2_ITERATIONS};
+use crate::metrics::{MeteredSigner, Metrics};
+use crate::node_status::BeaconNode as NodeStatusBeaconNode;
+use crate::scheduler::Scheduler;
+use crate::signer::{LocalSigner, RemoteSigner, Signer};
+use crate::slashing_protection::{SlashingProtection, SLASHING_PROTECTION_FILE};
+use bls::PublicKey;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use grpcio::{Channel, ChannelBuilder, ChannelCredentialsBuilder, EnvBuilder};
+use protos::services_grpc::{NodeServiceClient, SignerServiceClient, ValidatorServiceClient};
+use slog::{error, info, o, Drain, Logger};
 use slot_clock::SystemTimeSlotClock;
 use spec::ChainSpec;
+use ssz::ssz_encode;
+use std::collections::HashSet;
+use std::fs;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
-use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use types::Exit;
 
+mod attester;
+mod beacon_node_pool;
 mod block_producer;
 mod config;
+mod deposit;
+mod doppelganger;
 mod duties;
+mod exit;
+mod keystore;
+mod metrics;
+mod node_status;
+mod scheduler;
+mod signer;
+mod slashing_protection;
+
+/// The number of times each service is polled per slot.
+const POLLS_PER_SLOT: u32 = 10;
 
 fn main() {
     // Logging
@@ -38,10 +68,183 @@ fn main() {
         .arg(
             Arg::with_name("server")
                 .long("server")
-                .value_name("server")
-                .help("Address to connect to BeaconNode.")
+                .value_name("HOST:PORT")
+                .help("Address of a BeaconNode. May be repeated; nodes are preferred in the order given.")
+                .multiple(true)
+                .number_of_values(1)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("server-ca")
+                .long("server-ca")
+                .value_name("FILE")
+                .help("PEM CA certificate used to verify the BeaconNode; enables TLS.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("client-cert")
+                .long("client-cert")
+                .value_name("FILE")
+                .help("PEM certificate presented to the BeaconNode (mutual TLS).")
+                .requires_all(&["server-ca", "client-key"])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("client-key")
+                .long("client-key")
+                .value_name("FILE")
+                .help("PEM private key for the client certificate (mutual TLS).")
+                .requires_all(&["server-ca", "client-cert"])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("max-head-distance")
+                .long("max-head-distance")
+                .value_name("SLOTS")
+                .help("Refuse to sign if the BeaconNode head is more than this many slots behind.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("remote-signer")
+                .long("remote-signer")
+                .value_name("HOST:PORT")
+                .help("Address of a signing service holding the validator keys, used instead of the local keystores.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("signer-ca")
+                .long("signer-ca")
+                .value_name("FILE")
+                .help("PEM CA certificate used to verify the remote signer; enables TLS.")
+                .requires("remote-signer")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("signer-client-cert")
+                .long("signer-client-cert")
+                .value_name("FILE")
+                .help("PEM certificate presented to the remote signer (mutual TLS).")
+                .requires_all(&["signer-ca", "signer-client-key"])
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("signer-client-key")
+                .long("signer-client-key")
+                .value_name("FILE")
+                .help("PEM private key for the remote signer client certificate (mutual TLS).")
+                .requires_all(&["signer-ca", "signer-client-cert"])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("doppelganger-epochs")
+                .long("doppelganger-epochs")
+                .value_name("EPOCHS")
+                .help("Watch for the validators being live elsewhere for this many epochs before signing. Requires beacon nodes which track validator liveness.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("metrics")
+                .long("metrics")
+                .help("Serve Prometheus metrics over HTTP."),
+        )
+        .arg(
+            Arg::with_name("metrics-address")
+                .long("metrics-address")
+                .value_name("HOST")
+                .help("Address the metrics server binds to.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("metrics-port")
+                .long("metrics-port")
+                .value_name("PORT")
+                .help("Port the metrics server binds to.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("password-file")
+                .long("password-file")
+                .value_name("FILE")
+                .help("File containing the validator keystore password. Prompts if not supplied.")
+                .takes_value(true),
+        )
+        .subcommand(
+            SubCommand::with_name("keys")
+                .about("Manage the validator keys in the data directory.")
+                .subcommand(SubCommand::with_name("generate").about("Generate a new validator key."))
+                .subcommand(
+                    SubCommand::with_name("import")
+                        .about("Import an encrypted keystore.")
+                        .arg(Arg::with_name("file").value_name("FILE").required(true)),
+                )
+                .subcommand(SubCommand::with_name("list").about("List all validator keys."))
+                .subcommand(
+                    SubCommand::with_name("delete")
+                        .about("Permanently delete a validator key.")
+                        .arg(Arg::with_name("pubkey").value_name("PUBKEY").required(true)),
+                )
+                .subcommand(
+                    SubCommand::with_name("enable")
+                        .about("Load the key at startup.")
+                        .arg(Arg::with_name("pubkey").value_name("PUBKEY").required(true)),
+                )
+                .subcommand(
+                    SubCommand::with_name("disable")
+                        .about("Do not load the key at startup.")
+                        .arg(Arg::with_name("pubkey").value_name("PUBKEY").required(true)),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("slashing-protection")
+                .about("Import or export the slashing-protection database as JSON.")
+                .subcommand(
+                    SubCommand::with_name("import")
+                        .about("Merge the signing history in FILE into the database.")
+                        .arg(Arg::with_name("file").value_name("FILE").required(true)),
+                )
+                .subcommand(
+                    SubCommand::with_name("export")
+                        .about("Write the signing history of all validators to FILE.")
+                        .arg(Arg::with_name("file").value_name("FILE").required(true)),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("deposit")
+                .about("Print the deposit data with which a validator key may join the registry.")
+                .arg(Arg::with_name("pubkey").value_name("PUBKEY").required(true))
+                .arg(
+                    Arg::with_name("withdrawal-pubkey")
+                        .long("withdrawal-pubkey")
+                        .value_name("PUBKEY")
+                        .help("BLS public key permitted to withdraw. Defaults to the validator key.")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .value_name("GWEI")
+                        .help("Amount to deposit. Defaults to the maximum deposit.")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("ssz")
+                        .long("ssz")
+                        .help("Print the hex SSZ-encoded DepositInput for the deposit contract."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("exit")
+                .about("Sign a voluntary exit and publish it to the first beacon node.")
+                .arg(Arg::with_name("pubkey").value_name("PUBKEY").required(true))
+                .arg(
+                    Arg::with_name("epoch")
+                        .long("epoch")
+                        .value_name("EPOCH")
+                        .help("The epoch from which the exit is valid.")
+                        .takes_value(true)
+                        .required(true),
+                ),
+        )
         .get_matches();
 
     let mut config = ClientConfig::default();
@@ -51,34 +254,89 @@ fn main() {
         config.data_dir = PathBuf::from(dir.to_string());
     }
 
-    // Custom server port
-    if let Some(server_str) = matches.value_of("server") {
-        if let Ok(addr) = server_str.parse::<u16>() {
-            config.server = addr.to_string();
+    // Custom beacon node addresses
+    if let Some(servers) = matches.values_of("server") {
+        let mut addresses = vec![];
+        for server_str in servers {
+            if is_valid_server_address(server_str) {
+                addresses.push(server_str.to_string());
+            } else {
+                error!(log, "Invalid address"; "server" => server_str);
+                return;
+            }
+        }
+        config.servers = addresses;
+    }
+
+    // TLS for the beacon node connection
+    config.server_tls = tls_config(&matches, "server-ca", "client-cert", "client-key");
+
+    // Remote signer
+    if let Some(server) = matches.value_of("remote-signer") {
+        if is_valid_server_address(server) {
+            config.remote_signer = Some(server.to_string());
+        } else {
+            error!(log, "Invalid address"; "remote_signer" => server);
+            return;
+        }
+    }
+    config.remote_signer_tls = tls_config(
+        &matches,
+        "signer-ca",
+        "signer-client-cert",
+        "signer-client-key",
+    );
+
+    // Doppelganger detection
+    if let Some(epochs_str) = matches.value_of("doppelganger-epochs") {
+        if let Ok(epochs) = epochs_str.parse::<u64>() {
+            config.doppelganger_epochs = epochs;
+        } else {
+            error!(log, "Invalid doppelganger epochs"; "doppelganger_epochs" => epochs_str);
+            return;
+        }
+    }
+
+    // Metrics server
+    if matches.is_present("metrics") {
+        config.metrics.enabled = true;
+    }
+    if let Some(address) = matches.value_of("metrics-address") {
+        config.metrics.listen_address = address.to_string();
+    }
+    if let Some(port_str) = matches.value_of("metrics-port") {
+        if let Ok(port) = port_str.parse::<u16>() {
+            config.metrics.port = port;
         } else {
-            error!(log, "Invalid address"; "server" => server_str);
+            error!(log, "Invalid metrics port"; "port" => port_str);
             return;
         }
     }
 
+    // Custom maximum head distance
+    if let Some(distance_str) = matches.value_of("max-head-distance") {
+        if let Ok(distance) = distance_str.parse::<u64>() {
+            config.max_head_distance = distance;
+        } else {
+            error!(log, "Invalid max head distance"; "max_head_distance" => distance_str);
+            return;
+        }
+    }
+
+    let key_manager = KeyManager::new(&config.data_dir);
+
+    if let Some(keys_matches) = matches.subcommand_matches("keys") {
+        if let Err(e) = keys_command(keys_matches, &matches, &key_manager) {
+            error!(log, "Key management command failed"; "error" => e);
+        }
+        return;
+    }
+
     // Log configuration
     info!(log, "";
           "data_dir" => &config.data_dir.to_str(),
-          "server" => &config.server);
-
-    // Beacon node gRPC beacon block endpoints.
-    let beacon_block_grpc_client = {
-        let env = Arc::new(EnvBuilder::new().build());
-        let ch = ChannelBuilder::new(env).connect(&config.server);
-        Arc::new(BeaconBlockServiceClient::new(ch))
-    };
-
-    // Beacon node gRPC validator endpoints.
-    let validator_grpc_client = {
-        let env = Arc::new(EnvBuilder::new().build());
-        let ch = ChannelBuilder::new(env).connect(&config.server);
-        Arc::new(ValidatorServiceClient::new(ch))
-    };
+          "servers" => config.servers.join(","),
+          "tls" => config.server_tls.is_some());
 
     // Ethereum
     //
@@ -86,6 +344,42 @@ fn main() {
     // https://github.com/sigp/lighthouse/issues/160
     let spec = Arc::new(ChainSpec::foundation());
 
+    if let Some(deposit_matches) = matches.subcommand_matches("deposit") {
+        if let Err(e) = deposit_command(deposit_matches, &matches, &key_manager, &spec) {
+            error!(log, "Deposit command failed"; "error" => e);
+        }
+        return;
+    }
+
+    if let Some(exit_matches) = matches.subcommand_matches("exit") {
+        match exit_command(exit_matches, &matches, &config, &key_manager, &spec) {
+            Ok(exit) => {
+                info!(log, "Published voluntary exit"; "slot" => exit.slot, "validator_index" => exit.validator_index)
+            }
+            Err(e) => error!(log, "Exit command failed"; "error" => e),
+        }
+        return;
+    }
+
+    // Slashing protection, shared by all validators.
+    let slashing_protection = {
+        let path = config.data_dir.join(SLASHING_PROTECTION_FILE);
+        match SlashingProtection::open(&path, spec.genesis_time) {
+            Ok(db) => Arc::new(db),
+            Err(e) => {
+                error!(log, "Unable to open slashing protection database"; "path" => format!("{:?}", path), "error" => format!("{:?}", e));
+                return;
+            }
+        }
+    };
+
+    if let Some(matches) = matches.subcommand_matches("slashing-protection") {
+        if let Err(e) = slashing_protection_command(matches, &slashing_protection) {
+            error!(log, "Slashing protection command failed"; "error" => e);
+        }
+        return;
+    }
+
     // Clock for determining the present slot.
     let slot_clock = {
         info!(log, "Genesis time"; "unix_epoch_seconds" => spec.genesis_time);
@@ -94,73 +388,516 @@ fn main() {
         Arc::new(RwLock::new(clock))
     };
 
-    let poll_interval_millis = spec.slot_duration * 1000 / 10; // 10% epoch time precision.
-    info!(log, "Starting block producer service"; "polls_per_epoch" => spec.slot_duration * 1000 / poll_interval_millis);
+    // Beacon nodes, each serving all gRPC endpoints.
+    let beacon_nodes = {
+        let mut nodes = vec![];
+        for server in &config.servers {
+            match connect(&config.server_tls, server) {
+                Ok(ch) => nodes.push((server.clone(), GrpcBeaconNode::new(ch))),
+                Err(e) => {
+                    error!(log, "Unable to connect to beacon node"; "server" => server, "error" => e);
+                    return;
+                }
+            }
+        }
+        if config.doppelganger_epochs > 0 {
+            for (server, node) in &nodes {
+                if node.validator_liveness(0, &[]) == Err(DoppelgangerError::Unimplemented) {
+                    error!(log, "Beacon node does not support doppelganger detection, remove --doppelganger-epochs to start without it"; "server" => server);
+                    return;
+                }
+            }
+        }
+        Arc::new(BeaconNodePool::new(
+            nodes,
+            spec.clone(),
+            slot_clock.clone(),
+            config.max_head_distance,
+            log.clone(),
+        ))
+    };
 
     /*
-     * Start threads.
+     * Start services.
      */
-    let mut threads = vec![];
-    // TODO: keypairs are randomly generated; they should be loaded from a file or generated.
-    // https://github.com/sigp/lighthouse/issues/160
-    let keypairs = vec![Keypair::random()];
-
-    for keypair in keypairs {
-        info!(log, "Starting validator services"; "validator" => keypair.pk.concatenated_hex_id());
-        let duties_map = Arc::new(RwLock::new(EpochDutiesMap::new()));
-
-        // Spawn a new thread to maintain the validator's `EpochDuties`.
-        let duties_manager_thread = {
-            let spec = spec.clone();
-            let duties_map = duties_map.clone();
-            let slot_clock = slot_clock.clone();
-            let log = log.clone();
-            let beacon_node = validator_grpc_client.clone();
-            let pubkey = keypair.pk.clone();
-            thread::spawn(move || {
-                let manager = DutiesManager {
-                    duties_map,
-                    pubkey,
-                    spec,
-                    slot_clock,
-                    beacon_node,
-                };
-                let mut duties_manager_service = DutiesManagerService {
-                    manager,
-                    poll_interval_millis,
-                    log,
-                };
-
-                duties_manager_service.run();
-            })
-        };
+    let mut scheduler = match Scheduler::new(
+        slot_clock.clone(),
+        Duration::from_secs(spec.slot_duration),
+        log.clone(),
+    ) {
+        Ok(scheduler) => scheduler,
+        Err(e) => {
+            error!(log, "Unable to start runtime"; "error" => format!("{:?}", e));
+            return;
+        }
+    };
 
-        // Spawn a new thread to perform block production for the validator.
-        let producer_thread = {
-            let spec = spec.clone();
-            let duties_map = duties_map.clone();
-            let slot_clock = slot_clock.clone();
-            let log = log.clone();
-            let client = beacon_block_grpc_client.clone();
-            thread::spawn(move || {
-                let block_producer = BlockProducer::new(spec, duties_map, slot_clock, client);
-                let mut block_producer_service = BlockProducerService {
-                    block_producer,
-                    poll_interval_millis,
-                    log,
-                };
-
-                block_producer_service.run();
-            })
+    // Metrics, shared by all validators.
+    let metrics = match Metrics::new() {
+        Ok(metrics) => Arc::new(metrics),
+        Err(e) => {
+            error!(log, "Unable to create metrics"; "error" => format!("{:?}", e));
+            return;
+        }
+    };
+    if config.metrics.enabled {
+        if let Err(e) = metrics::start_server(&config.metrics, metrics.clone(), log.clone()) {
+            error!(log, "Unable to start metrics server"; "error" => format!("{:?}", e));
+            return;
+        }
+    }
+
+    // Verify that the beacon nodes are safe to sign upon.
+    let mut beacon_node_pool_service = BeaconNodePoolService {
+        pool: beacon_nodes.clone(),
+        log: log.clone(),
+    };
+    scheduler.spawn("beacon_node_pool", POLLS_PER_SLOT, move || {
+        beacon_node_pool_service.poll()
+    });
+
+    // Validators, signing either with local keys or via a remote signer.
+    let context = ValidatorContext {
+        spec,
+        slot_clock,
+        beacon_nodes,
+        slashing_protection,
+        doppelganger_epochs: config.doppelganger_epochs,
+        metrics,
+        log: log.clone(),
+    };
+    let result = match &config.remote_signer {
+        Some(server) => key_manager
+            .disabled()
+            .map_err(|e| format!("Unable to read disabled keys: {:?}", e))
+            .and_then(|disabled| remote_signers(&config, server, &disabled))
+            .and_then(|signers| context.spawn_services(&mut scheduler, signers)),
+        None => local_signers(&key_manager, &matches)
+            .and_then(|signers| context.spawn_services(&mut scheduler, signers)),
+    };
+    if let Err(e) = result {
+        error!(log, "Unable to start validator services"; "error" => e);
+        return;
+    }
+
+    scheduler.run();
+}
+
+/// The resources shared by the services of all validators.
+struct ValidatorContext {
+    spec: Arc<ChainSpec>,
+    slot_clock: Arc<RwLock<SystemTimeSlotClock>>,
+    beacon_nodes: Arc<BeaconNodePool<SystemTimeSlotClock, GrpcBeaconNode>>,
+    slashing_protection: Arc<SlashingProtection>,
+    /// The number of epochs to watch for doppelgangers before signing; `0` disables detection.
+    doppelganger_epochs: u64,
+    metrics: Arc<Metrics>,
+    log: Logger,
+}
+
+impl ValidatorContext {
+    /// Spawn the duties, block production and attestation services for the validators of
+    /// `signers`.
+    ///
+    /// If doppelganger detection is enabled, the validators do not sign until detection (itself
+    /// a scheduled task) is complete, and any validator found to be live elsewhere never signs.
+    fn spawn_services<V: Signer + 'static>(
+        &self,
+        scheduler: &mut Scheduler<SystemTimeSlotClock>,
+        signers: Vec<Arc<V>>,
+    ) -> Result<(), String> {
+        let spec = &self.spec;
+        let slot_clock = &self.slot_clock;
+        let beacon_nodes = &self.beacon_nodes;
+        let log = &self.log;
+
+        if signers.is_empty() {
+            return Err(
+                "No enabled validator keys; add one with the `keys` subcommand".to_string(),
+            );
+        }
+
+        // Record the latency of every signature.
+        let signers: Vec<_> = signers
+            .into_iter()
+            .map(|signer| Arc::new(MeteredSigner::new(signer, self.metrics.clone())))
+            .collect();
+
+        let verified_slot = beacon_nodes.verified_slot();
+        let verified_fork = beacon_nodes.verified_fork();
+        let duties_maps: Vec<_> = signers
+            .iter()
+            .map(|_| Arc::new(RwLock::new(EpochDutiesMap::new())))
+            .collect();
+
+        // Maintain the `EpochDuties` of all validators.
+        let mut duties_manager_service = DutiesManagerService {
+            managers: signers
+                .iter()
+                .zip(&duties_maps)
+                .map(|(signer, duties_map)| DutiesManager {
+                    duties_map: duties_map.clone(),
+                    pubkey: signer.public_key().clone(),
+                    spec: spec.clone(),
+                    slot_clock: slot_clock.clone(),
+                    beacon_node: beacon_nodes.clone(),
+                })
+                .collect(),
+            log: log.clone(),
         };
+        scheduler.spawn("duties", 1, move || duties_manager_service.poll());
+
+        let gate = self.spawn_doppelganger_detection(scheduler, &signers, &duties_maps)?;
+        info!(log, "Starting validator services"; "validators" => signers.len(), "polls_per_slot" => POLLS_PER_SLOT);
+
+        for (signer, duties_map) in signers.into_iter().zip(duties_maps) {
+            let log = log.new(o!("validator" => signer.public_key().concatenated_hex_id()));
+
+            // Perform block production for the validator.
+            let block_producer = BlockProducer::new(
+                spec.clone(),
+                duties_map.clone(),
+                verified_slot.clone(),
+                verified_fork.clone(),
+                slot_clock.clone(),
+                beacon_nodes.clone(),
+                signer.clone(),
+                self.slashing_protection.clone(),
+            );
+            let mut block_producer_service = BlockProducerService {
+                block_producer,
+                gate: gate.clone(),
+                metrics: self.metrics.clone(),
+                duties_unknown_slot: None,
+                log: log.clone(),
+            };
+            scheduler.spawn("block_producer", POLLS_PER_SLOT, move || {
+                block_producer_service.poll()
+            });
+
+            // Perform attestation for the validator.
+            let attester = Attester::new(
+                spec.clone(),
+                duties_map,
+                verified_slot.clone(),
+                verified_fork.clone(),
+                slot_clock.clone(),
+                beacon_nodes.clone(),
+                signer,
+                self.slashing_protection.clone(),
+            );
+            let mut attester_service = AttesterService {
+                attester,
+                gate: gate.clone(),
+                metrics: self.metrics.clone(),
+                duties_unknown_slot: None,
+                log,
+            };
+            scheduler.spawn("attester", POLLS_PER_SLOT, move || attester_service.poll());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the `SigningGate` of the validators of `signers`.
+    ///
+    /// If doppelganger detection is disabled, all validators are enabled immediately. Otherwise,
+    /// a doppelganger detection task is spawned which enables them once detection is complete.
+    fn spawn_doppelganger_detection<V: Signer>(
+        &self,
+        scheduler: &mut Scheduler<SystemTimeSlotClock>,
+        signers: &[Arc<V>],
+        duties_maps: &[Arc<RwLock<EpochDutiesMap>>],
+    ) -> Result<Arc<SigningGate>, String> {
+        let gate = Arc::new(SigningGate::default());
+
+        if self.doppelganger_epochs == 0 {
+            for signer in signers {
+                gate.enable(signer.public_key().clone());
+            }
+            return Ok(gate);
+        }
+        info!(self.log, "Waiting for doppelganger detection before signing"; "epochs" => self.doppelganger_epochs);
+
+        let detector = DoppelgangerDetector::new(
+            self.spec.clone(),
+            self.slot_clock.clone(),
+            self.beacon_nodes.clone(),
+            signers
+                .iter()
+                .zip(duties_maps)
+                .map(|(signer, duties_map)| (signer.public_key().clone(), duties_map.clone()))
+                .collect(),
+            self.doppelganger_epochs,
+        )
+        .map_err(|e| format!("Unable to start doppelganger detection: {:?}", e))?;
+        let mut doppelganger_service =
+            DoppelgangerService::new(detector, gate.clone(), self.log.clone());
+        scheduler.spawn("doppelganger", 1, move || doppelganger_service.poll());
+
+        Ok(gate)
+    }
+}
+
+/// Decrypt the enabled keystores of the `KeyManager`, returning a signer for each.
+fn local_signers(
+    key_manager: &KeyManager,
+    matches: &ArgMatches,
+) -> Result<Vec<Arc<LocalSigner>>, String> {
+    let password = read_password(matches)?;
+    let keypairs = key_manager
+        .load_enabled(&password)
+        .map_err(|e| format!("Unable to load validator keys: {:?}", e))?;
+    Ok(keypairs
+        .into_iter()
+        .map(|keypair| Arc::new(LocalSigner::new(keypair)))
+        .collect())
+}
+
+/// Returns a signer for each of the keys held by the signing service at `server`, except those in
+/// `disabled`.
+///
+/// Unless `--signer-ca` is supplied the connection is not encrypted, so the signing service
+/// should only be reachable from the validator client (e.g., on `localhost`).
+fn remote_signers(
+    config: &ClientConfig,
+    server: &str,
+    disabled: &HashSet<String>,
+) -> Result<Vec<Arc<RemoteSigner>>, String> {
+    let client = SignerServiceClient::new(connect(&config.remote_signer_tls, server)?);
+    let signers = RemoteSigner::all(&client, disabled)
+        .map_err(|e| format!("Unable to list remote signer keys: {:?}", e))?;
+    Ok(signers.into_iter().map(Arc::new).collect())
+}
+
+/// Returns the TLS configuration given by the CA, certificate and key arguments, if the CA was
+/// supplied.
+fn tls_config(
+    matches: &ArgMatches,
+    ca_arg: &str,
+    cert_arg: &str,
+    key_arg: &str,
+) -> Option<ClientTlsConfig> {
+    let server_ca = matches.value_of(ca_arg)?;
+    let client_cert_and_key = match (matches.value_of(cert_arg), matches.value_of(key_arg)) {
+        (Some(cert), Some(key)) => Some((PathBuf::from(cert), PathBuf::from(key))),
+        _ => None,
+    };
+    Some(ClientTlsConfig {
+        server_ca: PathBuf::from(server_ca),
+        client_cert_and_key,
+    })
+}
+
+/// Open a gRPC channel to `server`, using TLS if it has been configured.
+fn connect(tls: &Option<ClientTlsConfig>, server: &str) -> Result<Channel, String> {
+    let env = Arc::new(EnvBuilder::new().build());
+    let builder = ChannelBuilder::new(env);
+
+    match tls {
+        None => Ok(builder.connect(server)),
+        Some(tls) => {
+            let read = |path: &PathBuf| {
+                fs::read(path).map_err(|e| format!("Unable to read {:?}: {}", path, e))
+            };
+            let mut credentials = ChannelCredentialsBuilder::new().root_cert(read(&tls.server_ca)?);
+            if let Some((cert, key)) = &tls.client_cert_and_key {
+                credentials = credentials.cert(read(cert)?, read(key)?);
+            }
+            Ok(builder.secure_connect(server, credentials.build()))
+        }
+    }
+}
 
-        threads.push((duties_manager_thread, producer_thread));
+/// Run an `import` or `export` of the slashing-protection database.
+fn slashing_protection_command(
+    matches: &ArgMatches,
+    slashing_protection: &SlashingProtection,
+) -> Result<(), String> {
+    match matches.subcommand() {
+        ("import", Some(matches)) => {
+            let path = PathBuf::from(matches.value_of("file").expect("file is required"));
+            let bytes = fs::read(&path).map_err(|e| format!("Unable to read {:?}: {}", path, e))?;
+            let interchange = slashing_protection::parse(&bytes).map_err(|e| format!("{:?}", e))?;
+            slashing_protection
+                .import(&interchange)
+                .map_err(|e| format!("{:?}", e))
+        }
+        ("export", Some(matches)) => {
+            let path = PathBuf::from(matches.value_of("file").expect("file is required"));
+            let interchange = slashing_protection
+                .export()
+                .map_err(|e| format!("{:?}", e))?;
+            let bytes = serde_json::to_vec_pretty(&interchange).map_err(|e| e.to_string())?;
+            fs::write(&path, bytes).map_err(|e| format!("Unable to write {:?}: {}", path, e))
+        }
+        _ => Err("Expected an import or export subcommand.".to_string()),
     }
+}
 
-    // Naively wait for all the threads to complete.
-    for tuple in threads {
-        let (manager, producer) = tuple;
-        let _ = producer.join();
-        let _ = manager.join();
+/// Run a key management subcommand.
+fn keys_command(
+    matches: &ArgMatches,
+    global_matches: &ArgMatches,
+    key_manager: &KeyManager,
+) -> Result<(), String> {
+    let string_err = |e: KeyManagerError| format!("{:?}", e);
+    match matches.subcommand() {
+        ("generate", Some(_)) => {
+            let password = read_password(global_matches)?;
+            let pubkey = key_manager
+                .generate(&password, DEFAULT_PBKDF2_ITERATIONS)
+                .map_err(string_err)?;
+            println!("{}", pubkey);
+        }
+        ("import", Some(matches)) => {
+            let path = PathBuf::from(matches.value_of("file").expect("file is required"));
+            let bytes = fs::read(&path).map_err(|e| format!("Unable to read {:?}: {}", path, e))?;
+            let keystore: Keystore = serde_json::from_slice(&bytes).map_err(|e| e.to_string())?;
+            let password = read_password(global_matches)?;
+            let pubkey = key_manager
+                .import(&keystore, &password)
+                .map_err(string_err)?;
+            println!("{}", pubkey);
+        }
+        ("list", Some(_)) => {
+            for key in key_manager.list().map_err(string_err)? {
+                let status = if key.enabled { "enabled" } else { "disabled" };
+                println!("{} {}", key.pubkey, status);
+            }
+        }
+        ("delete", Some(matches)) => {
+            let pubkey = matches.value_of("pubkey").expect("pubkey is required");
+            key_manager.delete(pubkey).map_err(string_err)?;
+        }
+        ("enable", Some(matches)) => {
+            let pubkey = matches.value_of("pubkey").expect("pubkey is required");
+            key_manager.set_enabled(pubkey, true).map_err(string_err)?;
+        }
+        ("disable", Some(matches)) => {
+            let pubkey = matches.value_of("pubkey").expect("pubkey is required");
+            key_manager.set_enabled(pubkey, false).map_err(string_err)?;
+        }
+        _ => return Err("Expected a keys subcommand, see --help.".to_string()),
+    }
+    Ok(())
+}
+
+/// Print the deposit data of a validator key, as JSON or as the hex SSZ of its `DepositInput`.
+fn deposit_command(
+    matches: &ArgMatches,
+    global_matches: &ArgMatches,
+    key_manager: &KeyManager,
+    spec: &ChainSpec,
+) -> Result<(), String> {
+    let pubkey = matches.value_of("pubkey").expect("pubkey is required");
+    let password = read_password(global_matches)?;
+    let keypair = key_manager
+        .load(pubkey, &password)
+        .map_err(|e| format!("{:?}", e))?;
+
+    let withdrawal_pubkey = match matches.value_of("withdrawal-pubkey") {
+        Some(string) => PublicKey::from_hex_string(string)
+            .ok_or_else(|| format!("Invalid withdrawal public key: {}", string))?,
+        None => keypair.pk.clone(),
+    };
+    let amount = match matches.value_of("amount") {
+        Some(string) => string
+            .parse::<u64>()
+            .map_err(|_| format!("Invalid amount: {}", string))?,
+        None => spec.max_deposit,
+    };
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs();
+
+    let data = deposit::deposit_data(&keypair, &withdrawal_pubkey, amount, timestamp, spec);
+    if matches.is_present("ssz") {
+        println!("0x{}", hex::encode(ssz_encode(&data.deposit_input)));
+    } else {
+        let json = serde_json::to_string_pretty(&data).map_err(|e| e.to_string())?;
+        println!("{}", json);
+    }
+    Ok(())
+}
+
+/// Sign a voluntary exit for a validator key and publish it to the first Beacon Node.
+///
+/// The exit is signed by the remote signer, if one is configured, with the fork reported by the
+/// Beacon Node.
+fn exit_command(
+    matches: &ArgMatches,
+    global_matches: &ArgMatches,
+    config: &ClientConfig,
+    key_manager: &KeyManager,
+    spec: &ChainSpec,
+) -> Result<Exit, String> {
+    let pubkey = matches.value_of("pubkey").expect("pubkey is required");
+    let epoch_str = matches.value_of("epoch").expect("epoch is required");
+    let epoch = epoch_str
+        .parse::<u64>()
+        .map_err(|_| format!("Invalid epoch: {}", epoch_str))?;
+
+    let server = config
+        .servers
+        .first()
+        .ok_or_else(|| "No beacon node configured".to_string())?;
+    let channel = connect(&config.server_tls, server)?;
+    let beacon_node = ValidatorServiceClient::new(channel.clone());
+
+    let status = NodeServiceClient::new(channel)
+        .node_status()
+        .map_err(|e| format!("Unable to get beacon node status: {:?}", e))?;
+    if status.genesis_time != spec.genesis_time {
+        return Err(format!(
+            "Beacon node has genesis time {}, expected {}",
+            status.genesis_time, spec.genesis_time
+        ));
+    }
+
+    let result = match &config.remote_signer {
+        Some(signer_server) => {
+            // Disabled keys may still be exited.
+            let signer = remote_signers(config, signer_server, &HashSet::new())?
+                .into_iter()
+                .find(|signer| signer.public_key().as_hex_string() == pubkey)
+                .ok_or_else(|| format!("Remote signer does not hold {}", pubkey))?;
+            exit::publish_exit(spec, &status.fork, &beacon_node, &*signer, epoch)
+        }
+        None => {
+            let password = read_password(global_matches)?;
+            let keypair = key_manager
+                .load(pubkey, &password)
+                .map_err(|e| format!("{:?}", e))?;
+            exit::publish_exit(
+                spec,
+                &status.fork,
+                &beacon_node,
+                &LocalSigner::new(keypair),
+                epoch,
+            )
+        }
+    };
+    result.map_err(|e| match e {
+        exit::Error::BeaconNodeError(exit::BeaconNodeError::Unimplemented) => {
+            "Beacon node does not yet accept voluntary exits, the exit was not published"
+                .to_string()
+        }
+        e => format!("{:?}", e),
+    })
+}
+
+/// Read the keystore password from the `--password-file`, or prompt for it if not supplied.
+fn read_password(matches: &ArgMatches) -> Result<String, String> {
+    match matches.value_of("password-file") {
+        Some(path) => fs::read_to_string(path)
+            .map(|password| {
+                password
+                    .trim_end_matches(|c| c == '\r' || c == '\n')
+                    .to_string()
+            })
+            .map_err(|e| format!("Unable to read {}: {}", path, e)),
+        None => rpassword::prompt_password_stderr("Keystore password: ").map_err(|e| e.to_string()),
     }
 }
@@ -0,0 +1,20 @@
+/// Stores the configuration for the Prometheus metrics server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsConfig {
+    /// If `false`, the metrics server is not started.
+    pub enabled: bool,
+    /// The host (e.g., an IP address) the server should bind to.
+    pub listen_address: String,
+    /// The TCP port the server should bind to.
+    pub port: u16,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen_address: "127.0.0.1".to_string(),
+            port: 5_064,
+        }
+    }
+}
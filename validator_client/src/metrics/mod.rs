@@ -0,0 +1,200 @@
+mod config;
+mod signer;
+
+use futures::Future;
+use hyper::header::CONTENT_TYPE;
+use hyper::service::service_fn_ok;
+use hyper::{Body, Request, Response, Server, StatusCode};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder,
+};
+use slog::{error, info, Logger};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+pub use self::config::MetricsConfig;
+pub use self::signer::MeteredSigner;
+
+#[derive(Debug)]
+pub enum Error {
+    /// A metric could not be created or encoded.
+    PrometheusError(String),
+    /// The configured host and port do not form a valid socket address.
+    InvalidAddress(String),
+    /// The server could not be bound to the configured address.
+    UnableToBind(SocketAddr, String),
+}
+
+/// The metrics of all validators of this client, labelled by validator public key.
+pub struct Metrics {
+    registry: Registry,
+    block_producer_outcomes: IntCounterVec,
+    attester_outcomes: IntCounterVec,
+    proposed_blocks: IntCounterVec,
+    signing_seconds: HistogramVec,
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self, Error> {
+        let block_producer_outcomes = IntCounterVec::new(
+            Opts::new(
+                "validator_block_producer_outcomes_total",
+                "Final outcomes of block production, once per slot.",
+            ),
+            &["validator", "outcome"],
+        )?;
+        let attester_outcomes = IntCounterVec::new(
+            Opts::new(
+                "validator_attester_outcomes_total",
+                "Final outcomes of attestation, once per slot.",
+            ),
+            &["validator", "outcome"],
+        )?;
+        let proposed_blocks = IntCounterVec::new(
+            Opts::new(
+                "validator_proposed_blocks_total",
+                "Published blocks, by whether they were included in the canonical chain.",
+            ),
+            &["validator", "status"],
+        )?;
+        let signing_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "validator_signing_seconds",
+                "Time taken by the signer to produce a signature.",
+            ),
+            &["validator"],
+        )?;
+
+        let registry = Registry::new();
+        registry.register(Box::new(block_producer_outcomes.clone()))?;
+        registry.register(Box::new(attester_outcomes.clone()))?;
+        registry.register(Box::new(proposed_blocks.clone()))?;
+        registry.register(Box::new(signing_seconds.clone()))?;
+
+        Ok(Self {
+            registry,
+            block_producer_outcomes,
+            attester_outcomes,
+            proposed_blocks,
+            signing_seconds,
+        })
+    }
+
+    /// Count the final outcome of a slot for the block producer of `validator`.
+    pub fn inc_block_producer_outcome(&self, validator: &str, outcome: &str) {
+        self.block_producer_outcomes
+            .with_label_values(&[validator, outcome])
+            .inc();
+    }
+
+    /// Count the final outcome of a slot for the attester of `validator`.
+    pub fn inc_attester_outcome(&self, validator: &str, outcome: &str) {
+        self.attester_outcomes
+            .with_label_values(&[validator, outcome])
+            .inc();
+    }
+
+    /// Count a block published by `validator` which was found to be canonical, or not.
+    pub fn inc_proposed_block(&self, validator: &str, canonical: bool) {
+        let status = if canonical { "canonical" } else { "orphaned" };
+        self.proposed_blocks
+            .with_label_values(&[validator, status])
+            .inc();
+    }
+
+    /// Record the time taken to produce a signature for `validator`.
+    pub fn observe_signing(&self, validator: &str, duration: Duration) {
+        let seconds = duration.as_secs() as f64 + f64::from(duration.subsec_nanos()) / 1e9;
+        self.signing_seconds
+            .with_label_values(&[validator])
+            .observe(seconds);
+    }
+
+    /// Returns all metrics in the Prometheus text format.
+    pub fn encode(&self) -> Result<Vec<u8>, Error> {
+        let mut buffer = vec![];
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+/// Start a HTTP server which serves the `metrics` at `/metrics`.
+///
+/// The server runs on its own thread; the returned handle may be used to join it.
+pub fn start_server(
+    config: &MetricsConfig,
+    metrics: Arc<Metrics>,
+    log: Logger,
+) -> Result<thread::JoinHandle<()>, Error> {
+    let addr: SocketAddr = format!("{}:{}", config.listen_address, config.port)
+        .parse()
+        .map_err(|e| Error::InvalidAddress(format!("{:?}", e)))?;
+
+    let builder = Server::try_bind(&addr).map_err(|e| Error::UnableToBind(addr, e.to_string()))?;
+
+    let server = {
+        let log = log.clone();
+        builder
+            .serve(move || {
+                let metrics = metrics.clone();
+                service_fn_ok(move |req| route(&req, &metrics))
+            })
+            .map_err(move |e| error!(log, "Metrics server failed"; "error" => e.to_string()))
+    };
+
+    info!(log, "Metrics listening on {}", addr);
+
+    Ok(thread::spawn(move || hyper::rt::run(server)))
+}
+
+fn route(req: &Request<Body>, metrics: &Metrics) -> Response<Body> {
+    let (status, body) = match req.uri().path() {
+        "/metrics" => match metrics.encode() {
+            Ok(body) => (StatusCode::OK, body),
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("{:?}", e).into_bytes(),
+            ),
+        },
+        path => (
+            StatusCode::NOT_FOUND,
+            format!("Unknown endpoint: {}", path).into_bytes(),
+        ),
+    };
+
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, TextEncoder::new().format_type())
+        .body(Body::from(body))
+        .expect("Response is valid")
+}
+
+impl From<prometheus::Error> for Error {
+    fn from(e: prometheus::Error) -> Error {
+        Error::PrometheusError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn encoding() {
+        let metrics = Metrics::new().unwrap();
+        metrics.inc_block_producer_outcome("0xab", "block_produced");
+        metrics.inc_block_producer_outcome("0xab", "block_produced");
+        metrics.inc_proposed_block("0xab", false);
+        metrics.observe_signing("0xab", Duration::from_millis(5));
+
+        let text = String::from_utf8(metrics.encode().unwrap()).unwrap();
+        assert!(text.contains(
+            "validator_block_producer_outcomes_total{outcome=\"block_produced\",validator=\"0xab\"} 2"
+        ));
+        assert!(text
+            .contains("validator_proposed_blocks_total{status=\"orphaned\",validator=\"0xab\"} 1"));
+        assert!(text.contains("validator_signing_seconds_count{validator=\"0xab\"} 1"));
+    }
+}
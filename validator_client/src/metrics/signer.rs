@@ -0,0 +1,37 @@
+use super::Metrics;
+use crate::signer::{Error, Signer};
+use bls::{PublicKey, Signature};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// A `Signer` which records the latency of each signature produced by another `Signer`.
+pub struct MeteredSigner<V: Signer> {
+    signer: Arc<V>,
+    metrics: Arc<Metrics>,
+    validator: String,
+}
+
+impl<V: Signer> MeteredSigner<V> {
+    pub fn new(signer: Arc<V>, metrics: Arc<Metrics>) -> Self {
+        let validator = signer.public_key().as_hex_string();
+        Self {
+            signer,
+            metrics,
+            validator,
+        }
+    }
+}
+
+impl<V: Signer> Signer for MeteredSigner<V> {
+    fn public_key(&self) -> &PublicKey {
+        self.signer.public_key()
+    }
+
+    fn sign(&self, message: &[u8], domain: u64, slot: u64) -> Result<Signature, Error> {
+        let start = Instant::now();
+        let result = self.signer.sign(message, domain, slot);
+        self.metrics
+            .observe_signing(&self.validator, start.elapsed());
+        result
+    }
+}
@@ -0,0 +1,28 @@
+use super::traits::{BeaconNode, BeaconNodeError, NodeStatus};
+use protos::services::Empty;
+use protos::services_grpc::NodeServiceClient;
+use types::Fork;
+
+impl BeaconNode for NodeServiceClient {
+    /// Request the status of the Beacon Node (BN).
+    fn node_status(&self) -> Result<NodeStatus, BeaconNodeError> {
+        let reply = self
+            .get_status(&Empty::new())
+            .map_err(|err| BeaconNodeError::RemoteFailure(format!("{:?}", err)))?;
+
+        let fork = reply.get_fork();
+
+        Ok(NodeStatus {
+            genesis_time: reply.get_genesis_time(),
+            fork_version: reply.get_fork_version(),
+            head_slot: reply.get_head_slot(),
+            finalized_slot: reply.get_finalized_slot(),
+            is_syncing: reply.get_is_syncing(),
+            fork: Fork {
+                pre_fork_version: fork.get_pre_fork_version(),
+                post_fork_version: fork.get_post_fork_version(),
+                fork_slot: fork.get_fork_slot(),
+            },
+        })
+    }
+}
@@ -0,0 +1,199 @@
+mod grpc;
+#[cfg(test)]
+mod test_node;
+mod traits;
+
+use slot_clock::SlotClock;
+use spec::ChainSpec;
+use std::sync::{Arc, RwLock};
+use types::Fork;
+
+pub use self::traits::{BeaconNode, BeaconNodeError, NodeStatus};
+
+/// The most recent slot at which the Beacon Node was found to be safe to sign upon, if any.
+///
+/// Signing services must refuse to sign unless this is equal to the present slot.
+pub type VerifiedSlot = Option<u64>;
+
+/// The fork reported by the Beacon Node when it was last verified, if any.
+///
+/// Signing services compute signature domains from this fork.
+pub type VerifiedFork = Option<Fork>;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PollOutcome {
+    /// The node is synced and on the same chain as the validator; signing is permitted.
+    NodeVerified(u64),
+    /// The node reports that it is syncing.
+    NodeSyncing(u64),
+    /// The node has a genesis time which differs from the local `ChainSpec`.
+    GenesisTimeMismatch(u64),
+    /// The head of the node (first value) is too far behind the present slot (second value).
+    HeadTooFarBehind(u64, u64),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    SlotClockError,
+    SlotUnknowable,
+    SlotClockPoisoned,
+    VerifiedSlotPoisoned,
+    VerifiedForkPoisoned,
+    BeaconNodeError(BeaconNodeError),
+}
+
+/// A polling state machine which checks that the Beacon Node is safe to sign upon, recording the
+/// result in a shared `VerifiedSlot` and the fork of the node in a shared `VerifiedFork`.
+///
+/// There is a single `NodeStatusManager` per Beacon Node.
+pub struct NodeStatusManager<T: SlotClock, U: BeaconNode> {
+    pub verified_slot: Arc<RwLock<VerifiedSlot>>,
+    pub verified_fork: Arc<RwLock<VerifiedFork>>,
+    pub spec: Arc<ChainSpec>,
+    pub slot_clock: Arc<RwLock<T>>,
+    pub beacon_node: Arc<U>,
+    /// The maximum number of slots the head of the node may be behind the present slot.
+    pub max_head_distance: u64,
+}
+
+impl<T: SlotClock, U: BeaconNode> NodeStatusManager<T, U> {
+    /// Poll the Beacon Node for its status.
+    ///
+    /// If the node is found to be unsafe, or cannot be contacted, the `VerifiedSlot` and
+    /// `VerifiedFork` are cleared.
+    pub fn poll(&self) -> Result<PollOutcome, Error> {
+        let slot = self
+            .slot_clock
+            .read()
+            .map_err(|_| Error::SlotClockPoisoned)?
+            .present_slot()
+            .map_err(|_| Error::SlotClockError)?
+            .ok_or(Error::SlotUnknowable)?;
+
+        let (outcome, fork) = match self.beacon_node.node_status() {
+            Ok(status) => (Ok(self.check(&status, slot)), Some(status.fork)),
+            Err(e) => (Err(Error::from(e)), None),
+        };
+
+        let mut verified_slot = self
+            .verified_slot
+            .write()
+            .map_err(|_| Error::VerifiedSlotPoisoned)?;
+        let mut verified_fork = self
+            .verified_fork
+            .write()
+            .map_err(|_| Error::VerifiedForkPoisoned)?;
+        match outcome {
+            Ok(PollOutcome::NodeVerified(slot)) => {
+                *verified_slot = Some(slot);
+                *verified_fork = fork;
+            }
+            _ => {
+                *verified_slot = None;
+                *verified_fork = None;
+            }
+        }
+
+        outcome
+    }
+
+    /// Determine if a node with the given `status` is safe to sign upon at `slot`.
+    fn check(&self, status: &NodeStatus, slot: u64) -> PollOutcome {
+        if status.genesis_time != self.spec.genesis_time {
+            PollOutcome::GenesisTimeMismatch(status.genesis_time)
+        } else if status.is_syncing {
+            PollOutcome::NodeSyncing(slot)
+        } else if status.head_slot + self.max_head_distance < slot {
+            PollOutcome::HeadTooFarBehind(status.head_slot, slot)
+        } else {
+            PollOutcome::NodeVerified(slot)
+        }
+    }
+}
+
+impl From<BeaconNodeError> for Error {
+    fn from(e: BeaconNodeError) -> Error {
+        Error::BeaconNodeError(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_node::TestBeaconNode;
+    use super::*;
+    use slot_clock::TestingSlotClock;
+
+    #[test]
+    pub fn polling() {
+        let spec = Arc::new(ChainSpec::foundation());
+        let verified_slot = Arc::new(RwLock::new(None));
+        let verified_fork = Arc::new(RwLock::new(None));
+        let slot_clock = Arc::new(RwLock::new(TestingSlotClock::new(100)));
+        let beacon_node = Arc::new(TestBeaconNode::default());
+
+        let manager = NodeStatusManager {
+            verified_slot: verified_slot.clone(),
+            verified_fork: verified_fork.clone(),
+            spec: spec.clone(),
+            slot_clock: slot_clock.clone(),
+            beacon_node: beacon_node.clone(),
+            max_head_distance: 4,
+        };
+
+        let fork = Fork {
+            pre_fork_version: 1,
+            post_fork_version: 2,
+            fork_slot: 50,
+        };
+        let status = NodeStatus {
+            genesis_time: spec.genesis_time,
+            head_slot: 100,
+            fork: fork.clone(),
+            ..NodeStatus::default()
+        };
+
+        // A synced node...
+        beacon_node.set_next_status_result(Ok(status.clone()));
+        assert_eq!(manager.poll(), Ok(PollOutcome::NodeVerified(100)));
+        assert_eq!(*verified_slot.read().unwrap(), Some(100));
+        assert_eq!(*verified_fork.read().unwrap(), Some(fork));
+
+        // A syncing node...
+        beacon_node.set_next_status_result(Ok(NodeStatus {
+            is_syncing: true,
+            ..status.clone()
+        }));
+        assert_eq!(manager.poll(), Ok(PollOutcome::NodeSyncing(100)));
+        assert_eq!(*verified_slot.read().unwrap(), None);
+        assert_eq!(*verified_fork.read().unwrap(), None);
+
+        // A node on another chain...
+        beacon_node.set_next_status_result(Ok(NodeStatus {
+            genesis_time: spec.genesis_time + 1,
+            ..status.clone()
+        }));
+        assert_eq!(
+            manager.poll(),
+            Ok(PollOutcome::GenesisTimeMismatch(spec.genesis_time + 1))
+        );
+        assert_eq!(*verified_slot.read().unwrap(), None);
+
+        // A node whose head is at the limit...
+        slot_clock.write().unwrap().set_slot(104);
+        beacon_node.set_next_status_result(Ok(status));
+        assert_eq!(manager.poll(), Ok(PollOutcome::NodeVerified(104)));
+
+        // A node whose head is beyond the limit...
+        slot_clock.write().unwrap().set_slot(105);
+        assert_eq!(manager.poll(), Ok(PollOutcome::HeadTooFarBehind(100, 105)));
+        assert_eq!(*verified_slot.read().unwrap(), None);
+
+        // An unreachable node...
+        slot_clock.write().unwrap().set_slot(100);
+        assert_eq!(manager.poll(), Ok(PollOutcome::NodeVerified(100)));
+        let error = BeaconNodeError::RemoteFailure("unreachable".to_string());
+        beacon_node.set_next_status_result(Err(error.clone()));
+        assert_eq!(manager.poll(), Err(Error::BeaconNodeError(error)));
+        assert_eq!(*verified_slot.read().unwrap(), None);
+    }
+}
@@ -0,0 +1,27 @@
+use super::traits::{BeaconNode, BeaconNodeError, NodeStatus};
+use std::sync::RwLock;
+
+type StatusResult = Result<NodeStatus, BeaconNodeError>;
+
+/// A test-only struct used to simulate a Beacon Node.
+#[derive(Default)]
+pub struct TestBeaconNode {
+    pub status_result: RwLock<Option<StatusResult>>,
+}
+
+impl TestBeaconNode {
+    /// Set the result to be returned when `node_status` is called.
+    pub fn set_next_status_result(&self, result: StatusResult) {
+        *self.status_result.write().unwrap() = Some(result);
+    }
+}
+
+impl BeaconNode for TestBeaconNode {
+    /// Returns the value specified by the `set_next_status_result`.
+    fn node_status(&self) -> StatusResult {
+        match *self.status_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: status_result == None"),
+        }
+    }
+}
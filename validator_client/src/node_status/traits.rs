@@ -0,0 +1,24 @@
+use types::Fork;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum BeaconNodeError {
+    RemoteFailure(String),
+}
+
+/// The status of a Beacon Node, as reported by that node.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct NodeStatus {
+    pub genesis_time: u64,
+    pub fork_version: u64,
+    pub head_slot: u64,
+    pub finalized_slot: u64,
+    pub is_syncing: bool,
+    /// The fork of the head state of the node.
+    pub fork: Fork,
+}
+
+/// Defines the methods required to determine the status of a Beacon Node.
+pub trait BeaconNode: Send + Sync {
+    /// Request the present status of the node.
+    fn node_status(&self) -> Result<NodeStatus, BeaconNodeError>;
+}
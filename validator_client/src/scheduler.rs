@@ -0,0 +1,177 @@
+use futures::future::{self, Loop};
+use futures::Future;
+use slog::{error, Logger};
+use slot_clock::SlotClock;
+use std::io;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+use tokio::timer::Delay;
+use tokio_threadpool::blocking;
+
+/// Runs the validator client services as tasks on a single tokio runtime, rather than a thread
+/// per service.
+///
+/// Each task is run at evenly-spaced points within every slot, as determined by a `SlotClock`.
+pub struct Scheduler<T: SlotClock + 'static> {
+    runtime: Runtime,
+    slot_clock: Arc<RwLock<T>>,
+    slot_duration: Duration,
+    log: Logger,
+}
+
+impl<T: SlotClock + 'static> Scheduler<T> {
+    pub fn new(
+        slot_clock: Arc<RwLock<T>>,
+        slot_duration: Duration,
+        log: Logger,
+    ) -> Result<Self, io::Error> {
+        Ok(Self {
+            runtime: Runtime::new()?,
+            slot_clock,
+            slot_duration,
+            log,
+        })
+    }
+
+    /// Spawn a task which calls `f` `ticks_per_slot` times each slot, with the first call of each
+    /// slot made at the start of the slot.
+    ///
+    /// `f` may block (e.g., on a gRPC call to a Beacon Node); it is run such that it does not
+    /// starve the other tasks of the runtime.
+    pub fn spawn<F>(&mut self, name: &'static str, ticks_per_slot: u32, f: F)
+    where
+        F: FnMut() + Send + 'static,
+    {
+        let f = Arc::new(Mutex::new(f));
+        let slot_clock = self.slot_clock.clone();
+        let slot_duration = self.slot_duration;
+        let tick = slot_duration / ticks_per_slot.max(1);
+        let log = self.log.clone();
+
+        let task = future::loop_fn(None, move |last_tick| {
+            let f = f.clone();
+            let (delay, next_tick) = next_tick(&slot_clock, slot_duration, tick, last_tick);
+            let deadline = Instant::now() + delay;
+
+            Delay::new(deadline)
+                .map_err(|e| format!("Timer failed: {:?}", e))
+                .and_then(move |()| {
+                    future::poll_fn(move || {
+                        blocking(|| -> Result<(), String> {
+                            let mut f = f.lock().map_err(|_| "Task poisoned".to_string())?;
+                            (*f)();
+                            Ok(())
+                        })
+                    })
+                    .map_err(|e| format!("Unable to block: {:?}", e))
+                    .and_then(|result: Result<(), String>| result)
+                })
+                .map(move |()| Loop::<(), Option<u64>>::Continue(next_tick))
+        })
+        .map_err(move |e| error!(log, "Scheduled task failed"; "task" => name, "error" => e));
+
+        self.runtime.spawn(task);
+    }
+
+    /// Run the spawned tasks until they are all complete (which, in practice, is forever).
+    pub fn run(self) {
+        let _ = self.runtime.shutdown_on_idle().wait();
+    }
+}
+
+/// Returns the duration until the next multiple of `tick` from the start of a slot, along with
+/// the time of that tick in milliseconds since genesis.
+///
+/// A tick falling on the present instant is due immediately, unless it is `last_tick` (i.e., the
+/// task has already run for it), in which case the following tick is returned. The first tick of
+/// the next slot is never skipped, even if `tick` does not evenly divide `slot_duration`.
+///
+/// If the slot clock cannot be read (e.g., prior to genesis) the full `tick` is returned, so the
+/// clock is read again after that duration.
+fn next_tick<T: SlotClock>(
+    slot_clock: &RwLock<T>,
+    slot_duration: Duration,
+    tick: Duration,
+    last_tick: Option<u64>,
+) -> (Duration, Option<u64>) {
+    let present = slot_clock.read().ok().and_then(|clock| {
+        match (clock.present_slot(), clock.present_slot_offset()) {
+            (Ok(Some(slot)), Ok(Some(offset))) => Some((slot, offset)),
+            _ => None,
+        }
+    });
+
+    match present {
+        Some((slot, offset)) => {
+            let slot_millis = duration_to_millis(slot_duration).max(1);
+            let tick_millis = duration_to_millis(tick).max(1);
+            let offset_millis = duration_to_millis(offset);
+
+            let slot_start = slot * slot_millis;
+            let now = slot_start + offset_millis;
+            let mut next = slot_start + offset_millis / tick_millis * tick_millis;
+            if next < now || Some(next) == last_tick {
+                next = (next + tick_millis).min(slot_start + slot_millis);
+            }
+
+            (Duration::from_millis(next - now), Some(next))
+        }
+        None => (tick, None),
+    }
+}
+
+fn duration_to_millis(duration: Duration) -> u64 {
+    duration.as_secs() * 1000 + u64::from(duration.subsec_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slot_clock::TestingSlotClock;
+
+    #[test]
+    pub fn ticks_are_slot_aligned() {
+        let slot_clock = RwLock::new(TestingSlotClock::new(10));
+        let slot_duration = Duration::from_millis(6_000);
+        let tick = Duration::from_millis(600);
+
+        // At the start of a slot, the tick is due immediately...
+        assert_eq!(
+            next_tick(&slot_clock, slot_duration, tick, None),
+            (Duration::from_millis(0), Some(60_000))
+        );
+
+        // ...unless the task has already run for it.
+        assert_eq!(
+            next_tick(&slot_clock, slot_duration, tick, Some(60_000)),
+            (tick, Some(60_600))
+        );
+
+        // Part-way through a tick, wait for the remainder.
+        slot_clock
+            .write()
+            .unwrap()
+            .set_slot_offset(Duration::from_millis(1_000));
+        assert_eq!(
+            next_tick(&slot_clock, slot_duration, tick, Some(60_600)),
+            (Duration::from_millis(200), Some(61_200))
+        );
+    }
+
+    #[test]
+    pub fn slot_start_is_never_skipped() {
+        let slot_clock = RwLock::new(TestingSlotClock::new(10));
+        let slot_duration = Duration::from_millis(1_000);
+        let tick = Duration::from_millis(600);
+
+        slot_clock
+            .write()
+            .unwrap()
+            .set_slot_offset(Duration::from_millis(700));
+        assert_eq!(
+            next_tick(&slot_clock, slot_duration, tick, Some(10_600)),
+            (Duration::from_millis(300), Some(11_000))
+        );
+    }
+}
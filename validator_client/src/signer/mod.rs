@@ -0,0 +1,51 @@
+mod remote;
+
+use bls::{Keypair, PublicKey, Signature};
+
+pub use self::remote::RemoteSigner;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    RemoteFailure(String),
+    DecodeFailure,
+    /// The signer returned a signature which is not valid for the message and public key.
+    InvalidSignature,
+}
+
+/// Produces signatures for a single validator.
+///
+/// Permits the secret key to be held outside of the validator client process.
+pub trait Signer: Send + Sync {
+    /// The public key of the validator.
+    fn public_key(&self) -> &PublicKey;
+    /// Sign `message` (generally the root of some object) with the given `domain`.
+    ///
+    /// The `slot` at which the message is being signed is supplied so that a remote signer may
+    /// apply its own policy.
+    fn sign(&self, message: &[u8], domain: u64, slot: u64) -> Result<Signature, Error>;
+}
+
+/// Signs with a secret key held in memory.
+pub struct LocalSigner {
+    keypair: Keypair,
+}
+
+impl LocalSigner {
+    pub fn new(keypair: Keypair) -> Self {
+        Self { keypair }
+    }
+}
+
+impl Signer for LocalSigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.keypair.pk
+    }
+
+    fn sign(&self, message: &[u8], domain: u64, _slot: u64) -> Result<Signature, Error> {
+        Ok(Signature::new_with_domain(
+            message,
+            domain,
+            &self.keypair.sk,
+        ))
+    }
+}
@@ -0,0 +1,115 @@
+use super::{Error, Signer};
+use bls::{PublicKey, Signature};
+use protos::services::{Empty, SignRequest};
+use protos::services_grpc::SignerServiceClient;
+use ssz::{ssz_encode, Decodable};
+use std::collections::HashSet;
+
+/// Signs by calling a remote `SignerService` over gRPC.
+pub struct RemoteSigner {
+    client: SignerServiceClient,
+    public_key: PublicKey,
+}
+
+impl RemoteSigner {
+    /// Returns a `RemoteSigner` for each of the keys held by the signing service, except those
+    /// whose hex-encoded public key is in `disabled`.
+    pub fn all(
+        client: &SignerServiceClient,
+        disabled: &HashSet<String>,
+    ) -> Result<Vec<RemoteSigner>, Error> {
+        let reply = client
+            .list_public_keys(&Empty::new())
+            .map_err(|err| Error::RemoteFailure(format!("{:?}", err)))?;
+
+        let mut signers = vec![];
+        for bytes in reply.get_public_keys() {
+            let (public_key, _) =
+                PublicKey::ssz_decode(bytes, 0).map_err(|_| Error::DecodeFailure)?;
+            if !disabled.contains(&public_key.as_hex_string()) {
+                signers.push(RemoteSigner {
+                    client: client.clone(),
+                    public_key,
+                });
+            }
+        }
+        Ok(signers)
+    }
+}
+
+impl Signer for RemoteSigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Request a signature from the signing service.
+    ///
+    /// The signature is verified before it is returned, so a misbehaving service cannot cause
+    /// invalid messages to be published.
+    fn sign(&self, message: &[u8], domain: u64, slot: u64) -> Result<Signature, Error> {
+        let mut req = SignRequest::new();
+        req.set_public_key(ssz_encode(&self.public_key));
+        req.set_message(message.to_vec());
+        req.set_domain(domain);
+        req.set_slot(slot);
+
+        let reply = self
+            .client
+            .sign(&req)
+            .map_err(|err| Error::RemoteFailure(format!("{:?}", err)))?;
+
+        let (signature, _) =
+            Signature::ssz_decode(reply.get_signature(), 0).map_err(|_| Error::DecodeFailure)?;
+
+        if signature.verify_with_domain(message, domain, &self.public_key) {
+            Ok(signature)
+        } else {
+            Err(Error::InvalidSignature)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls::SecretKey;
+    use grpcio::{ChannelBuilder, EnvBuilder};
+    use slog::{o, Logger};
+    use std::sync::Arc;
+
+    #[test]
+    pub fn signs_with_the_reference_signer() {
+        let sk = SecretKey::random();
+        let pk = PublicKey::from_secret_key(&sk);
+        let disabled_sk = SecretKey::random();
+        let disabled_pk = PublicKey::from_secret_key(&disabled_sk);
+        let log = Logger::root(slog::Discard, o!());
+        let server =
+            reference_signer::start_server(vec![sk, disabled_sk], "127.0.0.1", 0, None, log)
+                .unwrap();
+        let (host, port) = server.bind_addrs()[0].clone();
+
+        let env = Arc::new(EnvBuilder::new().build());
+        let ch = ChannelBuilder::new(env).connect(&format!("{}:{}", host, port));
+        let client = SignerServiceClient::new(ch);
+
+        // Disabled keys are not signed with.
+        let disabled: HashSet<_> = vec![disabled_pk.as_hex_string()].into_iter().collect();
+        let signers = RemoteSigner::all(&client, &disabled).unwrap();
+        assert_eq!(signers.len(), 1);
+        assert_eq!(*signers[0].public_key(), pk);
+
+        let signature = signers[0].sign(&[42; 32], 7, 100).unwrap();
+        assert!(signature.verify_with_domain(&[42; 32], 7, &pk));
+
+        // Keys unknown to the service are refused.
+        let unknown = RemoteSigner {
+            client,
+            public_key: PublicKey::from_secret_key(&SecretKey::random()),
+        };
+        match unknown.sign(&[42; 32], 7, 100) {
+            Err(Error::RemoteFailure(_)) => {}
+            other => panic!("Expected a remote failure, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use serde_derive::{Deserialize, Serialize};
+
+/// The version of the interchange format produced by this client.
+pub const INTERCHANGE_VERSION: u64 = 1;
+
+/// A portable record of the messages signed by some set of validators.
+///
+/// This is the format used to move signing history between machines.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interchange {
+    pub version: u64,
+    /// The genesis time of the chain upon which the messages were signed.
+    pub genesis_time: u64,
+    pub data: Vec<ValidatorHistory>,
+}
+
+/// The signing history of a single validator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorHistory {
+    /// The `0x`-prefixed hex encoding of the validators public key.
+    pub pubkey: String,
+    #[serde(default)]
+    pub signed_blocks: Vec<SignedBlock>,
+    #[serde(default)]
+    pub signed_attestations: Vec<SignedAttestation>,
+}
+
+/// A block proposal signed at `slot`.
+///
+/// If known, `signing_root` permits the same proposal to be safely re-signed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedBlock {
+    pub slot: u64,
+    #[serde(default)]
+    pub signing_root: Option<String>,
+}
+
+/// An attestation signed with the given source and target epochs.
+///
+/// If known, `signing_root` permits the same attestation to be safely re-signed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedAttestation {
+    pub source_epoch: u64,
+    pub target_epoch: u64,
+    #[serde(default)]
+    pub signing_root: Option<String>,
+}
+
+impl Interchange {
+    /// Returns an empty interchange for the chain with the given `genesis_time`.
+    pub fn new(genesis_time: u64) -> Self {
+        Self {
+            version: INTERCHANGE_VERSION,
+            genesis_time,
+            data: vec![],
+        }
+    }
+}
@@ -0,0 +1,899 @@
+mod interchange;
+
+use bls::PublicKey;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::btree_map::Entry as MapEntry;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::ops::Bound::{Excluded, Included, Unbounded};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use types::Hash256;
+
+use self::interchange::{SignedAttestation, SignedBlock, ValidatorHistory, INTERCHANGE_VERSION};
+
+pub use self::interchange::Interchange;
+
+/// The file in the data directory which holds the slashing-protection database.
+pub const SLASHING_PROTECTION_FILE: &str = "slashing_protection.log";
+
+/// The version of the on-disk log format.
+const LOG_VERSION: u64 = 1;
+
+/// The number of proposals, and of attestations, retained for each validator. Older messages are
+/// pruned, leaving the oldest retained message as a watermark below which nothing is signed.
+const MAX_HISTORY_LENGTH: usize = 512;
+
+/// The number of appends after which the log is rewritten to contain only the retained history.
+const COMPACTION_INTERVAL: usize = 4_096;
+
+/// The reason a message was refused by the slashing-protection database.
+#[derive(Debug, PartialEq)]
+pub enum NotSafe {
+    /// A different block has already been signed at this slot.
+    DoubleProposal(u64),
+    /// A different attestation has already been signed with this target epoch.
+    DoubleVote(u64),
+    /// The attestation would surround a previously signed attestation.
+    SurroundingVote(u64, u64),
+    /// The attestation would be surrounded by a previously signed attestation.
+    SurroundedVote(u64, u64),
+    /// The source epoch of the attestation is later than its target epoch.
+    InvalidAttestation(u64, u64),
+    /// The slot (first value) is earlier than the oldest retained proposal (second value).
+    ProposalBelowWatermark(u64, u64),
+    /// The source epoch (first value) is earlier than that of every retained attestation
+    /// (second value).
+    SourceBelowWatermark(u64, u64),
+    /// The target epoch (first value) is earlier than that of every retained attestation
+    /// (second value).
+    TargetBelowWatermark(u64, u64),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    NotSafe(NotSafe),
+    IoError(String),
+    InvalidJson(String),
+    UnsupportedVersion(u64),
+    GenesisTimeMismatch(u64, u64),
+    DatabasePoisoned,
+}
+
+/// An on-disk record of the proposals and attestations signed by the validators of this client.
+///
+/// Each `check_and_insert_*` function refuses slashable messages and durably records safe ones
+/// _before_ returning, so a message is never signed unless it has first been written to disk.
+///
+/// Messages are appended to a log, one line per message, which is replayed into a per-validator
+/// index when the database is opened. Checks only lock the history of the validator concerned;
+/// the log itself is only locked while appending.
+///
+/// Only the most recent `MAX_HISTORY_LENGTH` proposals and attestations of each validator are
+/// retained. Messages older than the retained history are refused, which is sufficient to
+/// prevent slashing as no two retained messages may conflict. The log is compacted when opened
+/// and after every `COMPACTION_INTERVAL` appends.
+pub struct SlashingProtection {
+    path: PathBuf,
+    genesis_time: u64,
+    validators: RwLock<HashMap<String, Arc<Mutex<ValidatorRecord>>>>,
+    log: Mutex<Log>,
+    compaction_interval: usize,
+}
+
+impl SlashingProtection {
+    /// Open the database at `path`, creating an empty database if the file does not exist.
+    ///
+    /// Returns an error if the database was created for a chain with a different `genesis_time`.
+    pub fn open(path: &Path, genesis_time: u64) -> Result<Self, Error> {
+        let validators = if path.exists() {
+            let contents = fs::read_to_string(path)?;
+            replay(&contents, genesis_time)?
+        } else {
+            HashMap::new()
+        };
+
+        compact(path, genesis_time, &validators)?;
+
+        let validators = validators
+            .into_iter()
+            .map(|(pubkey, record)| (pubkey, Arc::new(Mutex::new(record))))
+            .collect();
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            genesis_time,
+            validators: RwLock::new(validators),
+            log: Mutex::new(Log::open(path)?),
+            compaction_interval: COMPACTION_INTERVAL,
+        })
+    }
+
+    /// Records a block proposal by `pubkey` at `slot`, returning an error if signing it would be
+    /// slashable.
+    ///
+    /// Re-signing a proposal with the same `signing_root` is permitted.
+    pub fn check_and_insert_proposal(
+        &self,
+        pubkey: &PublicKey,
+        slot: u64,
+        signing_root: &Hash256,
+    ) -> Result<(), Error> {
+        let pubkey = pubkey.as_hex_string();
+        let signing_root = Some(root_to_hex(signing_root));
+
+        let validator = self.validator(&pubkey)?;
+        {
+            let mut record = validator.lock().map_err(|_| Error::DatabasePoisoned)?;
+            if record.check_proposal(slot, &signing_root)? {
+                self.append(&[LogEntry::Block {
+                    pubkey,
+                    slot,
+                    signing_root: signing_root.clone(),
+                }])?;
+                record.insert_block(slot, signing_root);
+            }
+        }
+
+        self.compact_if_required()
+    }
+
+    /// Records an attestation by `pubkey` with the given source and target epochs, returning an
+    /// error if signing it would be slashable.
+    ///
+    /// Re-signing an attestation with the same `signing_root` is permitted.
+    pub fn check_and_insert_attestation(
+        &self,
+        pubkey: &PublicKey,
+        source_epoch: u64,
+        target_epoch: u64,
+        signing_root: &Hash256,
+    ) -> Result<(), Error> {
+        if source_epoch > target_epoch {
+            return Err(Error::NotSafe(NotSafe::InvalidAttestation(
+                source_epoch,
+                target_epoch,
+            )));
+        }
+
+        let pubkey = pubkey.as_hex_string();
+        let signing_root = Some(root_to_hex(signing_root));
+
+        let validator = self.validator(&pubkey)?;
+        {
+            let mut record = validator.lock().map_err(|_| Error::DatabasePoisoned)?;
+            if record.check_attestation(source_epoch, target_epoch, &signing_root)? {
+                self.append(&[LogEntry::Attestation {
+                    pubkey,
+                    source_epoch,
+                    target_epoch,
+                    signing_root: signing_root.clone(),
+                }])?;
+                record.insert_attestation(source_epoch, target_epoch, signing_root);
+            }
+        }
+
+        self.compact_if_required()
+    }
+
+    /// Merges the history in `interchange` into the database.
+    ///
+    /// Returns an error if `interchange` is for a different chain.
+    pub fn import(&self, interchange: &Interchange) -> Result<(), Error> {
+        if interchange.version != INTERCHANGE_VERSION {
+            return Err(Error::UnsupportedVersion(interchange.version));
+        }
+        if interchange.genesis_time != self.genesis_time {
+            return Err(Error::GenesisTimeMismatch(
+                self.genesis_time,
+                interchange.genesis_time,
+            ));
+        }
+
+        for history in &interchange.data {
+            let validator = self.validator(&history.pubkey)?;
+            let mut record = validator.lock().map_err(|_| Error::DatabasePoisoned)?;
+
+            let entries: Vec<LogEntry> = history
+                .signed_blocks
+                .iter()
+                .map(|block| LogEntry::Block {
+                    pubkey: history.pubkey.clone(),
+                    slot: block.slot,
+                    signing_root: block.signing_root.clone(),
+                })
+                .chain(history.signed_attestations.iter().map(|attestation| {
+                    LogEntry::Attestation {
+                        pubkey: history.pubkey.clone(),
+                        source_epoch: attestation.source_epoch,
+                        target_epoch: attestation.target_epoch,
+                        signing_root: attestation.signing_root.clone(),
+                    }
+                }))
+                .collect();
+
+            self.append(&entries)?;
+            for entry in entries {
+                record.apply(entry);
+            }
+        }
+
+        self.compact_if_required()
+    }
+
+    /// Returns the retained history of the database, for transfer to another machine.
+    pub fn export(&self) -> Result<Interchange, Error> {
+        let mut interchange = Interchange::new(self.genesis_time);
+        for (pubkey, validator) in self.sorted_validators()? {
+            let record = validator.lock().map_err(|_| Error::DatabasePoisoned)?;
+            interchange.data.push(record.history(&pubkey));
+        }
+        Ok(interchange)
+    }
+
+    /// Returns the history of `pubkey`, creating an empty history if none exists.
+    fn validator(&self, pubkey: &str) -> Result<Arc<Mutex<ValidatorRecord>>, Error> {
+        if let Some(validator) = self
+            .validators
+            .read()
+            .map_err(|_| Error::DatabasePoisoned)?
+            .get(pubkey)
+        {
+            return Ok(validator.clone());
+        }
+
+        let mut validators = self
+            .validators
+            .write()
+            .map_err(|_| Error::DatabasePoisoned)?;
+        Ok(validators
+            .entry(pubkey.to_string())
+            .or_insert_with(Default::default)
+            .clone())
+    }
+
+    /// Returns the history of every validator, ordered by public key.
+    fn sorted_validators(&self) -> Result<Vec<(String, Arc<Mutex<ValidatorRecord>>)>, Error> {
+        let mut validators: Vec<_> = self
+            .validators
+            .read()
+            .map_err(|_| Error::DatabasePoisoned)?
+            .iter()
+            .map(|(pubkey, validator)| (pubkey.clone(), validator.clone()))
+            .collect();
+        validators.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(validators)
+    }
+
+    /// Durably append `entries` to the log.
+    fn append(&self, entries: &[LogEntry]) -> Result<(), Error> {
+        let mut log = self.log.lock().map_err(|_| Error::DatabasePoisoned)?;
+        log.append(entries)
+    }
+
+    /// Rewrite the log to contain only the retained history, if `COMPACTION_INTERVAL` appends
+    /// have been made since it was last rewritten.
+    ///
+    /// The set of validators is write-locked until the log has been rewritten, so no validator can
+    /// be created (and append to the log) after the snapshot is taken. The history of every
+    /// validator is then locked (in order of public key) before the log, the same order in which a
+    /// single validator and the log are locked when appending.
+    fn compact_if_required(&self) -> Result<(), Error> {
+        let required = {
+            let log = self.log.lock().map_err(|_| Error::DatabasePoisoned)?;
+            log.appends >= self.compaction_interval
+        };
+        if !required {
+            return Ok(());
+        }
+
+        let validators = self
+            .validators
+            .write()
+            .map_err(|_| Error::DatabasePoisoned)?;
+        let mut sorted: Vec<_> = validators.iter().collect();
+        sorted.sort_by(|a, b| a.0.cmp(b.0));
+        let guards = sorted
+            .into_iter()
+            .map(|(pubkey, validator)| {
+                validator
+                    .lock()
+                    .map(|record| (pubkey.clone(), record))
+                    .map_err(|_| Error::DatabasePoisoned)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut log = self.log.lock().map_err(|_| Error::DatabasePoisoned)?;
+        let snapshot: HashMap<String, ValidatorRecord> = guards
+            .iter()
+            .map(|(pubkey, record)| (pubkey.clone(), (**record).clone()))
+            .collect();
+        compact(&self.path, self.genesis_time, &snapshot)?;
+        *log = Log::open(&self.path)?;
+        Ok(())
+    }
+}
+
+/// The retained history of a single validator.
+#[derive(Debug, Clone, Default)]
+struct ValidatorRecord {
+    /// The signing root of each signed proposal, keyed by slot.
+    blocks: BTreeMap<u64, Option<String>>,
+    /// The signing root of each signed attestation, keyed by `(target_epoch, source_epoch)`.
+    attestations: BTreeMap<(u64, u64), Option<String>>,
+}
+
+impl ValidatorRecord {
+    /// Returns `true` if the proposal is safe and not yet recorded, `false` if it is already
+    /// recorded.
+    fn check_proposal(&self, slot: u64, signing_root: &Option<String>) -> Result<bool, NotSafe> {
+        if let Some(prev) = self.blocks.get(&slot) {
+            if prev.is_some() && prev == signing_root {
+                return Ok(false);
+            }
+            return Err(NotSafe::DoubleProposal(slot));
+        }
+
+        match self.blocks.keys().next() {
+            Some(&min_slot) if slot < min_slot => {
+                Err(NotSafe::ProposalBelowWatermark(slot, min_slot))
+            }
+            _ => Ok(true),
+        }
+    }
+
+    /// Returns `true` if the attestation is safe and not yet recorded, `false` if it is already
+    /// recorded.
+    fn check_attestation(
+        &self,
+        source_epoch: u64,
+        target_epoch: u64,
+        signing_root: &Option<String>,
+    ) -> Result<bool, NotSafe> {
+        let same_target = (
+            Included((target_epoch, 0)),
+            Included((target_epoch, u64::max_value())),
+        );
+        if let Some((&(_, prev_source), prev_root)) = self.attestations.range(same_target).next() {
+            if prev_source == source_epoch && prev_root.is_some() && prev_root == signing_root {
+                return Ok(false);
+            }
+            return Err(NotSafe::DoubleVote(target_epoch));
+        }
+
+        let earlier_target = (Unbounded, Excluded((target_epoch, 0)));
+        for (&(prev_target, prev_source), _) in self.attestations.range(earlier_target) {
+            if source_epoch < prev_source {
+                return Err(NotSafe::SurroundingVote(prev_source, prev_target));
+            }
+        }
+
+        let later_target = (Excluded((target_epoch, u64::max_value())), Unbounded);
+        for (&(prev_target, prev_source), _) in self.attestations.range(later_target) {
+            if prev_source < source_epoch {
+                return Err(NotSafe::SurroundedVote(prev_source, prev_target));
+            }
+        }
+
+        if let Some(min_source) = self.attestations.keys().map(|&(_, source)| source).min() {
+            if source_epoch < min_source {
+                return Err(NotSafe::SourceBelowWatermark(source_epoch, min_source));
+            }
+        }
+        if let Some(&(min_target, _)) = self.attestations.keys().next() {
+            if target_epoch < min_target {
+                return Err(NotSafe::TargetBelowWatermark(target_epoch, min_target));
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Records a proposal, pruning the oldest if the history is full.
+    ///
+    /// If a different proposal is already recorded at `slot`, the signing root is forgotten so
+    /// that neither may be re-signed.
+    fn insert_block(&mut self, slot: u64, signing_root: Option<String>) {
+        insert_or_forget(&mut self.blocks, slot, signing_root);
+        while self.blocks.len() > MAX_HISTORY_LENGTH {
+            let oldest = *self.blocks.keys().next().expect("blocks is not empty");
+            self.blocks.remove(&oldest);
+        }
+    }
+
+    /// Records an attestation, pruning the oldest if the history is full.
+    ///
+    /// If the same attestation is already recorded with a different signing root, the signing
+    /// root is forgotten so that neither may be re-signed.
+    fn insert_attestation(
+        &mut self,
+        source_epoch: u64,
+        target_epoch: u64,
+        signing_root: Option<String>,
+    ) {
+        let key = (target_epoch, source_epoch);
+        insert_or_forget(&mut self.attestations, key, signing_root);
+        while self.attestations.len() > MAX_HISTORY_LENGTH {
+            let oldest = *self
+                .attestations
+                .keys()
+                .next()
+                .expect("attestations is not empty");
+            self.attestations.remove(&oldest);
+        }
+    }
+
+    /// Records the message in a log entry, without checking that it is safe.
+    fn apply(&mut self, entry: LogEntry) {
+        match entry {
+            LogEntry::Block {
+                slot, signing_root, ..
+            } => self.insert_block(slot, signing_root),
+            LogEntry::Attestation {
+                source_epoch,
+                target_epoch,
+                signing_root,
+                ..
+            } => self.insert_attestation(source_epoch, target_epoch, signing_root),
+        }
+    }
+
+    /// Returns the retained history in the interchange format.
+    fn history(&self, pubkey: &str) -> ValidatorHistory {
+        ValidatorHistory {
+            pubkey: pubkey.to_string(),
+            signed_blocks: self
+                .blocks
+                .iter()
+                .map(|(&slot, signing_root)| SignedBlock {
+                    slot,
+                    signing_root: signing_root.clone(),
+                })
+                .collect(),
+            signed_attestations: self
+                .attestations
+                .iter()
+                .map(
+                    |(&(target_epoch, source_epoch), signing_root)| SignedAttestation {
+                        source_epoch,
+                        target_epoch,
+                        signing_root: signing_root.clone(),
+                    },
+                )
+                .collect(),
+        }
+    }
+
+    /// Returns a log entry for each retained message.
+    fn entries(&self, pubkey: &str) -> Vec<LogEntry> {
+        let blocks = self
+            .blocks
+            .iter()
+            .map(|(&slot, signing_root)| LogEntry::Block {
+                pubkey: pubkey.to_string(),
+                slot,
+                signing_root: signing_root.clone(),
+            });
+        let attestations =
+            self.attestations
+                .iter()
+                .map(
+                    |(&(target_epoch, source_epoch), signing_root)| LogEntry::Attestation {
+                        pubkey: pubkey.to_string(),
+                        source_epoch,
+                        target_epoch,
+                        signing_root: signing_root.clone(),
+                    },
+                );
+        blocks.chain(attestations).collect()
+    }
+}
+
+/// Insert `value` at `key`, replacing any different existing value with `None`.
+fn insert_or_forget<K: Ord>(map: &mut BTreeMap<K, Option<String>>, key: K, value: Option<String>) {
+    match map.entry(key) {
+        MapEntry::Vacant(entry) => {
+            entry.insert(value);
+        }
+        MapEntry::Occupied(mut entry) => {
+            if *entry.get() != value {
+                entry.insert(None);
+            }
+        }
+    }
+}
+
+/// The first line of the log.
+#[derive(Debug, Serialize, Deserialize)]
+struct LogHeader {
+    version: u64,
+    genesis_time: u64,
+}
+
+/// A single signed message, as recorded in the log.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum LogEntry {
+    Block {
+        pubkey: String,
+        slot: u64,
+        signing_root: Option<String>,
+    },
+    Attestation {
+        pubkey: String,
+        source_epoch: u64,
+        target_epoch: u64,
+        signing_root: Option<String>,
+    },
+}
+
+impl LogEntry {
+    fn pubkey(&self) -> &str {
+        match self {
+            LogEntry::Block { pubkey, .. } => pubkey,
+            LogEntry::Attestation { pubkey, .. } => pubkey,
+        }
+    }
+}
+
+/// The log file, open for appending.
+struct Log {
+    file: File,
+    /// The number of appends since the log was last compacted.
+    appends: usize,
+}
+
+impl Log {
+    fn open(path: &Path) -> Result<Self, Error> {
+        let file = OpenOptions::new().append(true).open(path)?;
+        Ok(Self { file, appends: 0 })
+    }
+
+    /// Write `entries` as a single write, returning once they have been synced to disk.
+    fn append(&mut self, entries: &[LogEntry]) -> Result<(), Error> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let mut bytes = vec![];
+        for entry in entries {
+            serde_json::to_writer(&mut bytes, entry)
+                .map_err(|e| Error::InvalidJson(e.to_string()))?;
+            bytes.push(b'\n');
+        }
+        self.file.write_all(&bytes)?;
+        self.file.sync_data()?;
+        self.appends += entries.len();
+        Ok(())
+    }
+}
+
+/// Rebuild the history of each validator from the `contents` of a log.
+///
+/// A final line without a trailing newline is the result of an interrupted append; it was never
+/// acknowledged, so its message was never signed and the line is ignored if it cannot be parsed.
+fn replay(contents: &str, genesis_time: u64) -> Result<HashMap<String, ValidatorRecord>, Error> {
+    let mut lines = contents.lines();
+    let header: LogHeader = match lines.next() {
+        Some(line) => serde_json::from_str(line).map_err(|e| Error::InvalidJson(e.to_string()))?,
+        None => {
+            return Err(Error::InvalidJson(
+                "Empty slashing protection log".to_string(),
+            ))
+        }
+    };
+    if header.version != LOG_VERSION {
+        return Err(Error::UnsupportedVersion(header.version));
+    }
+    if header.genesis_time != genesis_time {
+        return Err(Error::GenesisTimeMismatch(
+            genesis_time,
+            header.genesis_time,
+        ));
+    }
+
+    let torn_final_line = !contents.ends_with('\n');
+    let mut lines = lines.peekable();
+    let mut validators: HashMap<String, ValidatorRecord> = HashMap::new();
+    while let Some(line) = lines.next() {
+        let entry: LogEntry = match serde_json::from_str(line) {
+            Ok(entry) => entry,
+            Err(_) if torn_final_line && lines.peek().is_none() => break,
+            Err(e) => return Err(Error::InvalidJson(e.to_string())),
+        };
+        validators
+            .entry(entry.pubkey().to_string())
+            .or_insert_with(Default::default)
+            .apply(entry);
+    }
+
+    Ok(validators)
+}
+
+/// Atomically replace the log at `path` with one containing only the retained history of
+/// `validators`.
+///
+/// The new log is written and synced to a temporary file which is then renamed over `path`, so a
+/// crash leaves either the old or the new log intact.
+fn compact(
+    path: &Path,
+    genesis_time: u64,
+    validators: &HashMap<String, ValidatorRecord>,
+) -> Result<(), Error> {
+    let header = LogHeader {
+        version: LOG_VERSION,
+        genesis_time,
+    };
+    let mut bytes = serde_json::to_vec(&header).map_err(|e| Error::InvalidJson(e.to_string()))?;
+    bytes.push(b'\n');
+
+    let mut pubkeys: Vec<_> = validators.keys().collect();
+    pubkeys.sort();
+    for pubkey in pubkeys {
+        for entry in validators[pubkey].entries(pubkey) {
+            serde_json::to_writer(&mut bytes, &entry)
+                .map_err(|e| Error::InvalidJson(e.to_string()))?;
+            bytes.push(b'\n');
+        }
+    }
+
+    let tmp_path = path.with_extension("log.tmp");
+    {
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+    }
+    fs::rename(&tmp_path, path)?;
+
+    // Sync the directory so the rename itself is durable.
+    if let Some(dir) = path.parent() {
+        File::open(dir)?.sync_all()?;
+    }
+
+    Ok(())
+}
+
+/// Decode an `Interchange` from JSON bytes.
+pub fn parse(bytes: &[u8]) -> Result<Interchange, Error> {
+    serde_json::from_slice(bytes).map_err(|e| Error::InvalidJson(e.to_string()))
+}
+
+fn root_to_hex(root: &Hash256) -> String {
+    format!("0x{}", hex::encode(&root[..]))
+}
+
+impl From<NotSafe> for Error {
+    fn from(e: NotSafe) -> Error {
+        Error::NotSafe(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Error {
+        Error::IoError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls::Keypair;
+    use std::env;
+    use std::process;
+    use std::thread;
+
+    const GENESIS_TIME: u64 = 42;
+
+    fn test_path(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("slashing_protection_{}_{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join(SLASHING_PROTECTION_FILE)
+    }
+
+    fn root(byte: u8) -> Hash256 {
+        Hash256::from(&[byte; 32][..])
+    }
+
+    #[test]
+    pub fn proposals() {
+        let db = SlashingProtection::open(&test_path("proposals"), GENESIS_TIME).unwrap();
+        let pk = Keypair::random().pk;
+
+        assert_eq!(db.check_and_insert_proposal(&pk, 10, &root(1)), Ok(()));
+        // The same proposal may be re-signed.
+        assert_eq!(db.check_and_insert_proposal(&pk, 10, &root(1)), Ok(()));
+        assert_eq!(
+            db.check_and_insert_proposal(&pk, 10, &root(2)),
+            Err(Error::NotSafe(NotSafe::DoubleProposal(10)))
+        );
+        assert_eq!(db.check_and_insert_proposal(&pk, 11, &root(2)), Ok(()));
+
+        // Another validator may propose at the same slot.
+        let other = Keypair::random().pk;
+        assert_eq!(db.check_and_insert_proposal(&other, 10, &root(2)), Ok(()));
+    }
+
+    #[test]
+    pub fn attestations() {
+        let db = SlashingProtection::open(&test_path("attestations"), GENESIS_TIME).unwrap();
+        let pk = Keypair::random().pk;
+
+        assert_eq!(db.check_and_insert_attestation(&pk, 2, 3, &root(1)), Ok(()));
+        assert_eq!(db.check_and_insert_attestation(&pk, 2, 3, &root(1)), Ok(()));
+        assert_eq!(
+            db.check_and_insert_attestation(&pk, 2, 3, &root(2)),
+            Err(Error::NotSafe(NotSafe::DoubleVote(3)))
+        );
+        assert_eq!(
+            db.check_and_insert_attestation(&pk, 1, 4, &root(3)),
+            Err(Error::NotSafe(NotSafe::SurroundingVote(2, 3)))
+        );
+        assert_eq!(db.check_and_insert_attestation(&pk, 3, 6, &root(4)), Ok(()));
+        assert_eq!(
+            db.check_and_insert_attestation(&pk, 4, 5, &root(5)),
+            Err(Error::NotSafe(NotSafe::SurroundedVote(3, 6)))
+        );
+        assert_eq!(
+            db.check_and_insert_attestation(&pk, 8, 7, &root(6)),
+            Err(Error::NotSafe(NotSafe::InvalidAttestation(8, 7)))
+        );
+    }
+
+    #[test]
+    pub fn survives_reopen() {
+        let path = test_path("survives_reopen");
+        let pk = Keypair::random().pk;
+        {
+            let db = SlashingProtection::open(&path, GENESIS_TIME).unwrap();
+            db.check_and_insert_proposal(&pk, 10, &root(1)).unwrap();
+        }
+
+        let db = SlashingProtection::open(&path, GENESIS_TIME).unwrap();
+        assert_eq!(
+            db.check_and_insert_proposal(&pk, 10, &root(2)),
+            Err(Error::NotSafe(NotSafe::DoubleProposal(10)))
+        );
+
+        assert!(SlashingProtection::open(&path, GENESIS_TIME + 1).is_err());
+    }
+
+    #[test]
+    pub fn pruning() {
+        let path = test_path("pruning");
+        let pk = Keypair::random().pk;
+        let db = SlashingProtection::open(&path, GENESIS_TIME).unwrap();
+
+        let last = MAX_HISTORY_LENGTH as u64 + 10;
+        for epoch in 10..=last {
+            db.check_and_insert_proposal(&pk, epoch, &root(1)).unwrap();
+            db.check_and_insert_attestation(&pk, epoch - 1, epoch, &root(1))
+                .unwrap();
+        }
+
+        // The oldest messages were pruned and now act as a watermark.
+        let min = last - MAX_HISTORY_LENGTH as u64 + 1;
+        assert_eq!(
+            db.check_and_insert_proposal(&pk, 10, &root(1)),
+            Err(Error::NotSafe(NotSafe::ProposalBelowWatermark(10, min)))
+        );
+        assert_eq!(
+            db.check_and_insert_attestation(&pk, min - 1, min - 1, &root(1)),
+            Err(Error::NotSafe(NotSafe::TargetBelowWatermark(min - 1, min)))
+        );
+        assert_eq!(
+            db.check_and_insert_attestation(&pk, min - 2, min - 1, &root(1)),
+            Err(Error::NotSafe(NotSafe::SourceBelowWatermark(
+                min - 2,
+                min - 1
+            )))
+        );
+        assert_eq!(
+            db.check_and_insert_proposal(&pk, last + 1, &root(1)),
+            Ok(())
+        );
+
+        // Only the retained history is kept on disk after reopening.
+        drop(db);
+        let db = SlashingProtection::open(&path, GENESIS_TIME).unwrap();
+        let lines = fs::read_to_string(&path).unwrap().lines().count();
+        assert_eq!(lines, 1 + 2 * MAX_HISTORY_LENGTH);
+        assert_eq!(
+            db.check_and_insert_proposal(&pk, min, &root(1)),
+            Err(Error::NotSafe(NotSafe::ProposalBelowWatermark(
+                min,
+                min + 1
+            )))
+        );
+    }
+
+    #[test]
+    pub fn validators_created_during_compaction() {
+        let path = test_path("validators_created_during_compaction");
+        let mut db = SlashingProtection::open(&path, GENESIS_TIME).unwrap();
+        db.compaction_interval = 4;
+        let db = Arc::new(db);
+
+        // Each thread creates new validators whilst the others trigger compactions.
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let db = db.clone();
+                thread::spawn(move || {
+                    (0..16)
+                        .map(|slot| {
+                            let pk = Keypair::random().pk;
+                            db.check_and_insert_proposal(&pk, slot, &root(1)).unwrap();
+                            (pk, slot)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let proposals: Vec<_> = threads
+            .into_iter()
+            .flat_map(|thread| thread.join().unwrap())
+            .collect();
+
+        // Every proposal survives a restart.
+        drop(db);
+        let db = SlashingProtection::open(&path, GENESIS_TIME).unwrap();
+        for (pk, slot) in proposals {
+            assert_eq!(
+                db.check_and_insert_proposal(&pk, slot, &root(2)),
+                Err(Error::NotSafe(NotSafe::DoubleProposal(slot)))
+            );
+        }
+    }
+
+    #[test]
+    pub fn interrupted_append() {
+        let path = test_path("interrupted_append");
+        let pk = Keypair::random().pk;
+        {
+            let db = SlashingProtection::open(&path, GENESIS_TIME).unwrap();
+            db.check_and_insert_proposal(&pk, 10, &root(1)).unwrap();
+        }
+
+        // A crash part-way through an append leaves a partial final line.
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"type\":\"block\",\"pubk").unwrap();
+        drop(file);
+
+        let db = SlashingProtection::open(&path, GENESIS_TIME).unwrap();
+        assert_eq!(
+            db.check_and_insert_proposal(&pk, 10, &root(2)),
+            Err(Error::NotSafe(NotSafe::DoubleProposal(10)))
+        );
+        assert_eq!(db.check_and_insert_proposal(&pk, 11, &root(2)), Ok(()));
+    }
+
+    #[test]
+    pub fn import_export() {
+        let pk = Keypair::random().pk;
+
+        let source = SlashingProtection::open(&test_path("export"), GENESIS_TIME).unwrap();
+        source.check_and_insert_proposal(&pk, 10, &root(1)).unwrap();
+        source
+            .check_and_insert_attestation(&pk, 2, 3, &root(1))
+            .unwrap();
+        let json = serde_json::to_vec(&source.export().unwrap()).unwrap();
+
+        let dest = SlashingProtection::open(&test_path("import"), GENESIS_TIME).unwrap();
+        dest.import(&parse(&json).unwrap()).unwrap();
+        assert_eq!(
+            dest.check_and_insert_proposal(&pk, 10, &root(2)),
+            Err(Error::NotSafe(NotSafe::DoubleProposal(10)))
+        );
+        assert_eq!(
+            dest.check_and_insert_attestation(&pk, 2, 3, &root(2)),
+            Err(Error::NotSafe(NotSafe::DoubleVote(3)))
+        );
+
+        let other_chain = Interchange::new(GENESIS_TIME + 1);
+        assert_eq!(
+            dest.import(&other_chain),
+            Err(Error::GenesisTimeMismatch(GENESIS_TIME, GENESIS_TIME + 1))
+        );
+    }
+}